@@ -1,11 +1,12 @@
 use std::{
+    cmp::Ordering,
     fmt::Write,
-    fs::DirEntry,
-    io,
+    fs, io,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use actix_web::{dev::ServiceResponse, HttpRequest, HttpResponse};
+use actix_web::{dev::ServiceResponse, HttpMessage as _, HttpRequest, HttpResponse};
 use percent_encoding::{utf8_percent_encode, CONTROLS};
 use v_htmlescape::escape as escape_html_entity;
 
@@ -26,7 +27,7 @@ impl Directory {
     }
 
     /// Is this entry visible from this directory?
-    pub fn is_visible(&self, entry: &io::Result<DirEntry>) -> bool {
+    pub fn is_visible(&self, entry: &io::Result<fs::DirEntry>) -> bool {
         if let Ok(ref entry) = *entry {
             if let Some(name) = entry.file_name().to_str() {
                 if name.starts_with('.') {
@@ -40,6 +41,122 @@ impl Directory {
         }
         false
     }
+
+    /// Returns the visible entries of this directory, sorted using the order set with
+    /// [`Files::files_listing_sort()`](crate::Files::files_listing_sort) on the service handling
+    /// `req`.
+    ///
+    /// Hidden entries, whose names start with a `.`, are only included if
+    /// [`Files::use_hidden_files()`](crate::Files::use_hidden_files) is set. Symlinks are followed,
+    /// so they are reported with the size and type of their target. Entries whose metadata cannot be
+    /// read, such as broken symlinks, are skipped.
+    pub fn entries(&self, req: &HttpRequest) -> io::Result<Vec<DirEntry>> {
+        let opts = req
+            .extensions()
+            .get::<ListingOptions>()
+            .copied()
+            .unwrap_or_default();
+
+        let mut entries = Vec::new();
+
+        for entry in self.path.read_dir()? {
+            let Ok(entry) = entry else { continue };
+
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') && !opts.hidden_files {
+                continue;
+            }
+
+            let Ok(md) = fs::metadata(entry.path()) else {
+                continue;
+            };
+            let ft = md.file_type();
+            if !(ft.is_dir() || ft.is_file()) {
+                continue;
+            }
+
+            entries.push(DirEntry {
+                name,
+                size: md.len(),
+                is_dir: md.is_dir(),
+                modified: md.modified().ok(),
+            });
+        }
+
+        let (key, order) = opts.sort;
+        entries.sort_by(|a, b| {
+            let ord = match key {
+                SortKey::Name => Ordering::Equal,
+                SortKey::Size => a.size.cmp(&b.size),
+                SortKey::Modified => a.modified.cmp(&b.modified),
+            }
+            .then_with(|| a.name.cmp(&b.name));
+
+            match order {
+                SortOrder::Ascending => ord,
+                SortOrder::Descending => ord.reverse(),
+            }
+        });
+
+        Ok(entries)
+    }
+}
+
+/// An entry of a [`Directory`] listing.
+#[derive(Debug, Clone)]
+pub struct DirEntry {
+    /// File name of the entry.
+    pub name: String,
+
+    /// Size in bytes of the entry.
+    pub size: u64,
+
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+
+    /// Last modification time of the entry, if available.
+    pub modified: Option<SystemTime>,
+}
+
+/// Key used to sort [`Directory::entries()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Sort by file name.
+    Name,
+
+    /// Sort by size, then by file name.
+    Size,
+
+    /// Sort by last modification time, then by file name.
+    Modified,
+}
+
+/// Order used to sort [`Directory::entries()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest, oldest, or alphabetically first entries first.
+    Ascending,
+
+    /// Largest, newest, or alphabetically last entries first.
+    Descending,
+}
+
+/// Directory listing options of a [`Files`](crate::Files) service.
+///
+/// Added to the request extensions before the directory renderer is called.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ListingOptions {
+    pub(crate) sort: (SortKey, SortOrder),
+    pub(crate) hidden_files: bool,
+}
+
+impl Default for ListingOptions {
+    fn default() -> Self {
+        Self {
+            sort: (SortKey::Name, SortOrder::Ascending),
+            hidden_files: false,
+        }
+    }
 }
 
 pub(crate) type DirectoryRenderer =
@@ -64,7 +181,7 @@ macro_rules! encode_file_url {
 /// ```
 macro_rules! encode_file_name {
     ($entry:ident) => {
-        escape_html_entity(&$entry.file_name().to_string_lossy())
+        escape_html_entity(&$entry.name)
     };
 }
 
@@ -76,35 +193,33 @@ pub(crate) fn directory_listing(
     let mut body = String::new();
     let base = Path::new(req.path());
 
-    for entry in dir.path.read_dir()? {
-        if dir.is_visible(&entry) {
-            let entry = entry.unwrap();
-            let p = match entry.path().strip_prefix(&dir.path) {
-                Ok(p) if cfg!(windows) => base.join(p).to_string_lossy().replace('\\', "/"),
-                Ok(p) => base.join(p).to_string_lossy().into_owned(),
-                Err(_) => continue,
-            };
+    // hidden files are never shown in the default listing
+    for entry in dir.entries(req)? {
+        if entry.name.starts_with('.') {
+            continue;
+        }
 
-            // if file is a directory, add '/' to the end of the name
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_dir() {
-                    let _ = write!(
-                        body,
-                        "<li><a href=\"{}\">{}/</a></li>",
-                        encode_file_url!(p),
-                        encode_file_name!(entry),
-                    );
-                } else {
-                    let _ = write!(
-                        body,
-                        "<li><a href=\"{}\">{}</a></li>",
-                        encode_file_url!(p),
-                        encode_file_name!(entry),
-                    );
-                }
-            } else {
-                continue;
-            }
+        let p = if cfg!(windows) {
+            base.join(&entry.name).to_string_lossy().replace('\\', "/")
+        } else {
+            base.join(&entry.name).to_string_lossy().into_owned()
+        };
+
+        // if file is a directory, add '/' to the end of the name
+        if entry.is_dir {
+            let _ = write!(
+                body,
+                "<li><a href=\"{}\">{}/</a></li>",
+                encode_file_url!(p),
+                encode_file_name!(entry),
+            );
+        } else {
+            let _ = write!(
+                body,
+                "<li><a href=\"{}\">{}</a></li>",
+                encode_file_url!(p),
+                encode_file_name!(entry),
+            );
         }
     }
 
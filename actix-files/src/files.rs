@@ -1,5 +1,6 @@
 use std::{
     cell::RefCell,
+    collections::HashMap,
     fmt, io,
     path::{Path, PathBuf},
     rc::Rc,
@@ -13,14 +14,14 @@ use actix_web::{
     error::Error,
     guard::Guard,
     http::header::DispositionType,
-    HttpRequest,
+    HttpRequest, HttpResponse,
 };
 use futures_core::future::LocalBoxFuture;
 
 use crate::{
     directory_listing, named,
     service::{FilesService, FilesServiceInner},
-    Directory, DirectoryRenderer, HttpNewService, MimeOverride, PathFilter,
+    Directory, DirectoryRenderer, HttpNewService, MimeOverride, PathFilter, SortKey, SortOrder,
 };
 
 /// Static files handling service.
@@ -44,11 +45,14 @@ pub struct Files {
     default: Rc<RefCell<Option<Rc<HttpNewService>>>>,
     renderer: Rc<DirectoryRenderer>,
     mime_override: Option<Rc<MimeOverride>>,
+    mime_types: Option<Rc<HashMap<String, mime::Mime>>>,
     path_filter: Option<Rc<PathFilter>>,
     file_flags: named::Flags,
     use_guards: Option<Rc<dyn Guard>>,
     guards: Vec<Rc<dyn Guard>>,
     hidden_files: bool,
+    use_precompressed: bool,
+    listing_sort: (SortKey, SortOrder),
 }
 
 impl fmt::Debug for Files {
@@ -69,10 +73,13 @@ impl Clone for Files {
             file_flags: self.file_flags,
             mount_path: self.mount_path.clone(),
             mime_override: self.mime_override.clone(),
+            mime_types: self.mime_types.clone(),
             path_filter: self.path_filter.clone(),
             use_guards: self.use_guards.clone(),
             guards: self.guards.clone(),
             hidden_files: self.hidden_files,
+            use_precompressed: self.use_precompressed,
+            listing_sort: self.listing_sort,
         }
     }
 }
@@ -114,11 +121,14 @@ impl Files {
             default: Rc::new(RefCell::new(None)),
             renderer: Rc::new(directory_listing),
             mime_override: None,
+            mime_types: None,
             path_filter: None,
             file_flags: named::Flags::default(),
             use_guards: None,
             guards: Vec::new(),
             hidden_files: false,
+            use_precompressed: false,
+            listing_sort: (SortKey::Name, SortOrder::Ascending),
         }
     }
 
@@ -151,6 +161,47 @@ impl Files {
         self
     }
 
+    /// Shows files listing for directories using a custom renderer.
+    ///
+    /// This is a simpler alternative to [`Files::files_listing_renderer()`] that also enables the
+    /// files listing. The renderer can use [`Directory::entries()`] to get the sorted entries of
+    /// the directory, for example to respond with a JSON listing.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_files::{Files, SortKey, SortOrder};
+    /// use actix_web::HttpResponse;
+    ///
+    /// let files = Files::new("/", "./static")
+    ///     .files_listing_sort(SortKey::Modified, SortOrder::Descending)
+    ///     .show_files_listing_with(|dir, req| match dir.entries(req) {
+    ///         Ok(entries) => {
+    ///             let names = entries.iter().map(|entry| entry.name.as_str());
+    ///             HttpResponse::Ok().body(names.collect::<Vec<_>>().join("\n"))
+    ///         }
+    ///         Err(_) => HttpResponse::InternalServerError().finish(),
+    ///     });
+    /// ```
+    pub fn show_files_listing_with<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&Directory, &HttpRequest) -> HttpResponse + 'static,
+    {
+        self.show_index = true;
+        self.renderer =
+            Rc::new(move |dir, req| Ok(ServiceResponse::new(req.clone(), renderer(dir, req))));
+        self
+    }
+
+    /// Sets the order of directory listing entries.
+    ///
+    /// Applies to the default files listing and to entries returned by [`Directory::entries()`].
+    ///
+    /// Default is ascending by name.
+    pub fn files_listing_sort(mut self, key: SortKey, order: SortOrder) -> Self {
+        self.listing_sort = (key, order);
+        self
+    }
+
     /// Specifies MIME override callback.
     pub fn mime_override<F>(mut self, f: F) -> Self
     where
@@ -160,6 +211,34 @@ impl Files {
         self
     }
 
+    /// Sets content types to use for files with the given extensions.
+    ///
+    /// Keys are file extensions without the leading dot, and are matched case-insensitively. Files
+    /// with other extensions keep the content type guessed from their extension. The
+    /// `Content-Disposition` of overridden files is derived from the new content type, and
+    /// [`mime_override`](Self::mime_override) sees the new content type as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use actix_files::Files;
+    ///
+    /// let files = Files::new("/static", ".").mime_types(HashMap::from([
+    ///     ("glb".to_owned(), "model/gltf-binary".parse().unwrap()),
+    ///     ("avif".to_owned(), "image/avif".parse().unwrap()),
+    /// ]));
+    /// ```
+    pub fn mime_types(mut self, mime_types: HashMap<String, mime::Mime>) -> Self {
+        let mime_types = mime_types
+            .into_iter()
+            .map(|(ext, mime)| (ext.to_ascii_lowercase(), mime))
+            .collect();
+
+        self.mime_types = Some(Rc::new(mime_types));
+        self
+    }
+
     /// Sets path filtering closure.
     ///
     /// The path provided to the closure is relative to `serve_from` path.
@@ -220,6 +299,16 @@ impl Files {
         self
     }
 
+    /// Specifies whether to evaluate `If-Modified-Since` and `If-Unmodified-Since` preconditions.
+    ///
+    /// See [`NamedFile::use_date_preconditions()`](crate::NamedFile::use_date_preconditions).
+    ///
+    /// Default is true.
+    pub fn use_date_preconditions(mut self, value: bool) -> Self {
+        self.file_flags.set(named::Flags::DATE_PRECONDITIONS, value);
+        self
+    }
+
     /// Specifies whether text responses should signal a UTF-8 encoding.
     ///
     /// Default is false (but will default to true in a future version).
@@ -316,6 +405,21 @@ impl Files {
         self.hidden_files = true;
         self
     }
+
+    /// Specifies whether to serve precompressed variants of files.
+    ///
+    /// When enabled, a request for `file.js` is served from a `file.js.br` or `file.js.gz` sibling
+    /// file, with the matching `Content-Encoding`, if the client accepts that encoding. The original
+    /// file must also exist; its content type and `Content-Disposition` are used for the response.
+    ///
+    /// Requests with a `Range` header are always served from the original file, since a range of a
+    /// compressed variant is not meaningful to clients expecting a range of the original content.
+    ///
+    /// Default is false.
+    pub fn use_precompressed(mut self, value: bool) -> Self {
+        self.use_precompressed = value;
+        self
+    }
 }
 
 impl HttpServiceFactory for Files {
@@ -363,10 +467,13 @@ impl ServiceFactory<ServiceRequest> for Files {
             default: None,
             renderer: self.renderer.clone(),
             mime_override: self.mime_override.clone(),
+            mime_types: self.mime_types.clone(),
             path_filter: self.path_filter.clone(),
             file_flags: self.file_flags,
             guards: self.use_guards.clone(),
             hidden_files: self.hidden_files,
+            use_precompressed: self.use_precompressed,
+            listing_sort: self.listing_sort,
         };
 
         if let Some(ref default) = *self.default.borrow() {
@@ -427,4 +534,193 @@ mod tests {
             expected_path
         );
     }
+
+    #[actix_web::test]
+    async fn precompressed_variants() {
+        use actix_web::http::header;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.js"), "original").unwrap();
+        std::fs::write(dir.path().join("app.js.gz"), "gzip").unwrap();
+        std::fs::write(dir.path().join("app.js.br"), "brotli").unwrap();
+        std::fs::write(dir.path().join("other.js"), "original").unwrap();
+        std::fs::write(dir.path().join("other.js.gz"), "gzip").unwrap();
+
+        let srv = test::init_service(
+            App::new()
+                .service(Files::new("/enabled", dir.path()).use_precompressed(true))
+                .service(Files::new("/disabled", dir.path())),
+        )
+        .await;
+
+        for (uri, accept_encoding, encoding, body) in [
+            ("/enabled/app.js", "gzip", Some("gzip"), "gzip"),
+            ("/enabled/app.js", "gzip, br", Some("br"), "brotli"),
+            ("/enabled/app.js", "br;q=0.5, gzip", Some("gzip"), "gzip"),
+            ("/enabled/app.js", "deflate", None, "original"),
+            ("/enabled/other.js", "br, gzip;q=0.5", Some("gzip"), "gzip"),
+            ("/disabled/app.js", "gzip, br", None, "original"),
+        ] {
+            let req = TestRequest::with_uri(uri)
+                .insert_header((header::ACCEPT_ENCODING, accept_encoding))
+                .to_request();
+            let res = test::call_service(&srv, req).await;
+
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(
+                res.headers()
+                    .get(header::CONTENT_ENCODING)
+                    .map(|hdr| hdr.to_str().unwrap()),
+                encoding,
+                "{uri} with {accept_encoding}",
+            );
+            assert_eq!(
+                res.headers().get(header::CONTENT_TYPE).unwrap(),
+                "text/javascript"
+            );
+            assert_eq!(test::read_body(res).await, body);
+        }
+
+        // range requests are always served from the original file
+        let req = TestRequest::with_uri("/enabled/app.js")
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .insert_header((header::RANGE, "bytes=0-3"))
+            .to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            res.headers().get(header::CONTENT_ENCODING).unwrap(),
+            "identity"
+        );
+        assert_eq!(res.headers().get(header::VARY).unwrap(), "accept-encoding");
+        assert_eq!(test::read_body(res).await, "orig");
+    }
+
+    #[actix_web::test]
+    async fn files_listing_with_sort() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("a.txt"), "abc").unwrap();
+        std::fs::write(dir.path().join("c.txt"), "ab").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "abcd").unwrap();
+        std::fs::create_dir(dir.path().join("d")).unwrap();
+
+        let render = |dir: &Directory, req: &HttpRequest| {
+            let entries = dir.entries(req).unwrap();
+            let names = entries
+                .iter()
+                .filter(|entry| !entry.is_dir)
+                .map(|entry| format!("{}:{}", entry.name, entry.size))
+                .collect::<Vec<_>>();
+            HttpResponse::Ok().body(names.join(","))
+        };
+
+        let srv = test::init_service(
+            App::new()
+                .service(Files::new("/name", dir.path()).show_files_listing_with(render))
+                .service(
+                    Files::new("/size", dir.path())
+                        .files_listing_sort(SortKey::Size, SortOrder::Descending)
+                        .show_files_listing_with(render),
+                )
+                .service(
+                    Files::new("/hidden", dir.path())
+                        .use_hidden_files()
+                        .show_files_listing_with(render),
+                ),
+        )
+        .await;
+
+        for (uri, expected) in [
+            ("/name/", "a.txt:3,b.txt:1,c.txt:2"),
+            ("/size/", "a.txt:3,c.txt:2,b.txt:1"),
+            ("/hidden/", ".hidden:4,a.txt:3,b.txt:1,c.txt:2"),
+        ] {
+            let req = TestRequest::with_uri(uri).to_request();
+            let res = test::call_service(&srv, req).await;
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(test::read_body(res).await, expected);
+        }
+    }
+
+    #[cfg(unix)]
+    #[actix_web::test]
+    async fn files_listing_with_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "abc").unwrap();
+        std::fs::create_dir(dir.path().join("d")).unwrap();
+        symlink(dir.path().join("a.txt"), dir.path().join("link.txt")).unwrap();
+        symlink(dir.path().join("d"), dir.path().join("link-dir")).unwrap();
+        symlink(dir.path().join("missing"), dir.path().join("broken")).unwrap();
+
+        let render = |dir: &Directory, req: &HttpRequest| {
+            let entries = dir.entries(req).unwrap();
+            let names = entries
+                .iter()
+                .filter(|entry| entry.name != "d")
+                .map(|entry| format!("{}:{}:{}", entry.name, entry.is_dir, entry.size))
+                .collect::<Vec<_>>();
+            HttpResponse::Ok().body(names.join(","))
+        };
+
+        let srv = test::init_service(
+            App::new()
+                .service(Files::new("/custom", dir.path()).show_files_listing_with(render))
+                .service(Files::new("/default", dir.path()).show_files_listing()),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/custom/").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = test::read_body(res).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.starts_with("a.txt:false:3,"));
+        assert!(body.contains("link-dir:true:"));
+        assert!(body.ends_with(",link.txt:false:3"));
+        assert!(!body.contains("broken"));
+
+        let req = TestRequest::with_uri("/default/").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = test::read_body(res).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(body.contains(">link.txt</a>"));
+        assert!(body.contains(">link-dir/</a>"));
+        assert!(!body.contains("broken"));
+    }
+
+    #[actix_web::test]
+    async fn default_files_listing_sort() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("a.txt"), "abc").unwrap();
+        std::fs::write(dir.path().join("c.txt"), "ab").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "abcd").unwrap();
+
+        let srv = test::init_service(
+            App::new().service(
+                Files::new("/", dir.path())
+                    .use_hidden_files()
+                    .files_listing_sort(SortKey::Size, SortOrder::Descending)
+                    .show_files_listing(),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::with_uri("/").to_request();
+        let res = test::call_service(&srv, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let body = test::read_body(res).await;
+        let body = std::str::from_utf8(&body).unwrap();
+        assert!(!body.contains(".hidden"));
+
+        let a = body.find("a.txt").unwrap();
+        let b = body.find("b.txt").unwrap();
+        let c = body.find("c.txt").unwrap();
+        assert!(a < c && c < b);
+    }
 }
@@ -37,11 +37,15 @@ mod range;
 mod service;
 
 pub use self::{
-    chunked::ChunkedReadFile, directory::Directory, files::Files, named::NamedFile,
-    range::HttpRange, service::FilesService,
+    chunked::ChunkedReadFile,
+    directory::{DirEntry, Directory, SortKey, SortOrder},
+    files::Files,
+    named::NamedFile,
+    range::HttpRange,
+    service::FilesService,
 };
 use self::{
-    directory::{directory_listing, DirectoryRenderer},
+    directory::{directory_listing, DirectoryRenderer, ListingOptions},
     error::FilesError,
     path_buf::PathBufWrap,
 };
@@ -64,6 +68,7 @@ type PathFilter = dyn Fn(&Path, &RequestHead) -> bool;
 #[cfg(test)]
 mod tests {
     use std::{
+        collections::HashMap,
         fmt::Write as _,
         fs::{self},
         ops::Add,
@@ -162,6 +167,65 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
     }
 
+    #[actix_rt::test]
+    async fn test_if_none_match_takes_precedence() {
+        let file = NamedFile::open_async("Cargo.toml").await.unwrap();
+        let etag = file.etag().unwrap();
+        let since = header::HttpDate::from(SystemTime::UNIX_EPOCH);
+
+        // date check alone would consider the file modified
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, etag))
+            .insert_header((header::IF_MODIFIED_SINCE, since))
+            .to_http_request();
+        let resp = file.respond_to(&req);
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    }
+
+    #[actix_rt::test]
+    async fn test_if_match_takes_precedence() {
+        let file = NamedFile::open_async("Cargo.toml").await.unwrap();
+        let etag = file.etag().unwrap();
+        let since = header::HttpDate::from(SystemTime::UNIX_EPOCH);
+
+        // date check alone would fail the precondition
+        let req = TestRequest::default()
+            .insert_header((header::IF_MATCH, etag))
+            .insert_header((header::IF_UNMODIFIED_SINCE, since))
+            .to_http_request();
+        let resp = file.respond_to(&req);
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn test_date_preconditions_disabled() {
+        let future = header::HttpDate::from(SystemTime::now().add(Duration::from_secs(60)));
+        let past = header::HttpDate::from(SystemTime::UNIX_EPOCH);
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_MODIFIED_SINCE, future))
+            .to_http_request();
+        let file = NamedFile::open_async("Cargo.toml").await.unwrap();
+        let resp = file.use_date_preconditions(false).respond_to(&req);
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().contains_key(header::LAST_MODIFIED));
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_UNMODIFIED_SINCE, past))
+            .to_http_request();
+        let file = NamedFile::open_async("Cargo.toml").await.unwrap();
+        let resp = file.use_date_preconditions(false).respond_to(&req);
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // ETag preconditions are still evaluated
+        let file = NamedFile::open_async("Cargo.toml").await.unwrap();
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, file.etag().unwrap()))
+            .to_http_request();
+        let resp = file.use_date_preconditions(false).respond_to(&req);
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+    }
+
     #[actix_rt::test]
     async fn test_named_file_text() {
         assert!(NamedFile::open_async("test--").await.is_err());
@@ -446,6 +510,33 @@ mod tests {
         assert_eq!(content_disposition, "attachment; filename=\"Cargo.toml\"");
     }
 
+    #[actix_rt::test]
+    async fn test_mime_types() {
+        let srv = test::init_service(App::new().service(Files::new("/", ".").mime_types(
+            HashMap::from([("BINARY".to_owned(), "image/x-test".parse().unwrap())]),
+        )))
+        .await;
+
+        let request = TestRequest::get().uri("/tests/test.binary").to_request();
+        let response = test::call_service(&srv, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "image/x-test"
+        );
+        assert_eq!(
+            response.headers().get(header::CONTENT_DISPOSITION).unwrap(),
+            "inline; filename=\"test.binary\""
+        );
+
+        let request = TestRequest::get().uri("/tests/test.png").to_request();
+        let response = test::call_service(&srv, request).await;
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "image/png"
+        );
+    }
+
     #[actix_rt::test]
     async fn test_named_file_ranges_status_code() {
         let srv = test::init_service(
@@ -34,12 +34,13 @@ bitflags! {
         const LAST_MD =             0b0000_0010;
         const CONTENT_DISPOSITION = 0b0000_0100;
         const PREFER_UTF8 =         0b0000_1000;
+        const DATE_PRECONDITIONS =  0b0001_0000;
     }
 }
 
 impl Default for Flags {
     fn default() -> Self {
-        Flags::from_bits_truncate(0b0000_1111)
+        Flags::from_bits_truncate(0b0001_1111)
     }
 }
 
@@ -128,16 +129,7 @@ impl NamedFile {
             };
 
             let ct = mime_guess::from_path(&path).first_or_octet_stream();
-
-            let disposition = match ct.type_() {
-                mime::IMAGE | mime::TEXT | mime::AUDIO | mime::VIDEO => DispositionType::Inline,
-                mime::APPLICATION => match ct.subtype() {
-                    mime::JAVASCRIPT | mime::JSON => DispositionType::Inline,
-                    name if name == "wasm" || name == "xhtml" => DispositionType::Inline,
-                    _ => DispositionType::Attachment,
-                },
-                _ => DispositionType::Attachment,
-            };
+            let disposition = default_disposition(&ct);
 
             // replace special characters in filenames which could occur on some filesystems
             let filename_s = filename
@@ -371,6 +363,19 @@ impl NamedFile {
         self
     }
 
+    /// Specifies whether to evaluate `If-Modified-Since` and `If-Unmodified-Since` preconditions.
+    ///
+    /// Disabling these date checks is useful on filesystems with coarse modification times, where
+    /// a file can change without its modification time changing. Preconditions using `ETag`s are
+    /// still evaluated, and take precedence over the date checks when both are present.
+    ///
+    /// Default is true.
+    #[inline]
+    pub fn use_date_preconditions(mut self, value: bool) -> Self {
+        self.flags.set(Flags::DATE_PRECONDITIONS, value);
+        self
+    }
+
     /// Specifies whether text responses should signal a UTF-8 encoding.
     ///
     /// Default is false (but will default to true in a future version).
@@ -457,11 +462,14 @@ impl NamedFile {
             None
         };
 
-        // check preconditions
-        let precondition_failed = if !any_match(etag.as_ref(), req) {
-            true
-        } else if let (Some(ref m), Some(header::IfUnmodifiedSince(ref since))) =
-            (last_modified, req.get_header())
+        let date_preconditions = self.flags.contains(Flags::DATE_PRECONDITIONS);
+
+        // check preconditions; as with If-None-Match below, an If-Match header takes precedence
+        // over its date-based counterpart (RFC 7232 §6)
+        let precondition_failed = if req.headers().contains_key(header::IF_MATCH) {
+            !any_match(etag.as_ref(), req)
+        } else if let (true, Some(ref m), Some(header::IfUnmodifiedSince(ref since))) =
+            (date_preconditions, last_modified, req.get_header())
         {
             let t1: SystemTime = (*m).into();
             let t2: SystemTime = (*since).into();
@@ -479,8 +487,8 @@ impl NamedFile {
             true
         } else if req.headers().contains_key(header::IF_NONE_MATCH) {
             false
-        } else if let (Some(ref m), Some(header::IfModifiedSince(ref since))) =
-            (last_modified, req.get_header())
+        } else if let (true, Some(ref m), Some(header::IfModifiedSince(ref since))) =
+            (date_preconditions, last_modified, req.get_header())
         {
             let t1: SystemTime = (*m).into();
             let t2: SystemTime = (*since).into();
@@ -587,6 +595,19 @@ impl NamedFile {
     }
 }
 
+/// Returns the default `Content-Disposition` type for files of the given content type.
+pub(crate) fn default_disposition(ct: &mime::Mime) -> DispositionType {
+    match ct.type_() {
+        mime::IMAGE | mime::TEXT | mime::AUDIO | mime::VIDEO => DispositionType::Inline,
+        mime::APPLICATION => match ct.subtype() {
+            mime::JAVASCRIPT | mime::JSON => DispositionType::Inline,
+            name if name == "wasm" || name == "xhtml" => DispositionType::Inline,
+            _ => DispositionType::Attachment,
+        },
+        _ => DispositionType::Attachment,
+    }
+}
+
 /// Returns true if `req` has no `If-Match` header or one which matches `etag`.
 fn any_match(etag: Option<&header::EntityTag>, req: &HttpRequest) -> bool {
     match req.get_header::<header::IfMatch>() {
@@ -1,18 +1,27 @@
-use std::{fmt, io, ops::Deref, path::PathBuf, rc::Rc};
+use std::{
+    collections::HashMap,
+    fmt, io,
+    ops::Deref,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 use actix_web::{
     body::BoxBody,
     dev::{self, Service, ServiceRequest, ServiceResponse},
     error::Error,
     guard::Guard,
-    http::{header, Method},
-    HttpResponse,
+    http::{
+        header::{self, ContentEncoding},
+        Method,
+    },
+    HttpMessage as _, HttpResponse,
 };
 use futures_core::future::LocalBoxFuture;
 
 use crate::{
-    named, Directory, DirectoryRenderer, FilesError, HttpService, MimeOverride, NamedFile,
-    PathBufWrap, PathFilter,
+    named, Directory, DirectoryRenderer, FilesError, HttpService, ListingOptions, MimeOverride,
+    NamedFile, PathBufWrap, PathFilter, SortKey, SortOrder,
 };
 
 /// Assembled file serving service.
@@ -35,10 +44,13 @@ pub struct FilesServiceInner {
     pub(crate) default: Option<HttpService>,
     pub(crate) renderer: Rc<DirectoryRenderer>,
     pub(crate) mime_override: Option<Rc<MimeOverride>>,
+    pub(crate) mime_types: Option<Rc<HashMap<String, mime::Mime>>>,
     pub(crate) path_filter: Option<Rc<PathFilter>>,
     pub(crate) file_flags: named::Flags,
     pub(crate) guards: Option<Rc<dyn Guard>>,
     pub(crate) hidden_files: bool,
+    pub(crate) use_precompressed: bool,
+    pub(crate) listing_sort: (SortKey, SortOrder),
 }
 
 impl fmt::Debug for FilesServiceInner {
@@ -70,14 +82,86 @@ impl FilesService {
         named_file.flags = self.file_flags;
 
         let (req, _) = req.into_parts();
-        let res = named_file.into_response(&req);
+        let mut res = named_file.into_response(&req);
+
+        if self.use_precompressed {
+            // response depends on the client's accepted encodings
+            res.headers_mut().append_vary(header::ACCEPT_ENCODING);
+        }
+
         ServiceResponse::new(req, res)
     }
 
+    /// Replaces the guessed content type of `named_file` if one is configured for its extension.
+    fn apply_mime_types(&self, named_file: NamedFile) -> NamedFile {
+        let mime = self.mime_types.as_ref().and_then(|mime_types| {
+            let ext = named_file.path().extension()?.to_str()?;
+            mime_types.get(&ext.to_ascii_lowercase()).cloned()
+        });
+
+        match mime {
+            Some(mime) => {
+                let mut content_disposition = named_file.content_disposition.clone();
+                content_disposition.disposition = named::default_disposition(&mime);
+
+                named_file
+                    .set_content_type(mime)
+                    .set_content_disposition(content_disposition)
+            }
+            None => named_file,
+        }
+    }
+
+    /// Opens the file at `path`, or a precompressed variant of it if enabled and acceptable.
+    async fn open_named_file(&self, path: &Path, req: &ServiceRequest) -> io::Result<NamedFile> {
+        let named_file = self.apply_mime_types(NamedFile::open_async(path).await?);
+
+        if !self.use_precompressed || req.headers().contains_key(header::RANGE) {
+            return Ok(named_file);
+        }
+
+        let Some(accept_encoding) = req.headers().get(header::ACCEPT_ENCODING) else {
+            return Ok(named_file);
+        };
+
+        let mut available = vec![
+            ContentEncoding::Brotli,
+            ContentEncoding::Gzip,
+            ContentEncoding::Identity,
+        ];
+
+        while let Some(encoding) = ContentEncoding::negotiate(accept_encoding, &available) {
+            let ext = match encoding {
+                ContentEncoding::Brotli => "br",
+                ContentEncoding::Gzip => "gz",
+                _ => break,
+            };
+
+            let mut variant_path = path.as_os_str().to_owned();
+            variant_path.push(".");
+            variant_path.push(ext);
+
+            if let Ok(variant) = NamedFile::open_async(variant_path).await {
+                return Ok(variant
+                    .set_content_type(named_file.content_type.clone())
+                    .set_content_disposition(named_file.content_disposition.clone())
+                    .set_content_encoding(encoding));
+            }
+
+            available.retain(|&enc| enc != encoding);
+        }
+
+        Ok(named_file)
+    }
+
     fn show_index(&self, req: ServiceRequest, path: PathBuf) -> ServiceResponse {
         let dir = Directory::new(self.directory.clone(), path);
 
         let (req, _) = req.into_parts();
+        req.extensions_mut().insert(ListingOptions {
+            sort: self.listing_sort,
+            hidden_files: self.hidden_files,
+        });
 
         (self.renderer)(&dir, &req).unwrap_or_else(|err| ServiceResponse::from_err(err, req))
     }
@@ -155,7 +239,7 @@ impl Service<ServiceRequest> for FilesService {
                 match this.index {
                     Some(ref index) => {
                         let named_path = path.join(index);
-                        match NamedFile::open_async(named_path).await {
+                        match this.open_named_file(&named_path, &req).await {
                             Ok(named_file) => Ok(this.serve_named_file(req, named_file)),
                             Err(_) if this.show_index => Ok(this.show_index(req, path)),
                             Err(err) => this.handle_err(err, req).await,
@@ -168,18 +252,8 @@ impl Service<ServiceRequest> for FilesService {
                     )),
                 }
             } else {
-                match NamedFile::open_async(&path).await {
-                    Ok(mut named_file) => {
-                        if let Some(ref mime_override) = this.mime_override {
-                            let new_disposition = mime_override(&named_file.content_type.type_());
-                            named_file.content_disposition.disposition = new_disposition;
-                        }
-                        named_file.flags = this.file_flags;
-
-                        let (req, _) = req.into_parts();
-                        let res = named_file.into_response(&req);
-                        Ok(ServiceResponse::new(req, res))
-                    }
+                match this.open_named_file(&path, &req).await {
+                    Ok(named_file) => Ok(this.serve_named_file(req, named_file)),
                     Err(err) => this.handle_err(err, req).await,
                 }
             }
@@ -52,6 +52,18 @@ impl BoxBody {
         }
     }
 
+    /// Returns a reference to the body's bytes if it is held entirely in memory.
+    ///
+    /// Returns an empty slice for empty bodies and `None` for streaming bodies.
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.0 {
+            BoxBodyInner::None(_) => Some(&[]),
+            BoxBodyInner::Bytes(bytes) => Some(bytes.as_ref()),
+            BoxBodyInner::Stream(_) => None,
+        }
+    }
+
     /// Returns a mutable pinned reference to the inner message body type.
     #[inline]
     pub fn as_pin_mut(&mut self) -> Pin<&mut Self> {
@@ -108,6 +120,16 @@ mod tests {
     assert_impl_all!(BoxBody: fmt::Debug, MessageBody, Unpin);
     assert_not_impl_any!(BoxBody: Send, Sync);
 
+    #[test]
+    fn as_bytes() {
+        assert_eq!(BoxBody::new(()).as_bytes().unwrap(), b"");
+        assert_eq!(BoxBody::new("abc").as_bytes().unwrap(), b"abc");
+
+        let stream =
+            body::BodyStream::new(futures_util::stream::empty::<Result<Bytes, std::io::Error>>());
+        assert!(BoxBody::new(stream).as_bytes().is_none());
+    }
+
     #[actix_rt::test]
     async fn nested_boxed_body() {
         let body = Bytes::from_static(&[1, 2, 3]);
@@ -119,4 +119,16 @@ mod tests {
         let _body: EitherBody<_, ()> = EitherBody::left(());
         let _body: EitherBody<(), _> = EitherBody::right(());
     }
+
+    #[test]
+    fn size() {
+        let body: EitherBody<_, ()> = EitherBody::left("hello");
+        assert_eq!(body.size(), BodySize::Sized(5));
+
+        let body: EitherBody<&str, _> = EitherBody::right(());
+        assert_eq!(body.size(), BodySize::Sized(0));
+
+        let body: EitherBody<&str, _> = EitherBody::right(crate::body::None::new());
+        assert_eq!(body.size(), BodySize::None);
+    }
 }
@@ -0,0 +1,85 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use super::{BodySize, MessageBody};
+
+pin_project! {
+    /// Adapts a [`MessageBody`] into a [`Stream`] of its chunks.
+    ///
+    /// This is the inverse of [`BodyStream`](super::BodyStream). It allows stream combinators to be
+    /// used to transform a body without collecting it.
+    pub struct MessageBodyStream<B> {
+        #[pin]
+        body: B,
+    }
+}
+
+impl<B: MessageBody> MessageBodyStream<B> {
+    /// Constructs new stream of chunks from `body`.
+    #[inline]
+    pub fn new(body: B) -> Self {
+        Self { body }
+    }
+
+    /// Returns the size of the wrapped body.
+    #[inline]
+    pub fn body_size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    /// Returns the wrapped body.
+    #[inline]
+    pub fn into_inner(self) -> B {
+        self.body
+    }
+}
+
+impl<B: MessageBody> Stream for MessageBodyStream<B> {
+    type Item = Result<Bytes, B::Error>;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().body.poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.body.size() {
+            BodySize::None | BodySize::Sized(0) => (0, Some(0)),
+            _ => (0, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt as _;
+
+    use super::*;
+    use crate::body::{to_bytes, BodyStream};
+
+    #[actix_rt::test]
+    async fn yields_body_chunks() {
+        let stream = MessageBodyStream::new(Bytes::from_static(b"hello"));
+        assert_eq!(stream.body_size(), BodySize::Sized(5));
+
+        let chunks = stream.map(Result::unwrap).collect::<Vec<_>>().await;
+        assert_eq!(chunks, [Bytes::from_static(b"hello")]);
+
+        let stream = MessageBodyStream::new(crate::body::None::new());
+        assert!(stream.collect::<Vec<_>>().await.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn round_trip() {
+        let body = BodyStream::new(
+            MessageBodyStream::new("hello world").map(|chunk| chunk.map(|bytes| bytes.slice(6..))),
+        );
+        assert_eq!(to_bytes(body).await.unwrap(), "world");
+    }
+}
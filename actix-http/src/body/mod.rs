@@ -9,6 +9,7 @@ mod body_stream;
 mod boxed;
 mod either;
 mod message_body;
+mod message_body_stream;
 mod none;
 mod size;
 mod sized_stream;
@@ -20,6 +21,7 @@ pub use self::{
     boxed::BoxBody,
     either::EitherBody,
     message_body::MessageBody,
+    message_body_stream::MessageBodyStream,
     none::None,
     size::BodySize,
     sized_stream::SizedStream,
@@ -5,9 +5,9 @@ use actix_service::{IntoServiceFactory, Service, ServiceFactory};
 
 use crate::{
     body::{BoxBody, MessageBody},
-    h1::{self, ExpectHandler, H1Service, UpgradeHandler},
+    h1::{self, ExpectHandler, H1Service, HeadLimits, UpgradeHandler},
     service::HttpService,
-    ConnectCallback, Extensions, KeepAlive, Request, Response, ServiceConfig,
+    ConnectCallback, DateHeader, Extensions, KeepAlive, Request, Response, ServiceConfig,
 };
 
 /// An HTTP service builder.
@@ -19,6 +19,9 @@ pub struct HttpServiceBuilder<T, S, X = ExpectHandler, U = UpgradeHandler> {
     client_disconnect_timeout: Duration,
     secure: bool,
     local_addr: Option<net::SocketAddr>,
+    head_limits: HeadLimits,
+    max_keep_alive_timeout: Duration,
+    date_header: DateHeader,
     expect: X,
     upgrade: Option<U>,
     on_connect_ext: Option<Rc<ConnectCallback<T>>>,
@@ -40,6 +43,9 @@ where
             client_disconnect_timeout: Duration::ZERO,
             secure: false,
             local_addr: None,
+            head_limits: HeadLimits::default(),
+            max_keep_alive_timeout: Duration::from_secs(300),
+            date_header: DateHeader::Auto,
 
             // dispatcher parts
             expect: ExpectHandler,
@@ -124,6 +130,49 @@ where
         self.client_disconnect_timeout(dur)
     }
 
+    /// Set the maximum number of headers accepted in an HTTP/1.x request.
+    ///
+    /// Requests with more headers are rejected with a `431 Request Header Fields Too Large`
+    /// response.
+    ///
+    /// By default, up to 96 headers are accepted.
+    pub fn max_headers(mut self, max_headers: usize) -> Self {
+        self.head_limits.max_headers = max_headers;
+        self
+    }
+
+    /// Set the maximum size, in bytes, of an HTTP/1.x request head.
+    ///
+    /// The request head consists of the request line and all headers. Requests with a larger head
+    /// are rejected with a `431 Request Header Fields Too Large` response.
+    ///
+    /// By default, the limit is 128KiB.
+    pub fn max_header_size(mut self, max_size: usize) -> Self {
+        self.head_limits.max_size = max_size;
+        self
+    }
+
+    /// Set the upper limit for keep-alive timeouts set per response.
+    ///
+    /// Handlers can replace the keep-alive timeout that follows their response using
+    /// [`KeepAliveTimeout`](crate::KeepAliveTimeout), e.g., to keep long-polling connections open
+    /// for longer than usual. Such timeouts are capped at this duration.
+    ///
+    /// By default, the limit is 5 minutes.
+    pub fn max_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.max_keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Set how the `Date` header is added to responses.
+    ///
+    /// By default, the current date is added to responses that do not already have a `Date` header.
+    /// See [`DateHeader`] for the alternatives.
+    pub fn date_header(mut self, date_header: DateHeader) -> Self {
+        self.date_header = date_header;
+        self
+    }
+
     /// Provide service for `EXPECT: 100-Continue` support.
     ///
     /// Service get called with request that contains `EXPECT` header.
@@ -142,6 +191,9 @@ where
             client_disconnect_timeout: self.client_disconnect_timeout,
             secure: self.secure,
             local_addr: self.local_addr,
+            head_limits: self.head_limits,
+            max_keep_alive_timeout: self.max_keep_alive_timeout,
+            date_header: self.date_header,
             expect: expect.into_factory(),
             upgrade: self.upgrade,
             on_connect_ext: self.on_connect_ext,
@@ -166,6 +218,9 @@ where
             client_disconnect_timeout: self.client_disconnect_timeout,
             secure: self.secure,
             local_addr: self.local_addr,
+            head_limits: self.head_limits,
+            max_keep_alive_timeout: self.max_keep_alive_timeout,
+            date_header: self.date_header,
             expect: self.expect,
             upgrade: Some(upgrade.into_factory()),
             on_connect_ext: self.on_connect_ext,
@@ -186,6 +241,19 @@ where
         self
     }
 
+    fn service_config(&self) -> ServiceConfig {
+        ServiceConfig::with_head_limits(
+            self.keep_alive,
+            self.client_request_timeout,
+            self.client_disconnect_timeout,
+            self.secure,
+            self.local_addr,
+            self.head_limits,
+        )
+        .with_max_keep_alive_timeout(self.max_keep_alive_timeout)
+        .with_date_header(self.date_header)
+    }
+
     /// Finish service configuration and create a service for the HTTP/1 protocol.
     pub fn h1<F, B>(self, service: F) -> H1Service<T, S, B, X, U>
     where
@@ -195,13 +263,7 @@ where
         S::InitError: fmt::Debug,
         S::Response: Into<Response<B>>,
     {
-        let cfg = ServiceConfig::new(
-            self.keep_alive,
-            self.client_request_timeout,
-            self.client_disconnect_timeout,
-            self.secure,
-            self.local_addr,
-        );
+        let cfg = self.service_config();
 
         H1Service::with_config(cfg, service.into_factory())
             .expect(self.expect)
@@ -220,13 +282,7 @@ where
 
         B: MessageBody + 'static,
     {
-        let cfg = ServiceConfig::new(
-            self.keep_alive,
-            self.client_request_timeout,
-            self.client_disconnect_timeout,
-            self.secure,
-            self.local_addr,
-        );
+        let cfg = self.service_config();
 
         crate::h2::H2Service::with_config(cfg, service.into_factory())
             .on_connect_ext(self.on_connect_ext)
@@ -242,13 +298,7 @@ where
 
         B: MessageBody + 'static,
     {
-        let cfg = ServiceConfig::new(
-            self.keep_alive,
-            self.client_request_timeout,
-            self.client_disconnect_timeout,
-            self.secure,
-            self.local_addr,
-        );
+        let cfg = self.service_config();
 
         HttpService::with_config(cfg, service.into_factory())
             .expect(self.expect)
@@ -6,7 +6,11 @@ use std::{
 
 use bytes::BytesMut;
 
-use crate::{date::DateService, KeepAlive};
+use crate::{
+    date::{Date, DateHeader, DateService},
+    h1::HeadLimits,
+    KeepAlive,
+};
 
 /// HTTP service configuration.
 #[derive(Debug, Clone)]
@@ -19,6 +23,9 @@ struct Inner {
     client_disconnect_timeout: Duration,
     secure: bool,
     local_addr: Option<std::net::SocketAddr>,
+    head_limits: HeadLimits,
+    max_keep_alive_timeout: Duration,
+    date_header: DateHeader,
     date_service: DateService,
 }
 
@@ -42,6 +49,25 @@ impl ServiceConfig {
         client_disconnect_timeout: Duration,
         secure: bool,
         local_addr: Option<net::SocketAddr>,
+    ) -> ServiceConfig {
+        Self::with_head_limits(
+            keep_alive,
+            client_request_timeout,
+            client_disconnect_timeout,
+            secure,
+            local_addr,
+            HeadLimits::default(),
+        )
+    }
+
+    /// Create instance of `ServiceConfig` with custom limits for HTTP/1.x request heads.
+    pub(crate) fn with_head_limits(
+        keep_alive: KeepAlive,
+        client_request_timeout: Duration,
+        client_disconnect_timeout: Duration,
+        secure: bool,
+        local_addr: Option<net::SocketAddr>,
+        head_limits: HeadLimits,
     ) -> ServiceConfig {
         ServiceConfig(Rc::new(Inner {
             keep_alive: keep_alive.normalize(),
@@ -49,10 +75,29 @@ impl ServiceConfig {
             client_disconnect_timeout,
             secure,
             local_addr,
+            head_limits,
+            max_keep_alive_timeout: Duration::from_secs(300),
+            date_header: DateHeader::Auto,
             date_service: DateService::new(),
         }))
     }
 
+    /// Sets the `Date` header setting of a newly constructed `ServiceConfig`.
+    pub(crate) fn with_date_header(mut self, date_header: DateHeader) -> ServiceConfig {
+        Rc::get_mut(&mut self.0)
+            .expect("service config should not be shared during construction")
+            .date_header = date_header;
+        self
+    }
+
+    /// Sets the maximum per-response keep-alive timeout of a newly constructed `ServiceConfig`.
+    pub(crate) fn with_max_keep_alive_timeout(mut self, timeout: Duration) -> ServiceConfig {
+        Rc::get_mut(&mut self.0)
+            .expect("service config should not be shared during construction")
+            .max_keep_alive_timeout = timeout;
+        self
+    }
+
     /// Returns `true` if connection is secure (i.e., using TLS / HTTPS).
     #[inline]
     pub fn secure(&self) -> bool {
@@ -67,12 +112,42 @@ impl ServiceConfig {
         self.0.local_addr
     }
 
+    /// Returns the maximum number of headers accepted in an HTTP/1.x request.
+    #[inline]
+    pub fn max_headers(&self) -> usize {
+        self.0.head_limits.max_headers
+    }
+
+    /// Returns the maximum size, in bytes, of an HTTP/1.x request head.
+    #[inline]
+    pub fn max_header_size(&self) -> usize {
+        self.0.head_limits.max_size
+    }
+
+    /// Returns the setting for adding a `Date` header to responses.
+    #[inline]
+    pub fn date_header(&self) -> DateHeader {
+        self.0.date_header
+    }
+
+    /// Returns the limits applied while decoding HTTP/1.x request heads.
+    pub(crate) fn head_limits(&self) -> HeadLimits {
+        self.0.head_limits
+    }
+
     /// Connection keep-alive setting.
     #[inline]
     pub fn keep_alive(&self) -> KeepAlive {
         self.0.keep_alive
     }
 
+    /// Returns the upper limit for keep-alive timeouts set per response using
+    /// [`KeepAliveTimeout`](crate::KeepAliveTimeout).
+    #[inline]
+    pub fn max_keep_alive_timeout(&self) -> Duration {
+        self.0.max_keep_alive_timeout
+    }
+
     /// Creates a time object representing the deadline for this connection's keep-alive period, if
     /// enabled.
     ///
@@ -85,6 +160,19 @@ impl ServiceConfig {
         }
     }
 
+    /// Creates a time object representing the deadline for this connection's keep-alive period,
+    /// taking into account a per-response `timeout` override.
+    ///
+    /// The override replaces the configured keep-alive timeout, capped at
+    /// [`max_keep_alive_timeout`](Self::max_keep_alive_timeout), and is ignored when keep-alive is
+    /// disabled.
+    pub(crate) fn keep_alive_deadline_with(&self, timeout: Option<Duration>) -> Option<Instant> {
+        match (self.keep_alive(), timeout) {
+            (KeepAlive::Disabled, _) | (_, None) => self.keep_alive_deadline(),
+            (_, Some(timeout)) => Some(self.now() + timeout.min(self.max_keep_alive_timeout())),
+        }
+    }
+
     /// Creates a time object representing the deadline for the client to finish sending the head of
     /// its first request.
     ///
@@ -107,16 +195,19 @@ impl ServiceConfig {
     /// Writes date header to `dst` buffer.
     ///
     /// Low-level method that utilizes the built-in efficient date service, requiring fewer syscalls
-    /// than normal. Note that a CRLF (`\r\n`) is included in what is written.
+    /// than normal. Note that a CRLF (`\r\n`) is included in what is written. Nothing is written
+    /// when the `Date` header is [disabled](DateHeader::Disabled).
     #[doc(hidden)]
     pub fn write_date_header(&self, dst: &mut BytesMut, camel_case: bool) {
+        if self.0.date_header == DateHeader::Disabled {
+            return;
+        }
+
         let mut buf: [u8; 37] = [0; 37];
 
         buf[..6].copy_from_slice(if camel_case { b"Date: " } else { b"date: " });
 
-        self.0
-            .date_service
-            .with_date(|date| buf[6..35].copy_from_slice(&date.bytes));
+        self.with_date(|date| buf[6..35].copy_from_slice(&date.bytes));
 
         buf[35..].copy_from_slice(b"\r\n");
         dst.extend_from_slice(&buf);
@@ -124,9 +215,14 @@ impl ServiceConfig {
 
     #[allow(unused)] // used with `http2` feature flag
     pub(crate) fn write_date_header_value(&self, dst: &mut BytesMut) {
-        self.0
-            .date_service
-            .with_date(|date| dst.extend_from_slice(&date.bytes));
+        self.with_date(|date| dst.extend_from_slice(&date.bytes));
+    }
+
+    fn with_date<F: FnMut(&Date)>(&self, mut f: F) {
+        match self.0.date_header {
+            DateHeader::Fixed(time) => f(&Date::from_system_time(time)),
+            _ => self.0.date_service.with_date(f),
+        }
     }
 }
 
@@ -141,6 +237,50 @@ mod tests {
     use super::*;
     use crate::{date::DATE_VALUE_LENGTH, notify_on_drop};
 
+    #[actix_rt::test]
+    async fn keep_alive_deadline_override() {
+        let cfg = ServiceConfig::new(
+            KeepAlive::Timeout(Duration::from_secs(5)),
+            Duration::ZERO,
+            Duration::ZERO,
+            false,
+            None,
+        )
+        .with_max_keep_alive_timeout(Duration::from_secs(60));
+        let now = cfg.now();
+
+        assert_eq!(
+            cfg.keep_alive_deadline_with(None),
+            cfg.keep_alive_deadline()
+        );
+        assert_eq!(
+            cfg.keep_alive_deadline_with(Some(Duration::from_secs(1))),
+            Some(now + Duration::from_secs(1))
+        );
+        // longer than the server keep-alive
+        assert_eq!(
+            cfg.keep_alive_deadline_with(Some(Duration::from_secs(30))),
+            Some(now + Duration::from_secs(30))
+        );
+        // capped at the maximum
+        assert_eq!(
+            cfg.keep_alive_deadline_with(Some(Duration::from_secs(600))),
+            Some(now + Duration::from_secs(60))
+        );
+
+        let cfg = ServiceConfig::new(
+            KeepAlive::Disabled,
+            Duration::ZERO,
+            Duration::ZERO,
+            false,
+            None,
+        );
+        assert_eq!(
+            cfg.keep_alive_deadline_with(Some(Duration::from_secs(1))),
+            None
+        );
+    }
+
     #[actix_rt::test]
     async fn test_date_service_update() {
         let settings =
@@ -221,6 +361,22 @@ mod tests {
         assert_eq!(buf1, buf2);
     }
 
+    #[actix_rt::test]
+    async fn test_date_header_setting() {
+        let settings = ServiceConfig::default().with_date_header(DateHeader::Disabled);
+
+        let mut buf = BytesMut::new();
+        settings.write_date_header(&mut buf, false);
+        assert!(buf.is_empty());
+
+        let time = std::time::UNIX_EPOCH + Duration::from_secs(784_111_777);
+        let settings = ServiceConfig::default().with_date_header(DateHeader::Fixed(time));
+
+        let mut buf = BytesMut::new();
+        settings.write_date_header(&mut buf, true);
+        assert_eq!(buf, "Date: Sun, 06 Nov 1994 08:49:37 GMT\r\n");
+    }
+
     #[actix_rt::test]
     async fn test_date_camel_case() {
         let settings = ServiceConfig::default();
@@ -16,20 +16,39 @@ pub(crate) struct Date {
     pos: usize,
 }
 
+/// Controls the `Date` header that is added to responses.
+///
+/// A `Date` header set by the service is always sent as-is; this setting only applies to responses
+/// that do not already have one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DateHeader {
+    /// Add the current date, as maintained by the server's low-resolution clock.
+    #[default]
+    Auto,
+
+    /// Do not add a `Date` header.
+    Disabled,
+
+    /// Add a fixed date.
+    ///
+    /// Useful for deterministic (e.g., snapshot) tests and for replaying archived responses.
+    Fixed(SystemTime),
+}
+
 impl Date {
     fn new() -> Date {
+        Date::from_system_time(SystemTime::now())
+    }
+
+    pub(crate) fn from_system_time(time: SystemTime) -> Date {
         let mut date = Date {
             bytes: [0; DATE_VALUE_LENGTH],
             pos: 0,
         };
-        date.update();
+        write!(date, "{}", httpdate::HttpDate::from(time)).unwrap();
         date
     }
-
-    fn update(&mut self) {
-        self.pos = 0;
-        write!(self, "{}", httpdate::HttpDate::from(SystemTime::now())).unwrap();
-    }
 }
 
 impl fmt::Write for Date {
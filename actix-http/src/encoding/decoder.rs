@@ -15,8 +15,10 @@ use futures_core::{ready, Stream};
 #[cfg(feature = "compress-zstd")]
 use zstd::stream::write::Decoder as ZstdDecoder;
 
+#[cfg(feature = "compress-lz4")]
+use crate::encoding::lz4::Lz4Decoder;
 use crate::{
-    encoding::Writer,
+    encoding::{Writer, WriterLimitExceeded},
     error::PayloadError,
     header::{ContentEncoding, HeaderMap, CONTENT_ENCODING},
 };
@@ -63,6 +65,15 @@ where
                          Please report it to the actix-web repository.",
                 ),
             ))),
+
+            #[cfg(feature = "compress-lz4")]
+            ContentEncoding::Lz4 => Some(ContentDecoder::Lz4(Box::new(
+                Lz4Decoder::new(Writer::new()).expect(
+                    "Failed to create lz4 decoder. This is a bug. \
+                         Please report it to the actix-web repository.",
+                ),
+            ))),
+
             _ => None,
         };
 
@@ -74,6 +85,21 @@ where
         }
     }
 
+    /// Sets maximum number of decoded bytes to produce.
+    ///
+    /// Once the decoded payload grows beyond this limit, decoding stops and the stream yields a
+    /// [`PayloadError::OverflowWithSize`] error. This protects against small compressed payloads that
+    /// expand to a very large size. By default there is no limit.
+    ///
+    /// Has no effect on payloads that are not compressed.
+    pub fn limit(mut self, limit: usize) -> Self {
+        if let Some(ref mut decoder) = self.decoder {
+            decoder.writer_mut().limit = limit;
+        }
+
+        self
+    }
+
     /// Construct decoder based on headers.
     #[inline]
     pub fn from_headers(stream: S, headers: &HeaderMap) -> Decoder<S> {
@@ -99,12 +125,14 @@ where
 
         loop {
             if let Some(ref mut fut) = this.fut {
-                let (chunk, decoder) = ready!(Pin::new(fut).poll(cx)).map_err(|_| {
-                    PayloadError::Io(io::Error::new(
-                        io::ErrorKind::Other,
-                        "Blocking task was cancelled unexpectedly",
-                    ))
-                })??;
+                let (chunk, decoder) = ready!(Pin::new(fut).poll(cx))
+                    .map_err(|_| {
+                        PayloadError::Io(io::Error::new(
+                            io::ErrorKind::Other,
+                            "Blocking task was cancelled unexpectedly",
+                        ))
+                    })?
+                    .map_err(decode_error)?;
 
                 *this.decoder = Some(decoder);
                 this.fut.take();
@@ -124,7 +152,7 @@ where
                 Some(Ok(chunk)) => {
                     if let Some(mut decoder) = this.decoder.take() {
                         if chunk.len() < MAX_CHUNK_SIZE_DECODE_IN_PLACE {
-                            let chunk = decoder.feed_data(chunk)?;
+                            let chunk = decoder.feed_data(chunk).map_err(decode_error)?;
                             *this.decoder = Some(decoder);
 
                             if let Some(chunk) = chunk {
@@ -150,7 +178,7 @@ where
                         match decoder.feed_eof() {
                             Ok(Some(res)) => Poll::Ready(Some(Ok(res))),
                             Ok(None) => Poll::Ready(None),
-                            Err(err) => Poll::Ready(Some(Err(err.into()))),
+                            Err(err) => Poll::Ready(Some(Err(decode_error(err)))),
                         }
                     } else {
                         Poll::Ready(None)
@@ -161,6 +189,20 @@ where
     }
 }
 
+/// Maps a decoder I/O error into a payload error, detecting when the decoded size limit is hit.
+fn decode_error(err: io::Error) -> PayloadError {
+    match err
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<WriterLimitExceeded>())
+    {
+        Some(&WriterLimitExceeded { limit, written }) => PayloadError::OverflowWithSize {
+            limit,
+            read: written,
+        },
+        None => err.into(),
+    }
+}
+
 enum ContentDecoder {
     #[cfg(feature = "compress-gzip")]
     Deflate(Box<ZlibDecoder<Writer>>),
@@ -175,9 +217,31 @@ enum ContentDecoder {
     // argument, and we use `spawn_blocking` in `Decoder::poll_next` that require `FnOnce() -> R + Send + 'static`
     #[cfg(feature = "compress-zstd")]
     Zstd(Box<ZstdDecoder<'static, Writer>>),
+
+    #[cfg(feature = "compress-lz4")]
+    Lz4(Box<Lz4Decoder>),
 }
 
 impl ContentDecoder {
+    fn writer_mut(&mut self) -> &mut Writer {
+        match self {
+            #[cfg(feature = "compress-brotli")]
+            ContentDecoder::Brotli(ref mut decoder) => decoder.get_mut(),
+
+            #[cfg(feature = "compress-gzip")]
+            ContentDecoder::Gzip(ref mut decoder) => decoder.get_mut(),
+
+            #[cfg(feature = "compress-gzip")]
+            ContentDecoder::Deflate(ref mut decoder) => decoder.get_mut(),
+
+            #[cfg(feature = "compress-zstd")]
+            ContentDecoder::Zstd(ref mut decoder) => decoder.get_mut(),
+
+            #[cfg(feature = "compress-lz4")]
+            ContentDecoder::Lz4(ref mut decoder) => decoder.get_mut(),
+        }
+    }
+
     fn feed_eof(&mut self) -> io::Result<Option<Bytes>> {
         match self {
             #[cfg(feature = "compress-brotli")]
@@ -233,6 +297,19 @@ impl ContentDecoder {
                 }
                 Err(err) => Err(err),
             },
+
+            #[cfg(feature = "compress-lz4")]
+            ContentDecoder::Lz4(ref mut decoder) => match decoder.try_finish() {
+                Ok(_) => {
+                    let b = decoder.get_mut().take();
+                    if !b.is_empty() {
+                        Ok(Some(b))
+                    } else {
+                        Ok(None)
+                    }
+                }
+                Err(err) => Err(err),
+            },
         }
     }
 
@@ -297,6 +374,102 @@ impl ContentDecoder {
                 }
                 Err(err) => Err(err),
             },
+
+            #[cfg(feature = "compress-lz4")]
+            ContentDecoder::Lz4(ref mut decoder) => match decoder.write_all(&data) {
+                Ok(_) => {
+                    let b = decoder.get_mut().take();
+                    if !b.is_empty() {
+                        Ok(Some(b))
+                    } else {
+                        Ok(None)
+                    }
+                }
+                Err(err) => Err(err),
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{stream, StreamExt as _};
+
+    use super::*;
+
+    #[cfg(feature = "compress-gzip")]
+    fn gzip(data: &[u8]) -> Bytes {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(data).unwrap();
+        Bytes::from(encoder.finish().unwrap())
+    }
+
+    #[cfg(feature = "compress-gzip")]
+    #[actix_rt::test]
+    async fn decoded_limit() {
+        let compressed = gzip(&[0; 64 * 1024]);
+
+        let payload = stream::iter([Ok::<_, PayloadError>(compressed)]);
+        let mut decoder = Decoder::new(payload, ContentEncoding::Gzip).limit(1024);
+
+        let mut decoded = 0;
+        let err = loop {
+            match decoder.next().await.unwrap() {
+                Ok(chunk) => decoded += chunk.len(),
+                Err(err) => break err,
+            }
+        };
+        match err {
+            PayloadError::OverflowWithSize { limit, read } => {
+                assert_eq!(limit, 1024);
+                assert!(read > limit);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+        assert!(decoded <= 1024);
+
+        let compressed = gzip(b"hello world");
+        let payload = stream::iter([Ok::<_, PayloadError>(compressed)]);
+        let decoder = Decoder::new(payload, ContentEncoding::Gzip).limit(11);
+        let decoded = decoder
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(decoded, b"hello world");
+    }
+
+    #[cfg(feature = "compress-lz4")]
+    #[actix_rt::test]
+    async fn lz4_chunked() {
+        // large enough to span several blocks and fill the decoder's output buffer
+        let data = (0..200_000u32).map(|n| (n % 251) as u8).collect::<Vec<_>>();
+
+        let mut encoder = lz4::EncoderBuilder::new()
+            .block_size(lz4::BlockSize::Max4MB)
+            .build(Vec::new())
+            .unwrap();
+        encoder.write_all(&data).unwrap();
+        let (compressed, res) = encoder.finish();
+        res.unwrap();
+
+        let chunks = compressed
+            .chunks(1000)
+            .map(|chunk| Ok::<_, PayloadError>(Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        let decoded = Decoder::new(stream::iter(chunks), ContentEncoding::Lz4)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await
+            .concat();
+        assert_eq!(decoded, data);
+
+        // truncated frame
+        let truncated = Bytes::copy_from_slice(&compressed[..compressed.len() - 10]);
+        let payload = stream::iter([Ok::<_, PayloadError>(truncated)]);
+        let res = Decoder::new(payload, ContentEncoding::Lz4)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(matches!(res.last(), Some(Err(PayloadError::Incomplete(_)))));
+    }
+}
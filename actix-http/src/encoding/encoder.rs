@@ -5,6 +5,7 @@ use std::{
     future::Future,
     io::{self, Write as _},
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -19,21 +20,28 @@ use tracing::trace;
 #[cfg(feature = "compress-zstd")]
 use zstd::stream::write::Encoder as ZstdEncoder;
 
+#[cfg(feature = "compress-lz4")]
+use super::lz4::Lz4Encoder;
 use super::Writer;
 use crate::{
     body::{self, BodySize, MessageBody},
-    header::{self, ContentEncoding, HeaderValue, CONTENT_ENCODING},
+    header::{self, ContentEncoding, CONTENT_ENCODING},
     ResponseHead, StatusCode,
 };
 
 const MAX_CHUNK_SIZE_ENCODE_IN_PLACE: usize = 1024;
 
+const BROTLI_DEFAULT_WINDOW: u32 = 22;
+const BROTLI_WINDOW_RANGE: std::ops::RangeInclusive<u32> = 10..=24;
+
 pin_project! {
     pub struct Encoder<B> {
         #[pin]
         body: EncoderBody<B>,
         encoder: Option<ContentEncoder>,
         fut: Option<JoinHandle<Result<ContentEncoder, io::Error>>>,
+        flush_per_chunk: bool,
+        max_inline_chunk_size: usize,
         eof: bool,
     }
 }
@@ -46,6 +54,8 @@ impl<B: MessageBody> Encoder<B> {
             },
             encoder: None,
             fut: None,
+            flush_per_chunk: false,
+            max_inline_chunk_size: MAX_CHUNK_SIZE_ENCODE_IN_PLACE,
             eof: true,
         }
     }
@@ -55,22 +65,40 @@ impl<B: MessageBody> Encoder<B> {
             body: EncoderBody::Full { body: Bytes::new() },
             encoder: None,
             fut: None,
+            flush_per_chunk: false,
+            max_inline_chunk_size: MAX_CHUNK_SIZE_ENCODE_IN_PLACE,
             eof: true,
         }
     }
 
     pub fn response(encoding: ContentEncoding, head: &mut ResponseHead, body: B) -> Self {
-        // no need to compress empty bodies
+        Self::response_with_config(encoding, head, body, &ContentEncoderConfig::default())
+    }
+
+    /// Wraps `body` in an encoder using the compression levels from `config`.
+    pub fn response_with_config(
+        encoding: ContentEncoding,
+        head: &mut ResponseHead,
+        body: B,
+        config: &ContentEncoderConfig,
+    ) -> Self {
+        // no need to compress empty bodies or set any encoding headers for them
         match body.size() {
             BodySize::None => return Self::none(),
             BodySize::Sized(0) => return Self::empty(),
             _ => {}
         }
 
-        let should_encode = !(head.headers().contains_key(&CONTENT_ENCODING)
+        let skip_encoding = head.headers().contains_key(&CONTENT_ENCODING)
             || head.status == StatusCode::SWITCHING_PROTOCOLS
-            || head.status == StatusCode::NO_CONTENT
-            || encoding == ContentEncoding::Identity);
+            || head.status == StatusCode::NO_CONTENT;
+
+        let should_encode = !(skip_encoding || encoding == ContentEncoding::Identity);
+
+        if !skip_encoding && encoding == ContentEncoding::Identity && config.identity_header {
+            head.headers_mut()
+                .insert(CONTENT_ENCODING, encoding.to_header_value());
+        }
 
         let body = match body.try_into_bytes() {
             Ok(body) => EncoderBody::Full { body },
@@ -79,13 +107,15 @@ impl<B: MessageBody> Encoder<B> {
 
         if should_encode {
             // wrap body only if encoder is feature-enabled
-            if let Some(enc) = ContentEncoder::select(encoding) {
+            if let Some(enc) = ContentEncoder::select(encoding, config) {
                 update_head(encoding, head);
 
                 return Encoder {
                     body,
                     encoder: Some(enc),
                     fut: None,
+                    flush_per_chunk: config.flush_per_chunk,
+                    max_inline_chunk_size: config.max_inline_chunk_size,
                     eof: false,
                 };
             }
@@ -95,6 +125,8 @@ impl<B: MessageBody> Encoder<B> {
             body,
             encoder: None,
             fut: None,
+            flush_per_chunk: false,
+            max_inline_chunk_size: config.max_inline_chunk_size,
             eof: false,
         }
     }
@@ -206,8 +238,10 @@ where
 
                 Some(Ok(chunk)) => {
                     if let Some(mut encoder) = this.encoder.take() {
-                        if chunk.len() < MAX_CHUNK_SIZE_ENCODE_IN_PLACE {
-                            encoder.write(&chunk).map_err(EncoderError::Io)?;
+                        let flush = *this.flush_per_chunk;
+
+                        if chunk.len() < *this.max_inline_chunk_size {
+                            encoder.write(&chunk, flush).map_err(EncoderError::Io)?;
                             let chunk = encoder.take();
                             *this.encoder = Some(encoder);
 
@@ -216,7 +250,7 @@ where
                             }
                         } else {
                             *this.fut = Some(spawn_blocking(move || {
-                                encoder.write(&chunk)?;
+                                encoder.write(&chunk, flush)?;
                                 Ok(encoder)
                             }));
                         }
@@ -265,12 +299,214 @@ where
 fn update_head(encoding: ContentEncoding, head: &mut ResponseHead) {
     head.headers_mut()
         .insert(header::CONTENT_ENCODING, encoding.to_header_value());
-    head.headers_mut()
-        .append(header::VARY, HeaderValue::from_static("accept-encoding"));
+    head.headers_mut().append_vary(header::ACCEPT_ENCODING);
 
     head.no_chunking(false);
 }
 
+/// Compression levels used when encoding response bodies.
+///
+/// When no config is provided, gzip and deflate use the fastest level, brotli uses quality 3, zstd
+/// uses level 3, and lz4 uses its fast mode.
+///
+/// Levels outside of an algorithm's supported range are clamped to the nearest valid value.
+///
+/// By default, responses that are not encoded are sent without a `Content-Encoding` header. Use
+/// [`identity_header`](Self::identity_header) to send `Content-Encoding: identity` instead, which
+/// some strict proxies expect. The body is passed through unchanged in either case.
+///
+/// A pre-trained zstd dictionary can also be provided, which greatly improves compression ratios
+/// for many small, similar responses. Clients must have the same dictionary to decode the body;
+/// see [`zstd_dictionary`](Self::zstd_dictionary) before enabling this.
+///
+/// # Examples
+/// ```
+/// use actix_http::encoding::ContentEncoderConfig;
+///
+/// let config = ContentEncoderConfig::new().gzip_level(9).brotli_quality(11);
+/// assert_eq!(config.get_gzip_level(), 9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentEncoderConfig {
+    gzip_level: u32,
+    deflate_level: u32,
+    brotli_quality: u32,
+    brotli_window: u32,
+    zstd_level: i32,
+    zstd_dictionary: Option<Arc<[u8]>>,
+    lz4_level: u32,
+    identity_header: bool,
+    flush_per_chunk: bool,
+    max_inline_chunk_size: usize,
+}
+
+impl ContentEncoderConfig {
+    /// Constructs new config with the default compression levels.
+    pub const fn new() -> Self {
+        Self {
+            gzip_level: 1,
+            deflate_level: 1,
+            brotli_quality: 3,
+            brotli_window: BROTLI_DEFAULT_WINDOW,
+            zstd_level: 3,
+            zstd_dictionary: None,
+            lz4_level: 0,
+            identity_header: false,
+            flush_per_chunk: false,
+            max_inline_chunk_size: MAX_CHUNK_SIZE_ENCODE_IN_PLACE,
+        }
+    }
+
+    /// Sets gzip compression level (0–9).
+    pub fn gzip_level(mut self, level: u32) -> Self {
+        self.gzip_level = level.min(9);
+        self
+    }
+
+    /// Sets deflate compression level (0–9).
+    pub fn deflate_level(mut self, level: u32) -> Self {
+        self.deflate_level = level.min(9);
+        self
+    }
+
+    /// Sets brotli compression quality (0–11).
+    pub fn brotli_quality(mut self, quality: u32) -> Self {
+        self.brotli_quality = quality.min(11);
+        self
+    }
+
+    /// Sets brotli window size, as a base-2 logarithm (10–24).
+    ///
+    /// Larger windows can improve compression ratio for large bodies at the cost of memory. Values
+    /// outside of the supported range fall back to the default window size of 22.
+    pub fn brotli_window(mut self, lgwin: u32) -> Self {
+        self.brotli_window = if BROTLI_WINDOW_RANGE.contains(&lgwin) {
+            lgwin
+        } else {
+            BROTLI_DEFAULT_WINDOW
+        };
+        self
+    }
+
+    /// Sets zstd compression level (1–22).
+    pub fn zstd_level(mut self, level: i32) -> Self {
+        self.zstd_level = level.clamp(1, 22);
+        self
+    }
+
+    /// Sets dictionary used by the zstd encoder.
+    ///
+    /// When no dictionary is set, zstd encoding is done without one.
+    ///
+    /// # Warning
+    /// Only use this when every client that can negotiate zstd is known to hold the same
+    /// dictionary, such as first-party apps or internal services. Dictionary-compressed bodies are
+    /// still sent with a plain `Content-Encoding: zstd` header, and no dictionary negotiation (e.g.,
+    /// `Available-Dictionary` / `dcz`) takes place. Browsers and other general-purpose clients that
+    /// advertise `zstd` support will fail to decode these responses.
+    pub fn zstd_dictionary(mut self, dictionary: impl Into<Vec<u8>>) -> Self {
+        self.zstd_dictionary = Some(Arc::from(dictionary.into()));
+        self
+    }
+
+    /// Sets lz4 compression level (0–12).
+    ///
+    /// Level 0 uses the fast mode; levels 3 and above use the slower, high-compression mode.
+    pub fn lz4_level(mut self, level: u32) -> Self {
+        self.lz4_level = level.min(12);
+        self
+    }
+
+    /// Sets whether `Content-Encoding: identity` is sent when a response body is not encoded.
+    ///
+    /// Defaults to false. The header is not added to empty bodies or to responses that already
+    /// have a `Content-Encoding` header.
+    pub fn identity_header(mut self, enabled: bool) -> Self {
+        self.identity_header = enabled;
+        self
+    }
+
+    /// Sets whether the encoder is flushed after each chunk of a streaming body.
+    ///
+    /// By default, compressed output is only emitted once the encoder's internal buffer fills up,
+    /// so clients receive streaming responses, such as server-sent events, in large bursts.
+    /// Flushing after each chunk sends every chunk as soon as it is produced, at the cost of a
+    /// slightly worse compression ratio. Defaults to false.
+    pub fn flush_per_chunk(mut self, enabled: bool) -> Self {
+        self.flush_per_chunk = enabled;
+        self
+    }
+
+    /// Sets the chunk size, in bytes, below which chunks are compressed on the current thread.
+    ///
+    /// Chunks of at least this size are compressed on the blocking thread pool instead. Offloading
+    /// keeps long compression jobs from stalling other connections on the same worker, but each
+    /// offloaded chunk pays the cost of a thread hand-off. Raising the limit lowers latency for
+    /// small servers and cheap compression levels; lowering it protects reactor responsiveness
+    /// when compressing large chunks at high levels. Setting it to 0 offloads every chunk.
+    /// Defaults to 1024.
+    pub fn max_inline_chunk_size(mut self, size: usize) -> Self {
+        self.max_inline_chunk_size = size;
+        self
+    }
+
+    /// Returns configured gzip compression level.
+    pub fn get_gzip_level(&self) -> u32 {
+        self.gzip_level
+    }
+
+    /// Returns configured deflate compression level.
+    pub fn get_deflate_level(&self) -> u32 {
+        self.deflate_level
+    }
+
+    /// Returns configured brotli compression quality.
+    pub fn get_brotli_quality(&self) -> u32 {
+        self.brotli_quality
+    }
+
+    /// Returns configured brotli window size.
+    pub fn get_brotli_window(&self) -> u32 {
+        self.brotli_window
+    }
+
+    /// Returns configured zstd compression level.
+    pub fn get_zstd_level(&self) -> i32 {
+        self.zstd_level
+    }
+
+    /// Returns configured zstd dictionary, if any.
+    pub fn get_zstd_dictionary(&self) -> Option<&[u8]> {
+        self.zstd_dictionary.as_deref()
+    }
+
+    /// Returns configured lz4 compression level.
+    pub fn get_lz4_level(&self) -> u32 {
+        self.lz4_level
+    }
+
+    /// Returns true if `Content-Encoding: identity` is sent for responses that are not encoded.
+    pub fn get_identity_header(&self) -> bool {
+        self.identity_header
+    }
+
+    /// Returns true if the encoder is flushed after each chunk of a streaming body.
+    pub fn get_flush_per_chunk(&self) -> bool {
+        self.flush_per_chunk
+    }
+
+    /// Returns the chunk size below which chunks are compressed on the current thread.
+    pub fn get_max_inline_chunk_size(&self) -> usize {
+        self.max_inline_chunk_size
+    }
+}
+
+impl Default for ContentEncoderConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 enum ContentEncoder {
     #[cfg(feature = "compress-gzip")]
     Deflate(ZlibEncoder<Writer>),
@@ -285,32 +521,50 @@ enum ContentEncoder {
     // use `spawn_blocking` in `Encoder::poll_next` that requires `FnOnce() -> R + Send + 'static`.
     #[cfg(feature = "compress-zstd")]
     Zstd(ZstdEncoder<'static, Writer>),
+
+    #[cfg(feature = "compress-lz4")]
+    Lz4(Box<Lz4Encoder>),
 }
 
 impl ContentEncoder {
-    fn select(encoding: ContentEncoding) -> Option<Self> {
+    #[allow(unused_variables)] // config is unused when no compress features are enabled
+    fn select(encoding: ContentEncoding, config: &ContentEncoderConfig) -> Option<Self> {
         match encoding {
             #[cfg(feature = "compress-gzip")]
             ContentEncoding::Deflate => Some(ContentEncoder::Deflate(ZlibEncoder::new(
                 Writer::new(),
-                flate2::Compression::fast(),
+                flate2::Compression::new(config.deflate_level),
             ))),
 
             #[cfg(feature = "compress-gzip")]
             ContentEncoding::Gzip => Some(ContentEncoder::Gzip(GzEncoder::new(
                 Writer::new(),
-                flate2::Compression::fast(),
+                flate2::Compression::new(config.gzip_level),
             ))),
 
             #[cfg(feature = "compress-brotli")]
-            ContentEncoding::Brotli => Some(ContentEncoder::Brotli(new_brotli_compressor())),
+            ContentEncoding::Brotli => Some(ContentEncoder::Brotli(new_brotli_compressor(
+                config.brotli_quality,
+                config.brotli_window,
+            ))),
 
             #[cfg(feature = "compress-zstd")]
             ContentEncoding::Zstd => {
-                let encoder = ZstdEncoder::new(Writer::new(), 3).ok()?;
+                let encoder = match config.zstd_dictionary {
+                    Some(ref dict) => {
+                        ZstdEncoder::with_dictionary(Writer::new(), config.zstd_level, dict)
+                    }
+                    None => ZstdEncoder::new(Writer::new(), config.zstd_level),
+                }
+                .ok()?;
                 Some(ContentEncoder::Zstd(encoder))
             }
 
+            #[cfg(feature = "compress-lz4")]
+            ContentEncoding::Lz4 => Some(ContentEncoder::Lz4(Box::new(
+                Lz4Encoder::new(Writer::new(), config.lz4_level).ok()?,
+            ))),
+
             _ => None,
         }
     }
@@ -329,6 +583,9 @@ impl ContentEncoder {
 
             #[cfg(feature = "compress-zstd")]
             ContentEncoder::Zstd(ref mut encoder) => encoder.get_mut().take(),
+
+            #[cfg(feature = "compress-lz4")]
+            ContentEncoder::Lz4(ref mut encoder) => encoder.get_mut().take(),
         }
     }
 
@@ -357,13 +614,20 @@ impl ContentEncoder {
                 Ok(writer) => Ok(writer.buf.freeze()),
                 Err(err) => Err(err),
             },
+
+            #[cfg(feature = "compress-lz4")]
+            ContentEncoder::Lz4(encoder) => match encoder.finish() {
+                Ok(writer) => Ok(writer.buf.freeze()),
+                Err(err) => Err(err),
+            },
         }
     }
 
-    fn write(&mut self, data: &[u8]) -> Result<(), io::Error> {
+    /// Writes `data` to the encoder, flushing any buffered output if `flush` is true.
+    fn write(&mut self, data: &[u8], flush: bool) -> Result<(), io::Error> {
         match *self {
             #[cfg(feature = "compress-brotli")]
-            ContentEncoder::Brotli(ref mut encoder) => match encoder.write_all(data) {
+            ContentEncoder::Brotli(ref mut encoder) => match write_all(encoder, data, flush) {
                 Ok(_) => Ok(()),
                 Err(err) => {
                     trace!("Error decoding br encoding: {}", err);
@@ -372,7 +636,7 @@ impl ContentEncoder {
             },
 
             #[cfg(feature = "compress-gzip")]
-            ContentEncoder::Gzip(ref mut encoder) => match encoder.write_all(data) {
+            ContentEncoder::Gzip(ref mut encoder) => match write_all(encoder, data, flush) {
                 Ok(_) => Ok(()),
                 Err(err) => {
                     trace!("Error decoding gzip encoding: {}", err);
@@ -381,7 +645,7 @@ impl ContentEncoder {
             },
 
             #[cfg(feature = "compress-gzip")]
-            ContentEncoder::Deflate(ref mut encoder) => match encoder.write_all(data) {
+            ContentEncoder::Deflate(ref mut encoder) => match write_all(encoder, data, flush) {
                 Ok(_) => Ok(()),
                 Err(err) => {
                     trace!("Error decoding deflate encoding: {}", err);
@@ -390,24 +654,94 @@ impl ContentEncoder {
             },
 
             #[cfg(feature = "compress-zstd")]
-            ContentEncoder::Zstd(ref mut encoder) => match encoder.write_all(data) {
+            ContentEncoder::Zstd(ref mut encoder) => match write_all(encoder, data, flush) {
                 Ok(_) => Ok(()),
                 Err(err) => {
                     trace!("Error decoding ztsd encoding: {}", err);
                     Err(err)
                 }
             },
+
+            #[cfg(feature = "compress-lz4")]
+            ContentEncoder::Lz4(ref mut encoder) => match write_all(encoder, data, flush) {
+                Ok(_) => Ok(()),
+                Err(err) => {
+                    trace!("Error decoding lz4 encoding: {}", err);
+                    Err(err)
+                }
+            },
         }
     }
 }
 
+#[cfg(feature = "__compress")]
+fn write_all(writer: &mut impl io::Write, data: &[u8], flush: bool) -> io::Result<()> {
+    writer.write_all(data)?;
+
+    if flush {
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Compresses `data` in one go using the given content encoding.
+///
+/// Uses the same encoders, and the same default compression levels, as response body compression.
+/// When `level` is provided, it is used as the gzip or deflate level (0–9), brotli quality (0–11),
+/// zstd level (1–22), or lz4 level (0–12), with out-of-range values clamped to the nearest valid level.
+///
+/// Identity encoding returns a copy of `data`.
+///
+/// # Errors
+/// Returns an error with kind [`Unsupported`](io::ErrorKind::Unsupported) if `encoding` is not
+/// supported by the enabled crate features, or any error produced by the encoder.
+///
+/// # Examples
+/// ```
+/// use actix_http::{encoding, header::ContentEncoding};
+///
+/// let compressed = encoding::compress(ContentEncoding::Identity, b"hello", None).unwrap();
+/// assert_eq!(compressed, "hello");
+/// ```
+pub fn compress(encoding: ContentEncoding, data: &[u8], level: Option<i32>) -> io::Result<Bytes> {
+    if encoding == ContentEncoding::Identity {
+        return Ok(Bytes::copy_from_slice(data));
+    }
+
+    let mut config = ContentEncoderConfig::new();
+
+    if let Some(level) = level {
+        let unsigned = u32::try_from(level).unwrap_or(0);
+
+        config = match encoding {
+            ContentEncoding::Deflate => config.deflate_level(unsigned),
+            ContentEncoding::Gzip => config.gzip_level(unsigned),
+            ContentEncoding::Brotli => config.brotli_quality(unsigned),
+            ContentEncoding::Zstd => config.zstd_level(level),
+            ContentEncoding::Lz4 => config.lz4_level(unsigned),
+            _ => config,
+        };
+    }
+
+    let mut encoder = ContentEncoder::select(encoding, &config).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("unsupported content encoding: {}", encoding.as_str()),
+        )
+    })?;
+
+    encoder.write(data, false)?;
+    encoder.finish()
+}
+
 #[cfg(feature = "compress-brotli")]
-fn new_brotli_compressor() -> Box<brotli::CompressorWriter<Writer>> {
+fn new_brotli_compressor(quality: u32, lgwin: u32) -> Box<brotli::CompressorWriter<Writer>> {
     Box::new(brotli::CompressorWriter::new(
         Writer::new(),
         32 * 1024, // 32 KiB buffer
-        3,         // BROTLI_PARAM_QUALITY
-        22,        // BROTLI_PARAM_LGWIN
+        quality,   // BROTLI_PARAM_QUALITY
+        lgwin,     // BROTLI_PARAM_LGWIN
     ))
 }
 
@@ -437,3 +771,278 @@ impl From<EncoderError> for crate::Error {
         crate::Error::new_encoder().with_cause(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::HeaderValue;
+
+    #[test]
+    fn brotli_window_out_of_range() {
+        let config = ContentEncoderConfig::new();
+        assert_eq!(config.get_brotli_window(), 22);
+        assert_eq!(config.clone().brotli_window(24).get_brotli_window(), 24);
+        assert_eq!(config.clone().brotli_window(10).get_brotli_window(), 10);
+        assert_eq!(config.clone().brotli_window(9).get_brotli_window(), 22);
+        assert_eq!(config.clone().brotli_window(25).get_brotli_window(), 22);
+    }
+
+    #[actix_rt::test]
+    async fn compress_round_trip() {
+        use futures_util::{stream, StreamExt as _};
+
+        use crate::encoding::Decoder;
+
+        let data = b"hello world ".repeat(100);
+
+        assert_eq!(
+            compress(ContentEncoding::Identity, &data, Some(9)).unwrap(),
+            data
+        );
+
+        #[allow(unused_mut)]
+        let mut encodings = Vec::new();
+        #[cfg(feature = "compress-gzip")]
+        encodings.extend([ContentEncoding::Gzip, ContentEncoding::Deflate]);
+        #[cfg(feature = "compress-brotli")]
+        encodings.push(ContentEncoding::Brotli);
+        #[cfg(feature = "compress-zstd")]
+        encodings.push(ContentEncoding::Zstd);
+        #[cfg(feature = "compress-lz4")]
+        encodings.push(ContentEncoding::Lz4);
+
+        for encoding in encodings {
+            for level in [None, Some(-5), Some(1), Some(100)] {
+                let compressed = compress(encoding, &data, level).unwrap();
+                assert_ne!(compressed, data);
+
+                let payload = stream::iter([Ok::<_, crate::error::PayloadError>(compressed)]);
+                let decoded = Decoder::new(payload, encoding)
+                    .map(|chunk| chunk.unwrap().to_vec())
+                    .concat()
+                    .await;
+                assert_eq!(decoded, data);
+            }
+        }
+    }
+
+    #[actix_rt::test]
+    async fn identity_header() {
+        let mut head = ResponseHead::new(StatusCode::OK);
+        let body = Encoder::response(ContentEncoding::Identity, &mut head, "hello");
+        assert!(!head.headers().contains_key(CONTENT_ENCODING));
+        assert_eq!(body::to_bytes(body).await.unwrap(), "hello");
+
+        let config = ContentEncoderConfig::new().identity_header(true);
+        assert!(config.get_identity_header());
+
+        let mut head = ResponseHead::new(StatusCode::OK);
+        let body =
+            Encoder::response_with_config(ContentEncoding::Identity, &mut head, "hello", &config);
+        assert_eq!(head.headers().get(CONTENT_ENCODING).unwrap(), "identity");
+        assert_eq!(body::to_bytes(body).await.unwrap(), "hello");
+
+        // existing header is left untouched
+        let mut head = ResponseHead::new(StatusCode::OK);
+        head.headers_mut()
+            .insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        Encoder::response_with_config(ContentEncoding::Identity, &mut head, "hello", &config);
+        assert_eq!(head.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+
+        // not added to 204 responses
+        let mut head = ResponseHead::new(StatusCode::NO_CONTENT);
+        Encoder::response_with_config(ContentEncoding::Identity, &mut head, "hello", &config);
+        assert!(!head.headers().contains_key(CONTENT_ENCODING));
+    }
+
+    #[actix_rt::test]
+    async fn empty_body_has_no_content_encoding() {
+        use futures_util::stream;
+
+        let config = ContentEncoderConfig::new().identity_header(true);
+
+        #[allow(unused_mut)]
+        let mut encodings = vec![ContentEncoding::Identity];
+        #[cfg(feature = "compress-gzip")]
+        encodings.extend([ContentEncoding::Gzip, ContentEncoding::Deflate]);
+        #[cfg(feature = "compress-brotli")]
+        encodings.push(ContentEncoding::Brotli);
+        #[cfg(feature = "compress-zstd")]
+        encodings.push(ContentEncoding::Zstd);
+        #[cfg(feature = "compress-lz4")]
+        encodings.push(ContentEncoding::Lz4);
+
+        for encoding in encodings {
+            let mut head = ResponseHead::new(StatusCode::OK);
+            let body = Encoder::response_with_config(encoding, &mut head, (), &config);
+            assert!(!head.headers().contains_key(CONTENT_ENCODING));
+            assert!(body::to_bytes(body).await.unwrap().is_empty());
+
+            let mut head = ResponseHead::new(StatusCode::OK);
+            let body =
+                Encoder::response_with_config(encoding, &mut head, body::None::new(), &config);
+            assert!(!head.headers().contains_key(CONTENT_ENCODING));
+            assert_eq!(body.size(), BodySize::None);
+
+            // zero-length stream
+            let stream = stream::empty::<Result<Bytes, io::Error>>();
+            let mut head = ResponseHead::new(StatusCode::OK);
+            let body = Encoder::response_with_config(
+                encoding,
+                &mut head,
+                body::SizedStream::new(0, stream),
+                &config,
+            );
+            assert!(!head.headers().contains_key(CONTENT_ENCODING));
+            assert!(!head.headers().contains_key(header::VARY));
+            assert!(body::to_bytes(body).await.unwrap().is_empty());
+        }
+    }
+
+    #[cfg(feature = "compress-gzip")]
+    #[actix_rt::test]
+    async fn flush_per_chunk() {
+        use std::future::poll_fn;
+
+        use futures_util::stream;
+
+        async fn first_chunk_decoded(config: &ContentEncoderConfig) -> Vec<u8> {
+            let chunks = [
+                Ok::<_, io::Error>(Bytes::from_static(b"hello ")),
+                Ok("world".into()),
+            ];
+            let mut head = ResponseHead::new(StatusCode::OK);
+            let body = Encoder::response_with_config(
+                ContentEncoding::Gzip,
+                &mut head,
+                body::BodyStream::new(stream::iter(chunks)),
+                config,
+            );
+            actix_rt::pin!(body);
+
+            let mut decoder = flate2::write::GzDecoder::new(Vec::new());
+            while decoder.get_ref().is_empty() {
+                match poll_fn(|cx| body.as_mut().poll_next(cx)).await {
+                    Some(chunk) => {
+                        decoder.write_all(&chunk.unwrap()).unwrap();
+                        decoder.flush().unwrap();
+                    }
+                    None => break,
+                }
+            }
+            decoder.get_ref().clone()
+        }
+
+        let config = ContentEncoderConfig::new();
+        assert!(!config.get_flush_per_chunk());
+        assert_eq!(first_chunk_decoded(&config).await, b"hello world");
+
+        let config = config.flush_per_chunk(true);
+        assert!(config.get_flush_per_chunk());
+        assert_eq!(first_chunk_decoded(&config).await, b"hello ");
+    }
+
+    #[cfg(feature = "compress-gzip")]
+    #[test]
+    fn max_inline_chunk_size() {
+        use std::{panic, pin::pin, task::Poll};
+
+        use futures_util::{stream, task::noop_waker_ref};
+
+        /// Encodes a body with a single chunk of `len` bytes outside of any async runtime.
+        ///
+        /// Returns `true` if the chunk was encoded in place. Chunks sent to the blocking thread pool
+        /// can not be encoded here because spawning a blocking task panics without a runtime.
+        fn encodes_inline(config: &ContentEncoderConfig, len: usize) -> bool {
+            panic::catch_unwind(|| {
+                let chunk = Bytes::from(vec![b'a'; len]);
+                let mut head = ResponseHead::new(StatusCode::OK);
+                let mut body = pin!(Encoder::response_with_config(
+                    ContentEncoding::Gzip,
+                    &mut head,
+                    body::BodyStream::new(stream::iter([Ok::<_, io::Error>(chunk.clone())])),
+                    config,
+                ));
+
+                let mut cx = Context::from_waker(noop_waker_ref());
+                let mut encoded = Vec::new();
+
+                loop {
+                    match body.as_mut().poll_next(&mut cx) {
+                        Poll::Ready(Some(res)) => encoded.extend_from_slice(&res.unwrap()),
+                        Poll::Ready(None) => break,
+                        Poll::Pending => panic!("inline encoding should never be pending"),
+                    }
+                }
+
+                let mut decoder = flate2::write::GzDecoder::new(Vec::new());
+                decoder.write_all(&encoded).unwrap();
+                assert_eq!(decoder.finish().unwrap(), chunk);
+            })
+            .is_ok()
+        }
+
+        let config = ContentEncoderConfig::new();
+        assert_eq!(config.get_max_inline_chunk_size(), 1024);
+        assert!(encodes_inline(&config, 1023));
+        assert!(!encodes_inline(&config, 1024));
+
+        let config = ContentEncoderConfig::new().max_inline_chunk_size(0);
+        assert_eq!(config.get_max_inline_chunk_size(), 0);
+        assert!(!encodes_inline(&config, 1));
+
+        let config = ContentEncoderConfig::new().max_inline_chunk_size(1 << 20);
+        assert_eq!(config.get_max_inline_chunk_size(), 1 << 20);
+        assert!(encodes_inline(&config, 4096));
+        assert!(!encodes_inline(&config, 1 << 20));
+    }
+
+    #[cfg(feature = "compress-brotli")]
+    #[actix_rt::test]
+    async fn brotli_custom_window() {
+        use std::io::Read as _;
+
+        let data = "hello world ".repeat(1024);
+
+        let mut head = ResponseHead::new(StatusCode::OK);
+        let config = ContentEncoderConfig::new().brotli_window(24);
+        let body = Encoder::response_with_config(
+            ContentEncoding::Brotli,
+            &mut head,
+            data.clone(),
+            &config,
+        );
+        let compressed = body::to_bytes(body).await.unwrap();
+
+        let mut decoded = String::new();
+        brotli::Decompressor::new(compressed.as_ref(), 4096)
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[cfg(feature = "compress-zstd")]
+    #[actix_rt::test]
+    async fn zstd_dictionary() {
+        use std::io::Read as _;
+
+        let dict = br#"{"id":0,"name":"","email":"@example.com"}"#.repeat(8);
+        let data = r#"{"id":42,"name":"Alice","email":"alice@example.com"}"#;
+
+        let mut head = ResponseHead::new(StatusCode::OK);
+        let config = ContentEncoderConfig::new().zstd_dictionary(dict.clone());
+        assert_eq!(config.get_zstd_dictionary(), Some(dict.as_slice()));
+        let body = Encoder::response_with_config(ContentEncoding::Zstd, &mut head, data, &config);
+        let compressed = body::to_bytes(body).await.unwrap();
+
+        let mut decoded = String::new();
+        zstd::stream::read::Decoder::with_dictionary(compressed.as_ref(), &dict)
+            .unwrap()
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+
+        // decoding without the dictionary fails
+        assert!(zstd::stream::decode_all(compressed.as_ref()).is_err());
+    }
+}
@@ -0,0 +1,253 @@
+//! Write-mode LZ4 frame encoder and decoder.
+//!
+//! The `lz4` crate's encoder does not give mutable access to its inner writer and its decoder is
+//! read-based, so neither fits the streaming [`Writer`] model used by the other codecs. These
+//! types drive the LZ4 frame API directly instead.
+
+use std::{
+    io::{self, Write as _},
+    ptr,
+};
+
+use ::lz4::liblz4::{
+    check_error, BlockChecksum, BlockMode, BlockSize, ContentChecksum, FrameType,
+    LZ4FCompressionContext, LZ4FDecompressionContext, LZ4FFrameInfo, LZ4FPreferences,
+    LZ4F_compressBegin, LZ4F_compressBound, LZ4F_compressEnd, LZ4F_compressUpdate,
+    LZ4F_createCompressionContext, LZ4F_createDecompressionContext, LZ4F_decompress, LZ4F_flush,
+    LZ4F_freeCompressionContext, LZ4F_freeDecompressionContext, LZ4F_VERSION,
+};
+
+use super::Writer;
+
+/// Size of the blocks that input is split into, and of the decoder's output buffer.
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Owned LZ4 compression context.
+struct CompressionContext(LZ4FCompressionContext);
+
+impl CompressionContext {
+    fn new() -> io::Result<Self> {
+        let mut ctx = LZ4FCompressionContext(ptr::null_mut());
+        // SAFETY: `ctx` is a valid pointer to a context handle for the duration of the call; it is
+        // only wrapped in `Self`, and so freed on drop, if creation succeeded
+        check_error(unsafe { LZ4F_createCompressionContext(&mut ctx, LZ4F_VERSION) })?;
+        Ok(Self(ctx))
+    }
+}
+
+impl Drop for CompressionContext {
+    fn drop(&mut self) {
+        // SAFETY: the context was successfully created in `new` and is not used after this
+        unsafe { LZ4F_freeCompressionContext(self.0) };
+    }
+}
+
+/// Owned LZ4 decompression context.
+struct DecompressionContext(LZ4FDecompressionContext);
+
+impl DecompressionContext {
+    fn new() -> io::Result<Self> {
+        let mut ctx = LZ4FDecompressionContext(ptr::null_mut());
+        // SAFETY: `ctx` is a valid pointer to a context handle for the duration of the call; it is
+        // only wrapped in `Self`, and so freed on drop, if creation succeeded
+        check_error(unsafe { LZ4F_createDecompressionContext(&mut ctx, LZ4F_VERSION) })?;
+        Ok(Self(ctx))
+    }
+}
+
+impl Drop for DecompressionContext {
+    fn drop(&mut self) {
+        // SAFETY: the context was successfully created in `new` and is not used after this
+        unsafe { LZ4F_freeDecompressionContext(self.0) };
+    }
+}
+
+/// Compresses data written to it into a single LZ4 frame.
+pub(super) struct Lz4Encoder {
+    ctx: CompressionContext,
+    buf: Vec<u8>,
+    writer: Writer,
+}
+
+impl Lz4Encoder {
+    /// Constructs an encoder using the given compression level and writes the frame header.
+    pub(super) fn new(writer: Writer, level: u32) -> io::Result<Self> {
+        let prefs = LZ4FPreferences {
+            frame_info: LZ4FFrameInfo {
+                block_size_id: BlockSize::Max64KB,
+                block_mode: BlockMode::Linked,
+                content_checksum_flag: ContentChecksum::ChecksumEnabled,
+                frame_type: FrameType::Frame,
+                content_size: 0,
+                dict_id: 0,
+                block_checksum_flag: BlockChecksum::NoBlockChecksum,
+            },
+            compression_level: level,
+            auto_flush: 0,
+            favor_dec_speed: 0,
+            reserved: [0; 3],
+        };
+
+        // SAFETY: `prefs` is a valid reference for the duration of the call
+        let bound = check_error(unsafe { LZ4F_compressBound(BLOCK_SIZE, &prefs) })?;
+
+        let mut encoder = Self {
+            ctx: CompressionContext::new()?,
+            buf: vec![0; bound],
+            writer,
+        };
+
+        // SAFETY: the context is valid and owned by the encoder; `buf` is writable for `buf.len()`
+        // bytes, which is at least the bound for `BLOCK_SIZE` and so larger than the maximum frame
+        // header size
+        let len = check_error(unsafe {
+            LZ4F_compressBegin(
+                encoder.ctx.0,
+                encoder.buf.as_mut_ptr(),
+                encoder.buf.len(),
+                &prefs,
+            )
+        })?;
+        encoder.writer.write_all(&encoder.buf[..len])?;
+
+        Ok(encoder)
+    }
+
+    pub(super) fn get_mut(&mut self) -> &mut Writer {
+        &mut self.writer
+    }
+
+    /// Writes the frame footer and returns the inner writer.
+    pub(super) fn finish(mut self) -> io::Result<Writer> {
+        // SAFETY: the context is valid and owned by the encoder; `buf` is writable for `buf.len()`
+        // bytes, and the bound used to size it includes any buffered data and the frame footer
+        let len = check_error(unsafe {
+            LZ4F_compressEnd(
+                self.ctx.0,
+                self.buf.as_mut_ptr(),
+                self.buf.len(),
+                ptr::null(),
+            )
+        })?;
+        self.writer.write_all(&self.buf[..len])?;
+
+        Ok(self.writer)
+    }
+}
+
+impl io::Write for Lz4Encoder {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        for chunk in data.chunks(BLOCK_SIZE) {
+            // SAFETY: the context is valid and owned by the encoder; `chunk` is readable for
+            // `chunk.len()` bytes; `buf` is writable for `buf.len()` bytes, which is the bound for
+            // `BLOCK_SIZE` bytes of input, including buffered data, so the output always fits
+            let len = check_error(unsafe {
+                LZ4F_compressUpdate(
+                    self.ctx.0,
+                    self.buf.as_mut_ptr(),
+                    self.buf.len(),
+                    chunk.as_ptr(),
+                    chunk.len(),
+                    ptr::null(),
+                )
+            })?;
+            self.writer.write_all(&self.buf[..len])?;
+        }
+
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // SAFETY: the context is valid and owned by the encoder; `buf` is writable for `buf.len()`
+        // bytes, and the bound used to size it includes any buffered data
+        let len = check_error(unsafe {
+            LZ4F_flush(
+                self.ctx.0,
+                self.buf.as_mut_ptr(),
+                self.buf.len(),
+                ptr::null(),
+            )
+        })?;
+        self.writer.write_all(&self.buf[..len])
+    }
+}
+
+/// Decompresses LZ4 frames written to it.
+pub(super) struct Lz4Decoder {
+    ctx: DecompressionContext,
+    buf: Vec<u8>,
+    writer: Writer,
+    frame_complete: bool,
+}
+
+impl Lz4Decoder {
+    pub(super) fn new(writer: Writer) -> io::Result<Self> {
+        Ok(Self {
+            ctx: DecompressionContext::new()?,
+            buf: vec![0; BLOCK_SIZE],
+            writer,
+            frame_complete: true,
+        })
+    }
+
+    pub(super) fn get_mut(&mut self) -> &mut Writer {
+        &mut self.writer
+    }
+
+    /// Returns an error if the input ended part way through a frame.
+    pub(super) fn try_finish(&mut self) -> io::Result<()> {
+        if self.frame_complete {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "incomplete lz4 frame",
+            ))
+        }
+    }
+}
+
+impl io::Write for Lz4Decoder {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        let mut src = data;
+
+        loop {
+            let mut dst_len = self.buf.len();
+            let mut src_len = src.len();
+
+            // SAFETY: the context is valid and owned by the decoder; `buf` is writable for
+            // `dst_len` bytes and `src` is readable for `src_len` bytes; the call updates both
+            // lengths to the number of bytes actually written and read, which never exceed them
+            let hint = check_error(unsafe {
+                LZ4F_decompress(
+                    self.ctx.0,
+                    self.buf.as_mut_ptr(),
+                    &mut dst_len,
+                    src.as_ptr(),
+                    &mut src_len,
+                    ptr::null(),
+                )
+            })?;
+
+            if src_len > 0 || dst_len > 0 {
+                self.frame_complete = hint == 0;
+            }
+
+            self.writer.write_all(&self.buf[..dst_len])?;
+            src = &src[src_len..];
+
+            // a full output buffer may leave decoded data behind in the context
+            if src.is_empty() && dst_len < self.buf.len() {
+                return Ok(data.len());
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -6,20 +6,29 @@ use bytes::{Bytes, BytesMut};
 
 mod decoder;
 mod encoder;
+#[cfg(feature = "compress-lz4")]
+mod lz4;
 
-pub use self::{decoder::Decoder, encoder::Encoder};
+pub use self::{
+    decoder::Decoder,
+    encoder::{compress, ContentEncoderConfig, Encoder},
+};
 
 /// Special-purpose writer for streaming (de-)compression.
 ///
 /// Pre-allocates 8KiB of capacity.
 struct Writer {
     buf: BytesMut,
+    written: usize,
+    limit: usize,
 }
 
 impl Writer {
     fn new() -> Writer {
         Writer {
             buf: BytesMut::with_capacity(8192),
+            written: 0,
+            limit: usize::MAX,
         }
     }
 
@@ -30,6 +39,15 @@ impl Writer {
 
 impl io::Write for Writer {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.written = self.written.saturating_add(buf.len());
+
+        if self.written > self.limit {
+            return Err(io::Error::other(WriterLimitExceeded {
+                limit: self.limit,
+                written: self.written,
+            }));
+        }
+
         self.buf.extend_from_slice(buf);
         Ok(buf.len())
     }
@@ -38,3 +56,11 @@ impl io::Write for Writer {
         Ok(())
     }
 }
+
+/// Error returned by [`Writer`] when more than its limit has been written.
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("writer limit exceeded")]
+struct WriterLimitExceeded {
+    limit: usize,
+    written: usize,
+}
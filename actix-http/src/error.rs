@@ -50,7 +50,6 @@ impl Error {
         Self::new(Kind::SendResponse)
     }
 
-    #[allow(unused)] // available for future use
     pub(crate) fn new_io() -> Self {
         Self::new(Kind::Io)
     }
@@ -148,6 +147,13 @@ impl From<crate::ws::HandshakeError> for Error {
     }
 }
 
+#[cfg(feature = "ws")]
+impl From<crate::ws::SubprotocolHandshakeError> for Error {
+    fn from(err: crate::ws::SubprotocolHandshakeError) -> Self {
+        Self::new_ws().with_cause(err)
+    }
+}
+
 #[cfg(feature = "ws")]
 impl From<crate::ws::ProtocolError> for Error {
     fn from(err: crate::ws::ProtocolError) -> Self {
@@ -175,10 +181,29 @@ pub enum ParseError {
     #[display("invalid Header provided")]
     Header,
 
+    /// An invalid header, along with the offending header line.
+    ///
+    /// The line is rendered as lossy UTF-8 and truncated to a bounded length so it is safe to log.
+    #[display("invalid Header provided: {:?}", line)]
+    HeaderLine {
+        /// The offending header line.
+        line: String,
+    },
+
     /// A message head is too large to be reasonable.
     #[display("message head is too large")]
     TooLarge,
 
+    /// A message head exceeded one of the configured [`HeadLimits`](crate::h1::HeadLimits).
+    #[display("message head exceeds {} limit of {}", limit, max)]
+    HeadLimitExceeded {
+        /// The limit that was exceeded.
+        limit: HeadLimit,
+
+        /// The configured value of the limit.
+        max: usize,
+    },
+
     /// A message reached EOF, but is not complete.
     #[display("message is incomplete")]
     Incomplete,
@@ -196,14 +221,58 @@ pub enum ParseError {
     #[display("I/O error: {}", _0)]
     Io(io::Error),
 
+    /// The peer closed the connection while a message was being read.
+    ///
+    /// Produced instead of [`ParseError::Io`] for I/O errors of kind `ConnectionReset`,
+    /// `BrokenPipe`, and `UnexpectedEof`. These are not caused by malformed input so, unlike other
+    /// parse errors, they are not converted into a 400 Bad Request response.
+    #[display("connection closed by peer: {}", _0)]
+    ConnectionClosed(io::Error),
+
     /// Parsing a field as string failed.
     #[display("UTF-8 error: {}", _0)]
     Utf8(Utf8Error),
 }
 
+/// A limit on the size of HTTP/1.x message heads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[non_exhaustive]
+pub enum HeadLimit {
+    /// Maximum number of headers.
+    #[display("header count")]
+    Headers,
+
+    /// Maximum size of the message head, in bytes.
+    #[display("size")]
+    Size,
+}
+
+impl ParseError {
+    /// Maximum number of header line bytes captured by [`ParseError::HeaderLine`].
+    const MAX_HEADER_LINE_LEN: usize = 128;
+
+    /// Constructs a [`ParseError::HeaderLine`] from the raw bytes of the offending header line.
+    pub(crate) fn header_line(line: &[u8]) -> Self {
+        let truncated = line.len() > Self::MAX_HEADER_LINE_LEN;
+        let line = &line[..line.len().min(Self::MAX_HEADER_LINE_LEN)];
+
+        let mut line = String::from_utf8_lossy(line).into_owned();
+        if truncated {
+            line.push_str("...");
+        }
+
+        ParseError::HeaderLine { line }
+    }
+}
+
 impl From<io::Error> for ParseError {
     fn from(err: io::Error) -> ParseError {
-        ParseError::Io(err)
+        match err.kind() {
+            io::ErrorKind::ConnectionReset
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::UnexpectedEof => ParseError::ConnectionClosed(err),
+            _ => ParseError::Io(err),
+        }
     }
 }
 
@@ -241,7 +310,10 @@ impl From<httparse::Error> for ParseError {
 
 impl From<ParseError> for Error {
     fn from(err: ParseError) -> Self {
-        Self::new_parse().with_cause(err)
+        match err {
+            ParseError::ConnectionClosed(_) => Self::new_io().with_cause(err),
+            _ => Self::new_parse().with_cause(err),
+        }
     }
 }
 
@@ -267,10 +339,34 @@ pub enum PayloadError {
     #[display("payload reached size limit")]
     Overflow,
 
+    /// Payload reached size limit, with the sizes involved.
+    ///
+    /// Returned instead of [`Overflow`](Self::Overflow) where the limit and the number of bytes
+    /// read are known. Both variants should be handled the same way.
+    #[display(
+        "payload reached size limit (limit: {} bytes, read: {} bytes)",
+        limit,
+        read
+    )]
+    OverflowWithSize {
+        /// The configured size limit, in bytes.
+        limit: usize,
+
+        /// The number of bytes read when the limit was exceeded.
+        ///
+        /// If the overflow was detected from the `Content-Length` header, before reading the
+        /// payload, this is the declared length instead.
+        read: usize,
+    },
+
     /// Payload length is unknown.
     #[display("payload length is unknown")]
     UnknownLength,
 
+    /// Payload was not received within the configured time.
+    #[display("payload read timed out")]
+    Timeout,
+
     /// HTTP/2 payload error.
     #[cfg(feature = "http2")]
     #[display("{}", _0)]
@@ -288,7 +384,9 @@ impl std::error::Error for PayloadError {
             PayloadError::Incomplete(Some(err)) => Some(err),
             PayloadError::EncodingCorrupted => None,
             PayloadError::Overflow => None,
+            PayloadError::OverflowWithSize { .. } => None,
             PayloadError::UnknownLength => None,
+            PayloadError::Timeout => None,
             #[cfg(feature = "http2")]
             PayloadError::Http2Payload(err) => Some(err),
             PayloadError::Io(err) => Some(err),
@@ -343,6 +441,7 @@ pub enum DispatchError {
 
     /// Request parse error.
     #[display("request parse error: {}", _0)]
+    #[from(ignore)]
     Parse(ParseError),
 
     /// HTTP/2 error.
@@ -367,6 +466,16 @@ pub enum DispatchError {
     InternalError,
 }
 
+impl From<ParseError> for DispatchError {
+    fn from(err: ParseError) -> Self {
+        match err {
+            // peer hang-ups are I/O failures, not malformed requests
+            ParseError::ConnectionClosed(err) => DispatchError::Io(err),
+            err => DispatchError::Parse(err),
+        }
+    }
+}
+
 impl StdError for DispatchError {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match self {
@@ -485,4 +594,53 @@ mod tests {
         from!(httparse::Error::TooManyHeaders => ParseError::TooLarge);
         from!(httparse::Error::Version => ParseError::Version);
     }
+
+    #[test]
+    fn test_connection_closed() {
+        for kind in [
+            io::ErrorKind::ConnectionReset,
+            io::ErrorKind::BrokenPipe,
+            io::ErrorKind::UnexpectedEof,
+        ] {
+            let err = ParseError::from(io::Error::new(kind, "closed"));
+            assert!(matches!(err, ParseError::ConnectionClosed(_)));
+            assert_eq!(err.to_string(), "connection closed by peer: closed");
+
+            let res: Response<BoxBody> = err.into();
+            assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+
+        let err = ParseError::from(io::Error::new(io::ErrorKind::InvalidData, "bad"));
+        assert!(matches!(err, ParseError::Io(_)));
+
+        let err = ParseError::from(io::Error::from(io::ErrorKind::ConnectionReset));
+        assert!(matches!(DispatchError::from(err), DispatchError::Io(_)));
+
+        let err = DispatchError::from(ParseError::Method);
+        assert!(matches!(err, DispatchError::Parse(ParseError::Method)));
+    }
+
+    #[test]
+    fn test_header_line() {
+        let err = ParseError::header_line(b"Content-Length: +1");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid Header provided: "Content-Length: +1""#
+        );
+
+        let err = ParseError::header_line(b"X-Bad: \xff\r\n");
+        assert_eq!(
+            err.to_string(),
+            r#"invalid Header provided: "X-Bad: �\r\n""#
+        );
+
+        let err = ParseError::header_line(&[b'a'; 1024]);
+        match err {
+            ParseError::HeaderLine { line } => {
+                assert_eq!(line.len(), ParseError::MAX_HEADER_LINE_LEN + 3);
+                assert!(line.ends_with("..."));
+            }
+            _ => unreachable!(),
+        }
+    }
 }
@@ -9,7 +9,9 @@ use super::{
     decoder::{self, PayloadDecoder, PayloadItem, PayloadType},
     encoder, Message, MessageType,
 };
-use crate::{body::BodySize, error::ParseError, ConnectionType, Request, Response, ServiceConfig};
+use crate::{
+    body::BodySize, error::ParseError, ConnectionType, Request, Response, ServiceConfig, Trailers,
+};
 
 bitflags! {
     #[derive(Debug, Clone, Copy)]
@@ -59,9 +61,9 @@ impl Codec {
         };
 
         Codec {
+            decoder: decoder::MessageDecoder::new(config.head_limits()),
             config,
             flags,
-            decoder: decoder::MessageDecoder::default(),
             payload: None,
             version: Version::HTTP_11,
             conn_type: ConnectionType::Close,
@@ -181,6 +183,10 @@ impl Encoder<Message<(Response<()>, BodySize)>> for Codec {
                     self.conn_type,
                     &self.config,
                 )?;
+
+                self.encoder
+                    .te
+                    .set_trailers(res.extensions().get::<Trailers>().cloned());
             }
 
             Message::Chunk(Some(bytes)) => {
@@ -1,4 +1,10 @@
-use std::{io, marker::PhantomData, mem::MaybeUninit, task::Poll};
+use std::{
+    io, iter,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    task::Poll,
+};
 
 use actix_codec::Decoder;
 use bytes::{Bytes, BytesMut};
@@ -6,16 +12,55 @@ use http::{
     header::{self, HeaderName, HeaderValue},
     Method, StatusCode, Uri, Version,
 };
-use tracing::{debug, error, trace};
+use tracing::{debug, trace};
 
 use super::chunked::ChunkedState;
-use crate::{error::ParseError, header::HeaderMap, ConnectionType, Request, ResponseHead};
+use crate::{
+    error::{HeadLimit, ParseError},
+    header::HeaderMap,
+    ConnectionType, Request, ResponseHead,
+};
 
 pub(crate) const MAX_BUFFER_SIZE: usize = 131_072;
 const MAX_HEADERS: usize = 96;
 
+/// Limits applied while decoding an HTTP/1.x message head.
+///
+/// By default, up to 96 headers are accepted and the head size is limited to 128KiB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct HeadLimits {
+    /// Maximum number of headers.
+    pub max_headers: usize,
+
+    /// Maximum size of the message head, in bytes.
+    pub max_size: usize,
+}
+
+impl Default for HeadLimits {
+    fn default() -> Self {
+        Self {
+            max_headers: MAX_HEADERS,
+            max_size: MAX_BUFFER_SIZE,
+        }
+    }
+}
+
 /// Incoming message decoder
-pub(crate) struct MessageDecoder<T: MessageType>(PhantomData<T>);
+pub(crate) struct MessageDecoder<T: MessageType> {
+    limits: HeadLimits,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: MessageType> MessageDecoder<T> {
+    /// Constructs a decoder that enforces the given head limits.
+    pub(crate) fn new(limits: HeadLimits) -> Self {
+        Self {
+            limits,
+            _phantom: PhantomData,
+        }
+    }
+}
 
 #[derive(Debug)]
 /// Incoming request type
@@ -27,7 +72,7 @@ pub(crate) enum PayloadType {
 
 impl<T: MessageType> Default for MessageDecoder<T> {
     fn default() -> Self {
-        MessageDecoder(PhantomData)
+        Self::new(HeadLimits::default())
     }
 }
 
@@ -36,7 +81,7 @@ impl<T: MessageType> Decoder for MessageDecoder<T> {
     type Error = ParseError;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        T::decode(src)
+        T::decode(src, &self.limits)
     }
 }
 
@@ -70,7 +115,10 @@ pub(crate) trait MessageType: Sized {
 
     fn headers_mut(&mut self) -> &mut HeaderMap;
 
-    fn decode(src: &mut BytesMut) -> Result<Option<(Self, PayloadType)>, ParseError>;
+    fn decode(
+        src: &mut BytesMut,
+        limits: &HeadLimits,
+    ) -> Result<Option<(Self, PayloadType)>, ParseError>;
 
     fn set_headers(
         &mut self,
@@ -97,16 +145,19 @@ pub(crate) trait MessageType: Sized {
                     HeaderValue::from_maybe_shared_unchecked(slice.slice(idx.value.0..idx.value.1))
                 };
 
+                // offending header line (sans line ending), captured for diagnostics
+                let invalid_header = || ParseError::header_line(&slice[idx.name.0..idx.value.1]);
+
                 match name {
                     header::CONTENT_LENGTH if content_length.is_some() => {
                         debug!("multiple Content-Length");
-                        return Err(ParseError::Header);
+                        return Err(invalid_header());
                     }
 
                     header::CONTENT_LENGTH => match value.to_str().map(str::trim) {
                         Ok(val) if val.starts_with('+') => {
                             debug!("illegal Content-Length: {:?}", val);
-                            return Err(ParseError::Header);
+                            return Err(invalid_header());
                         }
 
                         Ok(val) => {
@@ -116,20 +167,20 @@ pub(crate) trait MessageType: Sized {
                                 content_length = Some(len);
                             } else {
                                 debug!("illegal Content-Length: {:?}", val);
-                                return Err(ParseError::Header);
+                                return Err(invalid_header());
                             }
                         }
 
                         Err(_) => {
                             debug!("illegal Content-Length: {:?}", value);
-                            return Err(ParseError::Header);
+                            return Err(invalid_header());
                         }
                     },
 
                     // transfer-encoding
                     header::TRANSFER_ENCODING if seen_te => {
                         debug!("multiple Transfer-Encoding not allowed");
-                        return Err(ParseError::Header);
+                        return Err(invalid_header());
                     }
 
                     header::TRANSFER_ENCODING if version == Version::HTTP_11 => {
@@ -142,10 +193,10 @@ pub(crate) trait MessageType: Sized {
                                 // allow silently since multiple TE headers are already checked
                             } else {
                                 debug!("illegal Transfer-Encoding: {:?}", val);
-                                return Err(ParseError::Header);
+                                return Err(invalid_header());
                             }
                         } else {
-                            return Err(ParseError::Header);
+                            return Err(invalid_header());
                         }
                     }
 
@@ -228,23 +279,26 @@ impl MessageType for Request {
         &mut self.head_mut().headers
     }
 
-    fn decode(src: &mut BytesMut) -> Result<Option<(Self, PayloadType)>, ParseError> {
-        let mut headers: [HeaderIndex; MAX_HEADERS] = EMPTY_HEADER_INDEX_ARRAY;
+    fn decode(
+        src: &mut BytesMut,
+        limits: &HeadLimits,
+    ) -> Result<Option<(Self, PayloadType)>, ParseError> {
+        let mut headers = HeaderBuf::header_indices(limits.max_headers);
 
         let (len, method, uri, ver, h_len) = {
-            // SAFETY:
-            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is safe because the
-            // type we are claiming to have initialized here is a bunch of `MaybeUninit`s, which
-            // do not require initialization.
-            let mut parsed = unsafe {
-                MaybeUninit::<[MaybeUninit<httparse::Header<'_>>; MAX_HEADERS]>::uninit()
-                    .assume_init()
-            };
+            let mut parsed = HeaderBuf::uninit_headers(limits.max_headers);
 
             let mut req = httparse::Request::new(&mut []);
 
-            match req.parse_with_uninit_headers(src, &mut parsed)? {
+            match req
+                .parse_with_uninit_headers(src, &mut parsed)
+                .map_err(|err| parse_error(err, src, limits))?
+            {
                 httparse::Status::Complete(len) => {
+                    if len > limits.max_size {
+                        return Err(head_too_large(limits));
+                    }
+
                     let method = Method::from_bytes(req.method.unwrap().as_bytes())
                         .map_err(|_| ParseError::Method)?;
                     let uri = Uri::try_from(req.path.unwrap())?;
@@ -259,9 +313,8 @@ impl MessageType for Request {
                 }
 
                 httparse::Status::Partial => {
-                    return if src.len() >= MAX_BUFFER_SIZE {
-                        trace!("MAX_BUFFER_SIZE unprocessed data reached, closing");
-                        Err(ParseError::TooLarge)
+                    return if src.len() >= limits.max_size {
+                        Err(head_too_large(limits))
                     } else {
                         // Return None to notify more read are needed for parsing request
                         Ok(None)
@@ -327,26 +380,29 @@ impl MessageType for ResponseHead {
         &mut self.headers
     }
 
-    fn decode(src: &mut BytesMut) -> Result<Option<(Self, PayloadType)>, ParseError> {
-        let mut headers: [HeaderIndex; MAX_HEADERS] = EMPTY_HEADER_INDEX_ARRAY;
+    fn decode(
+        src: &mut BytesMut,
+        limits: &HeadLimits,
+    ) -> Result<Option<(Self, PayloadType)>, ParseError> {
+        let mut headers = HeaderBuf::header_indices(limits.max_headers);
 
         let (len, ver, status, h_len) = {
-            // SAFETY:
-            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is safe because the
-            // type we are claiming to have initialized here is a bunch of `MaybeUninit`s, which
-            // do not require initialization.
-            let mut parsed = unsafe {
-                MaybeUninit::<[MaybeUninit<httparse::Header<'_>>; MAX_HEADERS]>::uninit()
-                    .assume_init()
-            };
+            let mut parsed = HeaderBuf::uninit_headers(limits.max_headers);
 
             let mut res = httparse::Response::new(&mut []);
 
             let mut config = httparse::ParserConfig::default();
             config.allow_spaces_after_header_name_in_responses(true);
 
-            match config.parse_response_with_uninit_headers(&mut res, src, &mut parsed)? {
+            match config
+                .parse_response_with_uninit_headers(&mut res, src, &mut parsed)
+                .map_err(|err| parse_error(err, src, limits))?
+            {
                 httparse::Status::Complete(len) => {
+                    if len > limits.max_size {
+                        return Err(head_too_large(limits));
+                    }
+
                     let version = if res.version.unwrap() == 1 {
                         Version::HTTP_11
                     } else {
@@ -361,9 +417,8 @@ impl MessageType for ResponseHead {
                 }
 
                 httparse::Status::Partial => {
-                    return if src.len() >= MAX_BUFFER_SIZE {
-                        error!("MAX_BUFFER_SIZE unprocessed data reached, closing");
-                        Err(ParseError::TooLarge)
+                    return if src.len() >= limits.max_size {
+                        Err(head_too_large(limits))
                     } else {
                         Ok(None)
                     }
@@ -404,6 +459,55 @@ impl MessageType for ResponseHead {
     }
 }
 
+/// Returns the error for a message head that exceeds the size limit.
+fn head_too_large(limits: &HeadLimits) -> ParseError {
+    debug!(
+        "message head exceeds size limit of {} bytes",
+        limits.max_size
+    );
+    ParseError::HeadLimitExceeded {
+        limit: HeadLimit::Size,
+        max: limits.max_size,
+    }
+}
+
+/// Converts an `httparse` error, capturing the offending header line when it can be located.
+fn parse_error(err: httparse::Error, src: &[u8], limits: &HeadLimits) -> ParseError {
+    match err {
+        httparse::Error::HeaderName | httparse::Error::HeaderValue => {
+            find_invalid_header_line(src).map_or_else(|| err.into(), ParseError::header_line)
+        }
+        httparse::Error::TooManyHeaders => {
+            debug!(
+                "message head exceeds limit of {} headers",
+                limits.max_headers
+            );
+            ParseError::HeadLimitExceeded {
+                limit: HeadLimit::Headers,
+                max: limits.max_headers,
+            }
+        }
+        _ => err.into(),
+    }
+}
+
+/// Finds the first header line in a message head that fails to parse on its own.
+///
+/// Only used on the error path so re-parsing each line individually is acceptable.
+fn find_invalid_header_line(src: &[u8]) -> Option<&[u8]> {
+    src.split(|&b| b == b'\n')
+        // skip request/status line
+        .skip(1)
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .take_while(|line| !line.is_empty())
+        .find(|line| {
+            let mut buf = line.to_vec();
+            buf.extend_from_slice(b"\r\n\r\n");
+            let mut headers = [httparse::EMPTY_HEADER; 1];
+            httparse::parse_headers(&buf, &mut headers).is_err()
+        })
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct HeaderIndex {
     pub(crate) name: (usize, usize),
@@ -418,6 +522,67 @@ pub(crate) const EMPTY_HEADER_INDEX: HeaderIndex = HeaderIndex {
 pub(crate) const EMPTY_HEADER_INDEX_ARRAY: [HeaderIndex; MAX_HEADERS] =
     [EMPTY_HEADER_INDEX; MAX_HEADERS];
 
+/// Storage for parsed headers.
+///
+/// Stays on the stack unless the header limit is raised above the default of [`MAX_HEADERS`].
+enum HeaderBuf<T> {
+    Stack([T; MAX_HEADERS], usize),
+    Heap(Vec<T>),
+}
+
+impl<T> HeaderBuf<T> {
+    fn new(len: usize, stack: impl FnOnce() -> [T; MAX_HEADERS], item: impl FnMut() -> T) -> Self {
+        if len <= MAX_HEADERS {
+            Self::Stack(stack(), len)
+        } else {
+            Self::Heap(iter::repeat_with(item).take(len).collect())
+        }
+    }
+}
+
+impl HeaderBuf<HeaderIndex> {
+    fn header_indices(len: usize) -> Self {
+        Self::new(len, || EMPTY_HEADER_INDEX_ARRAY, || EMPTY_HEADER_INDEX)
+    }
+}
+
+impl<'b> HeaderBuf<MaybeUninit<httparse::Header<'b>>> {
+    fn uninit_headers(len: usize) -> Self {
+        Self::new(
+            len,
+            // SAFETY:
+            // Create an uninitialized array of `MaybeUninit`. The `assume_init` is safe because the
+            // type we are claiming to have initialized here is a bunch of `MaybeUninit`s, which
+            // do not require initialization.
+            || unsafe {
+                MaybeUninit::<[MaybeUninit<httparse::Header<'b>>; MAX_HEADERS]>::uninit()
+                    .assume_init()
+            },
+            MaybeUninit::uninit,
+        )
+    }
+}
+
+impl<T> Deref for HeaderBuf<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            Self::Stack(buf, len) => &buf[..*len],
+            Self::Heap(buf) => buf,
+        }
+    }
+}
+
+impl<T> DerefMut for HeaderBuf<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            Self::Stack(buf, len) => &mut buf[..*len],
+            Self::Heap(buf) => buf,
+        }
+    }
+}
+
 impl HeaderIndex {
     pub(crate) fn record(
         bytes: &[u8],
@@ -785,6 +950,67 @@ mod tests {
         assert_eq!(req.path(), "/test");
     }
 
+    #[test]
+    fn test_head_limits() {
+        let limits = HeadLimits {
+            max_headers: 2,
+            max_size: 64,
+        };
+
+        let mut buf = BytesMut::from("GET /test HTTP/1.1\r\na: 1\r\nb: 2\r\n\r\n");
+        let mut reader = MessageDecoder::<Request>::new(limits);
+        assert!(reader.decode(&mut buf).unwrap().is_some());
+
+        let mut buf = BytesMut::from("GET /test HTTP/1.1\r\na: 1\r\nb: 2\r\nc: 3\r\n\r\n");
+        let mut reader = MessageDecoder::<Request>::new(limits);
+        assert!(matches!(
+            reader.decode(&mut buf),
+            Err(ParseError::HeadLimitExceeded {
+                limit: HeadLimit::Headers,
+                max: 2,
+            })
+        ));
+
+        let mut buf = BytesMut::from("GET /test HTTP/1.1\r\n");
+        buf.extend_from_slice(format!("a: {}\r\n", "x".repeat(64)).as_bytes());
+        let mut reader = MessageDecoder::<Request>::new(limits);
+        assert!(matches!(
+            reader.decode(&mut buf),
+            Err(ParseError::HeadLimitExceeded {
+                limit: HeadLimit::Size,
+                max: 64,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_head_limits_raised() {
+        let limits = HeadLimits {
+            max_headers: 200,
+            ..HeadLimits::default()
+        };
+
+        let mut buf = BytesMut::from("GET /test HTTP/1.1\r\n");
+        for i in 0..150 {
+            buf.extend_from_slice(format!("x-header-{i}: {i}\r\n").as_bytes());
+        }
+        buf.extend_from_slice(b"\r\n");
+
+        let mut reader = MessageDecoder::<Request>::default();
+        assert!(matches!(
+            reader.decode(&mut buf.clone()),
+            Err(ParseError::HeadLimitExceeded {
+                limit: HeadLimit::Headers,
+                max: 96,
+            })
+        ));
+
+        let mut reader = MessageDecoder::<Request>::new(limits);
+        let (req, _) = reader.decode(&mut buf).unwrap().unwrap();
+        assert_eq!(req.headers().len(), 150);
+        assert_eq!(req.headers().get("x-header-149").unwrap(), "149");
+    }
+
     #[test]
     fn test_headers_split_field() {
         let mut buf = BytesMut::from("GET /test HTTP/1.1\r\n");
@@ -985,6 +1211,28 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_invalid_header_line() {
+        let mut buf = BytesMut::from(
+            "GET /test HTTP/1.1\r\n\
+             Host: example.com\r\n\
+             test[]: line\r\n\r\n",
+        );
+        match MessageDecoder::<Request>::default().decode(&mut buf) {
+            Err(ParseError::HeaderLine { line }) => assert_eq!(line, "test[]: line"),
+            res => panic!("unexpected result: {:?}", res.map(|_| ())),
+        }
+
+        let mut buf = BytesMut::from(
+            "POST /test HTTP/1.1\r\n\
+             Content-Length: +5\r\n\r\n",
+        );
+        match MessageDecoder::<Request>::default().decode(&mut buf) {
+            Err(ParseError::HeaderLine { line }) => assert_eq!(line, "Content-Length: +5"),
+            res => panic!("unexpected result: {:?}", res.map(|_| ())),
+        }
+    }
+
     #[test]
     fn test_http_request_bad_status_line() {
         expect_parse_err!(&mut BytesMut::from("getpath \r\n\r\n"));
@@ -1,4 +1,5 @@
 use std::{
+    cmp,
     collections::VecDeque,
     fmt,
     future::Future,
@@ -6,6 +7,7 @@ use std::{
     pin::Pin,
     rc::Rc,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use actix_codec::{Framed, FramedParts};
@@ -31,7 +33,7 @@ use crate::{
     config::ServiceConfig,
     error::{DispatchError, ParseError, PayloadError},
     service::HttpFlow,
-    Error, Extensions, OnConnectData, Request, Response, StatusCode,
+    Error, Extensions, KeepAliveTimeout, OnConnectData, Request, Response, StatusCode,
 };
 
 const LW_BUFFER_SIZE: usize = 1024;
@@ -161,6 +163,8 @@ pin_project! {
 
         head_timer: TimerState,
         ka_timer: TimerState,
+        // keep-alive timeout override set by the last response
+        ka_timeout: Option<Duration>,
         shutdown_timer: TimerState,
 
         pub(super) io: Option<T>,
@@ -273,6 +277,7 @@ where
 
                     head_timer: TimerState::new(config.client_request_deadline().is_some()),
                     ka_timer: TimerState::new(config.keep_alive().enabled()),
+                    ka_timeout: None,
                     shutdown_timer: TimerState::new(config.client_disconnect_deadline().is_some()),
 
                     io: Some(io),
@@ -364,6 +369,11 @@ where
 
         let size = body.size();
 
+        *this.ka_timeout = res
+            .extensions()
+            .get::<KeepAliveTimeout>()
+            .map(|timeout| timeout.0);
+
         this.codec
             .encode(Message::Item((res, size)), this.write_buf)
             .map_err(|err| {
@@ -784,11 +794,22 @@ where
                     break;
                 }
 
-                Err(ParseError::TooLarge) => {
+                Err(err @ ParseError::ConnectionClosed(_)) => {
+                    trace!("{}", &err);
+                    self.as_mut().client_disconnected();
+                    this = self.as_mut().project();
+                    *this.error = Some(err.into());
+                    break;
+                }
+
+                Err(err @ (ParseError::TooLarge | ParseError::HeadLimitExceeded { .. })) => {
                     trace!("request head was too big; returning 431 response");
 
                     if let Some(mut payload) = this.payload.take() {
-                        payload.set_error(PayloadError::Overflow);
+                        payload.set_error(PayloadError::OverflowWithSize {
+                            limit: this.config.max_header_size(),
+                            read: this.read_buf.len(),
+                        });
                     }
 
                     // request heads that overflow buffer size return a 431 error
@@ -799,7 +820,7 @@ where
                         )));
 
                     this.flags.insert(Flags::READ_DISCONNECT);
-                    *this.error = Some(ParseError::TooLarge.into());
+                    *this.error = Some(err.into());
 
                     break;
                 }
@@ -940,9 +961,12 @@ where
 
         let mut read_some = false;
 
+        // allow the read buffer to hold a request head up to the configured size limit
+        let max_buffer_size = cmp::max(MAX_BUFFER_SIZE, this.config.max_header_size());
+
         loop {
             // Return early when read buf exceed decoder's max buffer size.
-            if this.read_buf.len() >= MAX_BUFFER_SIZE {
+            if this.read_buf.len() >= max_buffer_size {
                 // At this point it's not known IO stream is still scheduled to be waked up so
                 // force wake up dispatcher just in case.
                 //
@@ -1132,7 +1156,9 @@ where
                                 // KEEP_ALIVE is set in send_response_inner if client allows it
                                 // FINISHED is set after writing last chunk of response
                                 if inner.flags.contains(Flags::KEEP_ALIVE | Flags::FINISHED) {
-                                    if let Some(timer) = inner.config.keep_alive_deadline() {
+                                    if let Some(timer) =
+                                        inner.config.keep_alive_deadline_with(inner.ka_timeout)
+                                    {
                                         inner.as_mut().project().ka_timer.set_and_init(
                                             cx,
                                             sleep_until(timer.into()),
@@ -11,10 +11,12 @@ use super::dispatcher::{Dispatcher, DispatcherState, DispatcherStateProj, Flags}
 use crate::{
     body::MessageBody,
     config::ServiceConfig,
-    h1::{Codec, ExpectHandler, UpgradeHandler},
+    error::{DispatchError, HeadLimit, ParseError},
+    h1::{Codec, ExpectHandler, HeadLimits, UpgradeHandler},
     service::HttpFlow,
     test::{TestBuffer, TestSeqBuffer},
-    Error, HttpMessage, KeepAlive, Method, OnConnectData, Request, Response, StatusCode,
+    Error, HttpMessage, KeepAlive, KeepAliveTimeout, Method, OnConnectData, Request, Response,
+    StatusCode,
 };
 
 fn find_slice(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
@@ -281,6 +283,244 @@ async fn keep_alive_timeout() {
     .await;
 }
 
+#[actix_rt::test]
+async fn keep_alive_timeout_override() {
+    let buf = TestBuffer::new("GET /abcd HTTP/1.1\r\n\r\n");
+
+    let cfg = ServiceConfig::new(
+        KeepAlive::Timeout(Duration::from_secs(5)),
+        Duration::from_millis(100),
+        Duration::ZERO,
+        false,
+        None,
+    );
+    let services = HttpFlow::new(
+        fn_service(|_req: Request| {
+            let mut res = Response::ok();
+            res.extensions_mut()
+                .insert(KeepAliveTimeout(Duration::from_millis(100)));
+            ready(Ok::<_, Error>(res))
+        }),
+        ExpectHandler,
+        None,
+    );
+
+    let h1 = Dispatcher::<_, _, _, _, UpgradeHandler>::new(
+        buf.clone(),
+        services,
+        cfg,
+        None,
+        OnConnectData::default(),
+    );
+    pin!(h1);
+
+    lazy(|cx| {
+        assert!(
+            h1.as_mut().poll(cx).is_pending(),
+            "keep-alive should prevent poll from resolving"
+        );
+        assert!(buf.take_write_buf().starts_with(b"HTTP/1.1 200 OK\r\n"));
+    })
+    .await;
+
+    // sleep longer than the overridden timeout but far shorter than the configured one
+    sleep(Duration::from_millis(150)).await;
+
+    lazy(|cx| {
+        assert!(
+            h1.as_mut().poll(cx).is_ready(),
+            "overridden keep-alive should have resolved",
+        );
+
+        if let DispatcherStateProj::Normal { inner } = h1.project().inner.project() {
+            assert!(inner.flags.contains(Flags::SHUTDOWN));
+        }
+    })
+    .await;
+}
+
+#[actix_rt::test]
+async fn keep_alive_timeout_override_extends() {
+    let buf = TestBuffer::new("GET /abcd HTTP/1.1\r\n\r\n");
+
+    let cfg = ServiceConfig::new(
+        KeepAlive::Timeout(Duration::from_millis(50)),
+        Duration::from_millis(100),
+        Duration::ZERO,
+        false,
+        None,
+    );
+    let services = HttpFlow::new(
+        fn_service(|_req: Request| {
+            let mut res = Response::ok();
+            res.extensions_mut()
+                .insert(KeepAliveTimeout(Duration::from_millis(300)));
+            ready(Ok::<_, Error>(res))
+        }),
+        ExpectHandler,
+        None,
+    );
+
+    let h1 = Dispatcher::<_, _, _, _, UpgradeHandler>::new(
+        buf.clone(),
+        services,
+        cfg,
+        None,
+        OnConnectData::default(),
+    );
+    pin!(h1);
+
+    lazy(|cx| {
+        assert!(h1.as_mut().poll(cx).is_pending());
+        assert!(buf.take_write_buf().starts_with(b"HTTP/1.1 200 OK\r\n"));
+    })
+    .await;
+
+    // sleep longer than the configured timeout but shorter than the overridden one
+    sleep(Duration::from_millis(150)).await;
+
+    lazy(|cx| {
+        assert!(
+            h1.as_mut().poll(cx).is_pending(),
+            "overridden keep-alive should still be open",
+        );
+    })
+    .await;
+
+    sleep(Duration::from_millis(250)).await;
+
+    lazy(|cx| {
+        assert!(
+            h1.as_mut().poll(cx).is_ready(),
+            "overridden keep-alive should have resolved",
+        );
+    })
+    .await;
+}
+
+#[actix_rt::test]
+async fn keep_alive_timeout_override_capped() {
+    let buf = TestBuffer::new("GET /abcd HTTP/1.1\r\n\r\n");
+
+    let cfg = ServiceConfig::new(
+        KeepAlive::Timeout(Duration::from_millis(50)),
+        Duration::from_millis(100),
+        Duration::ZERO,
+        false,
+        None,
+    )
+    .with_max_keep_alive_timeout(Duration::from_millis(100));
+    let services = HttpFlow::new(
+        fn_service(|_req: Request| {
+            let mut res = Response::ok();
+            res.extensions_mut()
+                .insert(KeepAliveTimeout(Duration::from_secs(60)));
+            ready(Ok::<_, Error>(res))
+        }),
+        ExpectHandler,
+        None,
+    );
+
+    let h1 = Dispatcher::<_, _, _, _, UpgradeHandler>::new(
+        buf.clone(),
+        services,
+        cfg,
+        None,
+        OnConnectData::default(),
+    );
+    pin!(h1);
+
+    lazy(|cx| {
+        assert!(h1.as_mut().poll(cx).is_pending());
+        assert!(buf.take_write_buf().starts_with(b"HTTP/1.1 200 OK\r\n"));
+    })
+    .await;
+
+    // sleep longer than the maximum keep-alive timeout
+    sleep(Duration::from_millis(150)).await;
+
+    lazy(|cx| {
+        assert!(
+            h1.as_mut().poll(cx).is_ready(),
+            "capped keep-alive should have resolved",
+        );
+    })
+    .await;
+}
+
+#[actix_rt::test]
+async fn trailers() {
+    use futures_util::{stream, StreamExt as _};
+
+    use crate::{
+        body::BodyStream,
+        header::{HeaderName, HeaderValue},
+        Trailers,
+    };
+
+    let buf = TestBuffer::new("GET /abcd HTTP/1.1\r\n\r\n");
+
+    let services = HttpFlow::new(
+        fn_service(|_req: Request| {
+            let trailers = Trailers::new([HeaderName::from_static("x-checksum")]);
+
+            let body = stream::iter(["hello", "world"]).map({
+                let trailers = trailers.clone();
+                move |chunk| {
+                    trailers.insert(
+                        HeaderName::from_static("x-checksum"),
+                        HeaderValue::from_static(chunk),
+                    );
+                    Ok::<_, Error>(Bytes::from_static(chunk.as_bytes()))
+                }
+            });
+
+            let mut res = Response::ok().set_body(BodyStream::new(body));
+            res.extensions_mut().insert(trailers);
+            ready(Ok::<_, Error>(res))
+        }),
+        ExpectHandler,
+        None,
+    );
+
+    let h1 = Dispatcher::<_, _, _, _, UpgradeHandler>::new(
+        buf.clone(),
+        services,
+        ServiceConfig::default(),
+        None,
+        OnConnectData::default(),
+    );
+    pin!(h1);
+
+    lazy(|cx| {
+        assert!(h1.as_mut().poll(cx).is_pending());
+
+        let mut res = buf.take_write_buf().to_vec();
+        stabilize_date_header(&mut res);
+        let res = &res[..];
+
+        let exp = b"\
+            HTTP/1.1 200 OK\r\n\
+            transfer-encoding: chunked\r\n\
+            date: Thu, 01 Jan 1970 12:34:56 UTC\r\n\r\n\
+            5\r\nhello\r\n\
+            5\r\nworld\r\n\
+            0\r\nx-checksum: world\r\n\r\n\
+            ";
+
+        assert_eq!(
+            res,
+            exp,
+            "\nexpected response not in write buffer:\n\
+            response: {:?}\n\
+            expected: {:?}",
+            String::from_utf8_lossy(res),
+            String::from_utf8_lossy(exp)
+        );
+    })
+    .await;
+}
+
 #[actix_rt::test]
 async fn keep_alive_follow_up_req() {
     let mut buf = TestBuffer::new("GET /abcd HTTP/1.1\r\n\r\n");
@@ -970,3 +1210,45 @@ fn http_msg_creates_msg() {
         "GET / HTTP/1.1\r\nContent-Length: 3\r\n\r\n"
     );
 }
+
+#[actix_rt::test]
+async fn head_limit_exceeded() {
+    let buf = TestBuffer::new("GET /abcd HTTP/1.1\r\na: 1\r\nb: 2\r\nc: 3\r\n\r\n");
+
+    let cfg = ServiceConfig::with_head_limits(
+        KeepAlive::Disabled,
+        Duration::ZERO,
+        Duration::ZERO,
+        false,
+        None,
+        HeadLimits {
+            max_headers: 2,
+            ..HeadLimits::default()
+        },
+    );
+    let services = HttpFlow::new(ok_service(), ExpectHandler, None);
+
+    let h1 = Dispatcher::<_, _, _, _, UpgradeHandler>::new(
+        buf.clone(),
+        services,
+        cfg,
+        None,
+        OnConnectData::default(),
+    );
+    pin!(h1);
+
+    lazy(|cx| {
+        match h1.as_mut().poll(cx) {
+            Poll::Ready(Err(DispatchError::Parse(ParseError::HeadLimitExceeded {
+                limit: HeadLimit::Headers,
+                max: 2,
+            }))) => {}
+            res => panic!("unexpected result: {res:?}"),
+        }
+
+        let res = buf.take_write_buf();
+        let res = str::from_utf8(&res).unwrap();
+        assert!(res.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"));
+    })
+    .await;
+}
@@ -13,7 +13,9 @@ use crate::{
     header::{
         map::Value, HeaderMap, HeaderName, CONNECTION, CONTENT_LENGTH, DATE, TRANSFER_ENCODING,
     },
-    helpers, ConnectionType, RequestHeadType, Response, ServiceConfig, StatusCode, Version,
+    helpers,
+    responses::Trailers,
+    ConnectionType, RequestHeadType, Response, ServiceConfig, StatusCode, Version,
 };
 
 const AVERAGE_HEADER_SIZE: usize = 30;
@@ -367,6 +369,7 @@ impl<T: MessageType> MessageEncoder<T> {
 #[derive(Debug)]
 pub(crate) struct TransferEncoding {
     kind: TransferEncodingKind,
+    trailers: Option<Trailers>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -390,6 +393,7 @@ impl TransferEncoding {
     pub fn empty() -> TransferEncoding {
         TransferEncoding {
             kind: TransferEncodingKind::Length(0),
+            trailers: None,
         }
     }
 
@@ -397,6 +401,7 @@ impl TransferEncoding {
     pub fn eof() -> TransferEncoding {
         TransferEncoding {
             kind: TransferEncodingKind::Eof,
+            trailers: None,
         }
     }
 
@@ -404,6 +409,7 @@ impl TransferEncoding {
     pub fn chunked() -> TransferEncoding {
         TransferEncoding {
             kind: TransferEncodingKind::Chunked(false),
+            trailers: None,
         }
     }
 
@@ -411,9 +417,33 @@ impl TransferEncoding {
     pub fn length(len: u64) -> TransferEncoding {
         TransferEncoding {
             kind: TransferEncodingKind::Length(len),
+            trailers: None,
         }
     }
 
+    /// Sets the trailer fields sent in the last chunk of a chunked body.
+    #[inline]
+    pub fn set_trailers(&mut self, trailers: Option<Trailers>) {
+        self.trailers = trailers;
+    }
+
+    /// Writes the last chunk of a chunked body, including any trailer fields.
+    fn encode_last_chunk(trailers: Option<&Trailers>, buf: &mut BytesMut) {
+        buf.extend_from_slice(b"0\r\n");
+
+        if let Some(trailers) = trailers {
+            for (name, value) in trailers.take().iter() {
+                buf.reserve(name.as_str().len() + value.len() + 4);
+                buf.extend_from_slice(name.as_str().as_bytes());
+                buf.extend_from_slice(b": ");
+                buf.extend_from_slice(value.as_bytes());
+                buf.extend_from_slice(b"\r\n");
+            }
+        }
+
+        buf.extend_from_slice(b"\r\n");
+    }
+
     /// Encode message. Return `EOF` state of encoder
     #[inline]
     pub fn encode(&mut self, msg: &[u8], buf: &mut BytesMut) -> io::Result<bool> {
@@ -430,7 +460,7 @@ impl TransferEncoding {
 
                 if msg.is_empty() {
                     *eof = true;
-                    buf.extend_from_slice(b"0\r\n\r\n");
+                    Self::encode_last_chunk(self.trailers.as_ref(), buf);
                 } else {
                     writeln!(helpers::MutWriter(buf), "{:X}\r", msg.len())
                         .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
@@ -474,7 +504,7 @@ impl TransferEncoding {
             TransferEncodingKind::Chunked(ref mut eof) => {
                 if !*eof {
                     *eof = true;
-                    buf.extend_from_slice(b"0\r\n\r\n");
+                    Self::encode_last_chunk(self.trailers.as_ref(), buf);
                 }
                 Ok(())
             }
@@ -551,6 +581,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_chunked_te_trailers() {
+        let checksum = HeaderName::from_static("x-checksum");
+
+        let trailers = Trailers::new([checksum.clone()]);
+        let mut enc = TransferEncoding::chunked();
+        enc.set_trailers(Some(trailers.clone()));
+
+        let mut bytes = BytesMut::new();
+        enc.encode(b"test", &mut bytes).unwrap();
+        trailers.insert(checksum, HeaderValue::from_static("abc"));
+        trailers.insert(CONTENT_LENGTH, HeaderValue::from_static("4"));
+        enc.encode_eof(&mut bytes).unwrap();
+        assert_eq!(
+            bytes.split().freeze(),
+            Bytes::from_static(b"4\r\ntest\r\n0\r\nx-checksum: abc\r\n\r\n")
+        );
+
+        // trailers are not sent when the body is not chunked
+        let mut enc = TransferEncoding::length(4);
+        enc.set_trailers(Some(trailers.clone()));
+        trailers.insert(
+            HeaderName::from_static("x-checksum"),
+            HeaderValue::from_static("abc"),
+        );
+        enc.encode(b"test", &mut bytes).unwrap();
+        enc.encode_eof(&mut bytes).unwrap();
+        assert_eq!(bytes.split().freeze(), Bytes::from_static(b"test"));
+    }
+
     #[actix_rt::test]
     async fn test_camel_case() {
         let mut bytes = BytesMut::with_capacity(2048);
@@ -20,6 +20,7 @@ mod utils;
 pub use self::{
     client::{ClientCodec, ClientPayloadCodec},
     codec::Codec,
+    decoder::HeadLimits,
     dispatcher::Dispatcher,
     expect::ExpectHandler,
     payload::Payload,
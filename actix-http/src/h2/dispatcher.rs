@@ -28,7 +28,7 @@ use crate::{
         HeaderName, HeaderValue, CONNECTION, CONTENT_LENGTH, DATE, TRANSFER_ENCODING, UPGRADE,
     },
     service::HttpFlow,
-    Extensions, Method, OnConnectData, Payload, Request, Response, ResponseHead,
+    DateHeader, Extensions, Method, OnConnectData, Payload, Request, Response, ResponseHead,
 };
 
 const CHUNK_SIZE: usize = 16_384;
@@ -341,7 +341,7 @@ fn prepare_response(
     }
 
     // set date header
-    if !has_date {
+    if !has_date && config.date_header() != DateHeader::Disabled {
         let mut bytes = BytesMut::with_capacity(29);
         config.write_date_header_value(&mut bytes);
         res.headers_mut().insert(
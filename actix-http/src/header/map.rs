@@ -420,6 +420,100 @@ impl HeaderMap {
         };
     }
 
+    /// Appends a value to a header holding a comma-separated list, skipping items already listed.
+    ///
+    /// Intended for list headers such as `Vary` or `Access-Control-Expose-Headers`, so that
+    /// middleware running more than once does not produce duplicate entries. Items are compared
+    /// case-insensitively, including items within existing comma-separated values. If some items of
+    /// `value` are already listed, only the missing ones are appended. Nothing is appended if all
+    /// of them are listed, or if `key` is `Vary` and it is already `*`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use actix_http::header::{self, HeaderMap, HeaderValue};
+    /// let mut map = HeaderMap::new();
+    ///
+    /// let expose = header::ACCESS_CONTROL_EXPOSE_HEADERS;
+    /// map.insert(expose.clone(), HeaderValue::from_static("ETag, X-Total"));
+    /// map.insert_or_append_unique(expose.clone(), HeaderValue::from_static("x-total"));
+    /// map.insert_or_append_unique(expose.clone(), HeaderValue::from_static("x-total, X-Page"));
+    ///
+    /// let mut values = map.get_all(expose);
+    /// assert_eq!(values.next().unwrap(), "ETag, X-Total");
+    /// assert_eq!(values.next().unwrap(), "X-Page");
+    /// assert!(values.next().is_none());
+    /// ```
+    pub fn insert_or_append_unique(&mut self, key: HeaderName, value: HeaderValue) {
+        let Ok(items) = value.to_str() else {
+            // not a token list; only skip exact duplicates
+            if !self.get_all(&key).any(|existing| existing == value) {
+                self.append(key, value);
+            }
+            return;
+        };
+
+        let items = items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>();
+
+        let mut missing = items
+            .iter()
+            .copied()
+            .filter(|item| !self.list_contains(&key, item))
+            .collect::<Vec<_>>();
+        missing.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+        if missing.is_empty() {
+            return;
+        }
+
+        if missing.len() == items.len() {
+            self.append(key, value);
+        } else {
+            // items were taken from a valid header value so joining them is still valid
+            let value = HeaderValue::from_str(&missing.join(", ")).unwrap();
+            self.append(key, value);
+        }
+    }
+
+    /// Adds a header name to the `Vary` header, unless it is already listed.
+    ///
+    /// Existing `Vary` values are compared case-insensitively, including names within
+    /// comma-separated lists. Nothing is added if `Vary` is already `*`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use actix_http::header::{self, HeaderMap, HeaderValue};
+    /// let mut map = HeaderMap::new();
+    ///
+    /// map.insert(header::VARY, HeaderValue::from_static("Origin, Accept-Encoding"));
+    /// map.append_vary(header::ACCEPT_ENCODING);
+    /// map.append_vary(header::ACCEPT_LANGUAGE);
+    ///
+    /// let mut vary = map.get_all(header::VARY);
+    /// assert_eq!(vary.next().unwrap(), "Origin, Accept-Encoding");
+    /// assert_eq!(vary.next().unwrap(), "accept-language");
+    /// assert!(vary.next().is_none());
+    /// ```
+    pub fn append_vary(&mut self, name: HeaderName) {
+        self.insert_or_append_unique(http::header::VARY, HeaderValue::from(name));
+    }
+
+    /// Returns true if `item` is listed in the comma-separated values of the `key` header.
+    ///
+    /// Items are compared case-insensitively. A `Vary` header of `*` lists every item.
+    fn list_contains(&self, key: &HeaderName, item: &str) -> bool {
+        let is_vary = key == http::header::VARY;
+
+        self.get_all(key)
+            .filter_map(|val| val.to_str().ok())
+            .flat_map(|val| val.split(','))
+            .map(str::trim)
+            .any(|listed| (is_vary && listed == "*") || listed.eq_ignore_ascii_case(item))
+    }
+
     /// Removes all headers for a particular header name from the map.
     ///
     /// Providing an invalid header names (as a string argument) will have no effect and return
@@ -936,6 +1030,61 @@ mod tests {
     assert_impl_all!(IntoIter: Iterator, ExactSizeIterator, FusedIterator);
     assert_impl_all!(Drain<'_>: Iterator, ExactSizeIterator, FusedIterator);
 
+    #[test]
+    fn insert_or_append_unique() {
+        let mut map = HeaderMap::new();
+        map.insert_or_append_unique(header::VARY, HeaderValue::from_static("Origin"));
+        map.insert_or_append_unique(header::VARY, HeaderValue::from_static("origin"));
+        assert_eq!(map.get_all(header::VARY).count(), 1);
+
+        map.insert_or_append_unique(
+            header::VARY,
+            HeaderValue::from_static("Accept, ORIGIN, accept-encoding"),
+        );
+        let values = map.get_all(header::VARY).collect::<Vec<_>>();
+        assert_eq!(values, ["Origin", "Accept, accept-encoding"]);
+
+        map.insert_or_append_unique(header::VARY, HeaderValue::from_static("accept,Origin"));
+        assert_eq!(map.get_all(header::VARY).count(), 2);
+
+        let mut vary = HeaderMap::new();
+        vary.insert(header::VARY, HeaderValue::from_static("*"));
+        vary.insert_or_append_unique(header::VARY, HeaderValue::from_static("Origin, Accept"));
+        assert_eq!(vary.get_all(header::VARY).count(), 1);
+
+        // non-UTF-8 values are only compared exactly
+        let bytes = HeaderValue::from_bytes(b"caf\xe9").unwrap();
+        map.insert_or_append_unique(header::WARNING, bytes.clone());
+        map.insert_or_append_unique(header::WARNING, bytes);
+        assert_eq!(map.get_all(header::WARNING).count(), 1);
+    }
+
+    #[test]
+    fn append_vary() {
+        let mut map = HeaderMap::new();
+        map.append_vary(header::ACCEPT_ENCODING);
+        map.append_vary(header::ACCEPT_ENCODING);
+        assert_eq!(map.get_all(header::VARY).count(), 1);
+        assert_eq!(map.get(header::VARY).unwrap(), "accept-encoding");
+
+        let mut map = HeaderMap::new();
+        map.insert(
+            header::VARY,
+            HeaderValue::from_static("Origin,ACCEPT-ENCODING"),
+        );
+        map.append_vary(header::ACCEPT_ENCODING);
+        assert_eq!(map.get_all(header::VARY).count(), 1);
+
+        map.append_vary(header::ACCEPT_LANGUAGE);
+        assert_eq!(map.get_all(header::VARY).count(), 2);
+
+        let mut map = HeaderMap::new();
+        map.insert(header::VARY, HeaderValue::from_static("*"));
+        map.append_vary(header::ACCEPT_ENCODING);
+        assert_eq!(map.get_all(header::VARY).count(), 1);
+        assert_eq!(map.get(header::VARY).unwrap(), "*");
+    }
+
     #[test]
     fn create() {
         let map = HeaderMap::new();
@@ -5,7 +5,10 @@ use http::header::InvalidHeaderValue;
 
 use crate::{
     error::ParseError,
-    header::{self, from_one_raw_str, Header, HeaderName, HeaderValue, TryIntoHeaderValue},
+    header::{
+        self, from_one_raw_str, Header, HeaderName, HeaderValue, Quality, QualityItem,
+        TryIntoHeaderValue,
+    },
     HttpMessage,
 };
 
@@ -39,6 +42,9 @@ pub enum ContentEncoding {
 
     /// Zstd algorithm.
     Zstd,
+
+    /// LZ4 frame format.
+    Lz4,
 }
 
 impl ContentEncoding {
@@ -50,6 +56,7 @@ impl ContentEncoding {
             ContentEncoding::Gzip => "gzip",
             ContentEncoding::Deflate => "deflate",
             ContentEncoding::Zstd => "zstd",
+            ContentEncoding::Lz4 => "lz4",
             ContentEncoding::Identity => "identity",
         }
     }
@@ -62,9 +69,78 @@ impl ContentEncoding {
             ContentEncoding::Gzip => HeaderValue::from_static("gzip"),
             ContentEncoding::Deflate => HeaderValue::from_static("deflate"),
             ContentEncoding::Zstd => HeaderValue::from_static("zstd"),
+            ContentEncoding::Lz4 => HeaderValue::from_static("lz4"),
             ContentEncoding::Identity => HeaderValue::from_static("identity"),
         }
     }
+
+    /// Selects the best encoding from `available` for an `Accept-Encoding` header value.
+    ///
+    /// Encodings are chosen by their q-value; an encoding without an entry of its own uses the
+    /// q-value of the `*` entry, if there is one. Ties are broken by the order of `available`, so
+    /// it should be listed in order of server preference. Unrecognized or malformed entries are
+    /// ignored.
+    ///
+    /// The identity encoding is acceptable, with the lowest preference, unless it is excluded by
+    /// `identity;q=0` or `*;q=0`.
+    ///
+    /// Returns `None` if none of the `available` encodings are acceptable to the client, in which
+    /// case a 406 Not Acceptable response is appropriate.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_http::header::{ContentEncoding, HeaderValue};
+    ///
+    /// let accept_encoding = HeaderValue::from_static("br;q=0.1, gzip;q=0.9");
+    /// let available = [ContentEncoding::Brotli, ContentEncoding::Gzip];
+    ///
+    /// assert_eq!(
+    ///     ContentEncoding::negotiate(&accept_encoding, &available),
+    ///     Some(ContentEncoding::Gzip),
+    /// );
+    /// ```
+    pub fn negotiate(
+        accept_encoding: &HeaderValue,
+        available: &[ContentEncoding],
+    ) -> Option<ContentEncoding> {
+        let items = accept_encoding
+            .to_str()
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|item| item.trim().parse::<QualityItem<String>>().ok())
+            .collect::<Vec<_>>();
+
+        let quality_of = |name: &str| {
+            items
+                .iter()
+                .find(|q| q.item.eq_ignore_ascii_case(name))
+                .map(|q| q.quality)
+        };
+
+        let any_quality = quality_of("*");
+
+        let mut best = None;
+        let mut best_quality = Quality::ZERO;
+
+        for &enc in available {
+            let quality = match (quality_of(enc.as_str()), any_quality) {
+                (Some(quality), _) | (None, Some(quality)) => quality,
+
+                // identity is acceptable unless explicitly excluded
+                (None, None) if enc == ContentEncoding::Identity => Quality::MIN,
+
+                (None, None) => Quality::ZERO,
+            };
+
+            // only change if strictly greater so that earlier encodings are preferred
+            if quality > best_quality {
+                best = Some(enc);
+                best_quality = quality;
+            }
+        }
+
+        best
+    }
 }
 
 impl Default for ContentEncoding {
@@ -90,6 +166,8 @@ impl FromStr for ContentEncoding {
             Ok(ContentEncoding::Identity)
         } else if enc.eq_ignore_ascii_case("zstd") {
             Ok(ContentEncoding::Zstd)
+        } else if enc.eq_ignore_ascii_case("lz4") {
+            Ok(ContentEncoding::Lz4)
         } else {
             Err(ContentEncodingParseError)
         }
@@ -121,3 +199,51 @@ impl Header for ContentEncoding {
         from_one_raw_str(msg.headers().get(Self::name()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn negotiate(
+        accept_encoding: &'static str,
+        available: &[ContentEncoding],
+    ) -> Option<ContentEncoding> {
+        ContentEncoding::negotiate(&HeaderValue::from_static(accept_encoding), available)
+    }
+
+    #[test]
+    fn negotiate_quality() {
+        use ContentEncoding::*;
+
+        assert_eq!(
+            negotiate("br;q=0.1, gzip;q=0.9", &[Brotli, Gzip]),
+            Some(Gzip)
+        );
+        assert_eq!(negotiate("br, gzip", &[Gzip, Brotli]), Some(Gzip));
+        assert_eq!(negotiate("br, gzip", &[Brotli, Gzip]), Some(Brotli));
+        assert_eq!(negotiate("GZIP;Q=0.5", &[Brotli, Gzip]), Some(Gzip));
+        assert_eq!(negotiate("br;q=0.1, *;q=0.5", &[Brotli, Zstd]), Some(Zstd));
+        assert_eq!(
+            negotiate("compress, zstd;q=0.2", &[Brotli, Zstd]),
+            Some(Zstd)
+        );
+        assert_eq!(negotiate("gzip;q=nope, br", &[Gzip, Brotli]), Some(Brotli));
+    }
+
+    #[test]
+    fn negotiate_identity() {
+        use ContentEncoding::*;
+
+        assert_eq!(negotiate("", &[Gzip, Identity]), Some(Identity));
+        assert_eq!(negotiate("br", &[Gzip, Identity]), Some(Identity));
+        assert_eq!(negotiate("gzip;q=0.1", &[Gzip, Identity]), Some(Gzip));
+        assert_eq!(negotiate("br, identity;q=0", &[Gzip, Identity]), None);
+        assert_eq!(negotiate("br, *;q=0", &[Gzip, Identity]), None);
+        assert_eq!(
+            negotiate("identity, *;q=0", &[Gzip, Identity]),
+            Some(Identity)
+        );
+        assert_eq!(negotiate("br", &[Gzip]), None);
+        assert_eq!(negotiate("br", &[]), None);
+    }
+}
@@ -63,6 +63,20 @@ impl From<Option<Duration>> for KeepAlive {
     }
 }
 
+/// Per-response keep-alive timeout override.
+///
+/// When inserted into a response's extensions, the HTTP/1.x dispatcher uses this duration for the
+/// keep-alive period that follows the response instead of the server's [`KeepAlive`] setting, so
+/// it can both shorten and extend the usual timeout, e.g., for long-polling handlers. The override
+/// is capped at the server's maximum (see [`HttpServiceBuilder::max_keep_alive_timeout`]) and has
+/// no effect when keep-alive is [disabled](KeepAlive::Disabled).
+///
+/// A zero duration closes the connection as soon as the response has been sent.
+///
+/// [`HttpServiceBuilder::max_keep_alive_timeout`]: crate::HttpServiceBuilder::max_keep_alive_timeout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAliveTimeout(pub Duration);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,6 +13,7 @@
 //! | `compress-brotli`   | Payload compression support: Brotli.        |
 //! | `compress-gzip`     | Payload compression support: Deflate, Gzip. |
 //! | `compress-zstd`     | Payload compression support: Zstd.          |
+//! | `compress-lz4`      | Payload compression support: LZ4.           |
 //! | `trust-dns`         | Use [trust-dns] as the client DNS resolver. |
 //!
 //! [h2]: https://crates.io/crates/h2
@@ -64,15 +65,16 @@ pub use self::service::TlsAcceptorConfig;
 pub use self::{
     builder::HttpServiceBuilder,
     config::ServiceConfig,
+    date::DateHeader,
     error::Error,
     extensions::Extensions,
     header::ContentEncoding,
     http_message::HttpMessage,
-    keep_alive::KeepAlive,
+    keep_alive::{KeepAlive, KeepAliveTimeout},
     message::{ConnectionType, Message},
     payload::{BoxedPayloadStream, Payload},
     requests::{Request, RequestHead, RequestHeadType},
-    responses::{Response, ResponseBuilder, ResponseHead},
+    responses::{Response, ResponseBuilder, ResponseHead, Trailers},
     service::HttpService,
 };
 
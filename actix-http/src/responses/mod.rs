@@ -4,6 +4,9 @@ mod builder;
 mod head;
 #[allow(clippy::module_inception)]
 mod response;
+mod trailers;
 
 pub(crate) use self::head::BoxedResponseHead;
-pub use self::{builder::ResponseBuilder, head::ResponseHead, response::Response};
+pub use self::{
+    builder::ResponseBuilder, head::ResponseHead, response::Response, trailers::Trailers,
+};
@@ -146,6 +146,12 @@ impl<B> Response<B> {
         &self.body
     }
 
+    /// Returns a mutable reference to the body of this response.
+    #[inline]
+    pub fn body_mut(&mut self) -> &mut B {
+        &mut self.body
+    }
+
     /// Sets new body.
     #[inline]
     pub fn set_body<B2>(self, body: B2) -> Response<B2> {
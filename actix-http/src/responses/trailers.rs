@@ -0,0 +1,83 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::header::{self, HeaderMap, HeaderName, HeaderValue};
+
+/// Trailer fields sent after a streaming response body.
+///
+/// When inserted into a response's extensions, the HTTP/1.1 dispatcher sends the fields held by
+/// this handle in the last chunk of a chunked body, as described in [RFC 7230 §4.1.2]. `Trailers`
+/// is a cheaply cloneable, shared handle: keep a clone in the body stream and fill it in before the
+/// stream ends, e.g., with a checksum of the data that was sent.
+///
+/// The field names passed to [`new`](Self::new) are declared up front and advertised in the
+/// `Trailer` header by response builders that support trailers. Fields that are not allowed in
+/// trailers, such as `Content-Length` or `Transfer-Encoding`, are never sent.
+///
+/// Trailers are only sent when the body uses chunked transfer encoding; they are ignored for
+/// sized bodies, HTTP/1.0 and HTTP/2 responses.
+///
+/// [RFC 7230 §4.1.2]: https://datatracker.ietf.org/doc/html/rfc7230#section-4.1.2
+#[derive(Debug, Clone)]
+pub struct Trailers {
+    names: Rc<[HeaderName]>,
+    fields: Rc<RefCell<HeaderMap>>,
+}
+
+impl Trailers {
+    /// Constructs a new, empty set of trailers that declares the given field names.
+    pub fn new(names: impl IntoIterator<Item = HeaderName>) -> Self {
+        Self {
+            names: names.into_iter().collect(),
+            fields: Rc::new(RefCell::new(HeaderMap::new())),
+        }
+    }
+
+    /// Returns the declared trailer field names.
+    pub fn names(&self) -> &[HeaderName] {
+        &self.names
+    }
+
+    /// Inserts a trailer field, replacing any existing values for the same name.
+    pub fn insert(&self, name: HeaderName, value: HeaderValue) {
+        self.fields.borrow_mut().insert(name, value);
+    }
+
+    /// Appends a trailer field, keeping any existing values for the same name.
+    pub fn append(&self, name: HeaderName, value: HeaderValue) {
+        self.fields.borrow_mut().append(name, value);
+    }
+
+    /// Returns true if no trailer fields have been set.
+    pub fn is_empty(&self) -> bool {
+        self.fields.borrow().is_empty()
+    }
+
+    /// Removes and returns the trailer fields that may be sent.
+    pub(crate) fn take(&self) -> HeaderMap {
+        let mut fields = self.fields.take();
+        fields.retain(|name, _| !is_disallowed(name));
+        fields
+    }
+}
+
+/// Returns true for fields that RFC 7230 §4.1.2 does not allow in trailers.
+fn is_disallowed(name: &HeaderName) -> bool {
+    matches!(
+        *name,
+        header::TRANSFER_ENCODING
+            | header::CONTENT_LENGTH
+            | header::TRAILER
+            | header::HOST
+            | header::CONTENT_ENCODING
+            | header::CONTENT_TYPE
+            | header::CONTENT_RANGE
+            | header::CACHE_CONTROL
+            | header::EXPIRES
+            | header::DATE
+            | header::LOCATION
+            | header::RETRY_AFTER
+            | header::VARY
+            | header::WWW_AUTHENTICATE
+            | header::SET_COOKIE
+    )
+}
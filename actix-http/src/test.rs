@@ -11,11 +11,13 @@ use std::{
 
 use actix_codec::{AsyncRead, AsyncWrite, ReadBuf};
 use bytes::{Bytes, BytesMut};
+use futures_core::Stream;
 use http::{Method, Uri, Version};
 
 use crate::{
-    header::{HeaderMap, TryIntoHeaderPair},
-    payload::Payload,
+    error::PayloadError,
+    header::{self, HeaderMap, TryIntoHeaderPair},
+    payload::{BoxedPayloadStream, Payload},
     Request,
 };
 
@@ -105,6 +107,23 @@ impl TestRequest {
         self
     }
 
+    /// Set request payload to a stream, sent using chunked transfer encoding.
+    ///
+    /// The `Transfer-Encoding: chunked` header is set and any `Content-Length` header is removed.
+    pub fn set_chunked_payload<S>(&mut self, stream: S) -> &mut Self
+    where
+        S: Stream<Item = Result<Bytes, PayloadError>> + 'static,
+    {
+        let inner = parts(&mut self.0);
+        inner.headers.remove(header::CONTENT_LENGTH);
+        inner.headers.insert(
+            header::TRANSFER_ENCODING,
+            header::HeaderValue::from_static("chunked"),
+        );
+        inner.payload = Some(Payload::from(Box::pin(stream) as BoxedPayloadStream));
+        self
+    }
+
     pub fn take(&mut self) -> TestRequest {
         TestRequest(self.0.take())
     }
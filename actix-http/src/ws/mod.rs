@@ -145,12 +145,79 @@ impl From<&HandshakeError> for Response<BoxBody> {
     }
 }
 
+/// WebSocket handshake errors returned by [`handshake_with_protocols`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Error, From)]
+#[non_exhaustive]
+pub enum SubprotocolHandshakeError {
+    /// The request is not a valid WebSocket handshake.
+    #[display("{}", _0)]
+    Handshake(HandshakeError),
+
+    /// None of the subprotocols offered by the client are supported.
+    #[display("no supported WebSocket subprotocol")]
+    NoMatchingProtocol,
+}
+
+impl From<SubprotocolHandshakeError> for Response<BoxBody> {
+    fn from(err: SubprotocolHandshakeError) -> Self {
+        match err {
+            SubprotocolHandshakeError::Handshake(err) => err.into(),
+
+            SubprotocolHandshakeError::NoMatchingProtocol => {
+                let mut res = Response::bad_request();
+                res.head_mut().reason = Some("No supported WebSocket subprotocol");
+                res
+            }
+        }
+    }
+}
+
+impl From<&SubprotocolHandshakeError> for Response<BoxBody> {
+    fn from(err: &SubprotocolHandshakeError) -> Self {
+        (*err).into()
+    }
+}
+
 /// Verify WebSocket handshake request and create handshake response.
 pub fn handshake(req: &RequestHead) -> Result<ResponseBuilder, HandshakeError> {
     verify_handshake(req)?;
     Ok(handshake_response(req))
 }
 
+/// Verify WebSocket handshake request and create handshake response, negotiating a subprotocol.
+///
+/// The first subprotocol offered by the client in the `Sec-WebSocket-Protocol` header that is also
+/// in `protocols` is echoed back in the response. When none match, the response has no
+/// `Sec-WebSocket-Protocol` header; if `required` is true,
+/// [`SubprotocolHandshakeError::NoMatchingProtocol`] is returned instead.
+pub fn handshake_with_protocols(
+    req: &RequestHead,
+    protocols: &[&str],
+    required: bool,
+) -> Result<ResponseBuilder, SubprotocolHandshakeError> {
+    verify_handshake(req)?;
+
+    let protocol = req
+        .headers()
+        .get_all(header::SEC_WEBSOCKET_PROTOCOL)
+        .filter_map(|hdr| hdr.to_str().ok())
+        .flat_map(|hdr| hdr.split(','))
+        .map(str::trim)
+        .find(|offered| protocols.contains(offered));
+
+    let mut res = handshake_response(req);
+
+    match protocol {
+        Some(protocol) => {
+            res.insert_header((header::SEC_WEBSOCKET_PROTOCOL, protocol));
+        }
+        None if required => return Err(SubprotocolHandshakeError::NoMatchingProtocol),
+        None => {}
+    }
+
+    Ok(res)
+}
+
 /// Verify WebSocket handshake request.
 pub fn verify_handshake(req: &RequestHead) -> Result<(), HandshakeError> {
     // WebSocket accepts only GET
@@ -333,6 +400,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_handshake_with_protocols() {
+        let req = |protocols: Option<&'static str>| {
+            let mut req = TestRequest::default();
+            req.insert_header((header::UPGRADE, "websocket"))
+                .insert_header((header::CONNECTION, "upgrade"))
+                .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+                .insert_header((header::SEC_WEBSOCKET_KEY, "13"));
+            if let Some(protocols) = protocols {
+                req.insert_header((header::SEC_WEBSOCKET_PROTOCOL, protocols));
+            }
+            req.finish()
+        };
+
+        let supported = ["graphql-ws", "mqtt"];
+
+        // client preference order wins
+        let res = handshake_with_protocols(req(Some("mqtt, graphql-ws")).head(), &supported, true)
+            .unwrap()
+            .finish();
+        assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(
+            res.headers().get(header::SEC_WEBSOCKET_PROTOCOL).unwrap(),
+            "mqtt"
+        );
+
+        let res = handshake_with_protocols(req(Some("soap")).head(), &supported, false)
+            .unwrap()
+            .finish();
+        assert!(!res.headers().contains_key(header::SEC_WEBSOCKET_PROTOCOL));
+
+        assert_eq!(
+            handshake_with_protocols(req(Some("soap")).head(), &supported, true).unwrap_err(),
+            SubprotocolHandshakeError::NoMatchingProtocol,
+        );
+        assert_eq!(
+            handshake_with_protocols(req(None).head(), &supported, true).unwrap_err(),
+            SubprotocolHandshakeError::NoMatchingProtocol,
+        );
+
+        let req = TestRequest::default().finish();
+        assert_eq!(
+            handshake_with_protocols(req.head(), &supported, false).unwrap_err(),
+            SubprotocolHandshakeError::Handshake(HandshakeError::NoWebsocketUpgrade),
+        );
+    }
+
     #[test]
     fn test_ws_error_http_response() {
         let resp: Response<BoxBody> = HandshakeError::GetMethodRequired.into();
@@ -347,5 +461,10 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
         let resp: Response<BoxBody> = HandshakeError::BadWebsocketKey.into();
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp: Response<BoxBody> = SubprotocolHandshakeError::NoMatchingProtocol.into();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp: Response<BoxBody> =
+            SubprotocolHandshakeError::Handshake(HandshakeError::GetMethodRequired).into();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
     }
 }
@@ -2,12 +2,12 @@ use std::{
     convert::Infallible,
     io::{Read, Write},
     net, thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, UNIX_EPOCH},
 };
 
 use actix_http::{
     body::{self, BodyStream, BoxBody, SizedStream},
-    header, Error, HttpService, KeepAlive, Request, Response, StatusCode, Version,
+    header, DateHeader, Error, HttpService, KeepAlive, Request, Response, StatusCode, Version,
 };
 use actix_http_test::test_server;
 use actix_rt::{net::TcpStream, time::sleep};
@@ -40,6 +40,57 @@ async fn h1_basic() {
     srv.stop().await;
 }
 
+#[actix_rt::test]
+async fn h1_date_header() {
+    let mut srv = test_server(|| {
+        HttpService::build()
+            .date_header(DateHeader::Disabled)
+            .h1(|_| ok::<_, Infallible>(Response::ok()))
+            .tcp()
+    })
+    .await;
+
+    let response = srv.get("/").send().await.unwrap();
+    assert!(!response.headers().contains_key(header::DATE));
+
+    srv.stop().await;
+
+    let time = UNIX_EPOCH + Duration::from_secs(784_111_777);
+    let mut srv = test_server(move || {
+        HttpService::build()
+            .date_header(DateHeader::Fixed(time))
+            .h1(|req: Request| {
+                let mut res = Response::ok();
+
+                // a date set by the service takes precedence
+                if req.path() == "/archived" {
+                    res.headers_mut().insert(
+                        header::DATE,
+                        header::HeaderValue::from_static("Thu, 01 Jan 1970 00:00:00 GMT"),
+                    );
+                }
+
+                ok::<_, Infallible>(res)
+            })
+            .tcp()
+    })
+    .await;
+
+    let response = srv.get("/").send().await.unwrap();
+    assert_eq!(
+        response.headers().get(header::DATE).unwrap(),
+        "Sun, 06 Nov 1994 08:49:37 GMT"
+    );
+
+    let response = srv.get("/archived").send().await.unwrap();
+    assert_eq!(
+        response.headers().get_all(header::DATE).collect::<Vec<_>>(),
+        ["Thu, 01 Jan 1970 00:00:00 GMT"]
+    );
+
+    srv.stop().await;
+}
+
 #[actix_rt::test]
 async fn h1_2() {
     let mut srv = test_server(|| {
@@ -9,12 +9,12 @@
 
 use std::collections::HashSet;
 
-use darling::{FromDeriveInput, FromField, FromMeta};
+use darling::{util::Override, FromDeriveInput, FromField, FromMeta};
 use parse_size::parse_size;
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::quote;
-use syn::{parse_macro_input, Type};
+use syn::{parse_macro_input, Expr, GenericArgument, Path, PathArguments, Type};
 
 #[derive(FromMeta)]
 enum DuplicateField {
@@ -42,12 +42,31 @@ struct MultipartFormAttrs {
 struct FieldAttrs {
     rename: Option<String>,
     limit: Option<String>,
+    limit_each: Option<String>,
+    #[darling(multiple)]
+    content_type: Vec<String>,
+    with: Option<Path>,
+    flatten: bool,
+    trim: bool,
+    memory_exempt: bool,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    default: Option<Override<Expr>>,
 }
 
 struct ParsedField<'t> {
     serialization_name: String,
     rust_name: &'t Ident,
     limit: Option<usize>,
+    limit_each: Option<usize>,
+    content_types: Vec<String>,
+    with: Option<Path>,
+    flatten: bool,
+    trim: bool,
+    memory_exempt: bool,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    default: Option<Override<Expr>>,
     ty: &'t Type,
 }
 
@@ -86,6 +105,47 @@ struct ParsedField<'t> {
 /// }
 /// ```
 ///
+/// # List Lengths
+///
+/// You can use the `#[multipart(min_len = <n>)]` and `#[multipart(max_len = <n>)]` attributes on a
+/// `Vec` field to bound the number of parts received under its name. A form with too few or too
+/// many parts for the field is rejected with a `MultipartError::InvalidFieldCount` error.
+///
+/// Note that `max_len` is checked once the whole form has been read; use a field limit or
+/// `MultipartFormConfig::max_parts` to stop reading early.
+///
+/// ```
+/// use actix_multipart::form::{tempfile::TempFile, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     // accept between 1 and 10 photos
+///     #[multipart(min_len = 1, max_len = 10)]
+///     photos: Vec<TempFile>,
+/// }
+/// ```
+///
+/// # Default Values
+///
+/// A missing field with a bare `T` type is rejected with a `MultipartError::MissingField` error.
+/// You can use the `#[multipart(default)]` attribute to fill in `Default::default()` instead, or
+/// `#[multipart(default = "<expr>")]` to fill in the value of an expression. The default is only
+/// used when no part is received under the field's name; a part that fails to be read is still an
+/// error.
+///
+/// ```
+/// use actix_multipart::form::{text::Text, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     #[multipart(default)]
+///     title: Text<String>,
+///
+///     #[multipart(default = "Text(10)")]
+///     page_size: Text<u32>,
+/// }
+/// ```
+///
 /// # Field Renaming
 ///
 /// You can use the `#[multipart(rename = "foo")]` attribute to receive a field by a different name.
@@ -120,6 +180,144 @@ struct ParsedField<'t> {
 /// }
 /// ```
 ///
+/// A field `limit` is shared by all parts received under the field's name, so it bounds the
+/// combined size of the items in a `Vec`. Use the `#[multipart(limit_each = "<size>")]` attribute
+/// to bound the size of each part on its own instead. Both attributes can be set on the same field,
+/// in which case every part must fit within `limit_each` and all parts together within `limit`.
+/// Neither replaces the form's total and memory limits, which keep counting across all fields.
+///
+/// ```
+/// use actix_multipart::form::{tempfile::TempFile, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     // up to 10 MiB per file, and up to 50 MiB for all files
+///     #[multipart(limit_each = "10 MiB", limit = "50 MiB")]
+///     files: Vec<TempFile>,
+/// }
+/// ```
+///
+/// # Memory Limit Exemption
+///
+/// Fields read into memory, such as `Text` and `Bytes`, count towards the form's memory limit,
+/// while fields streamed to disk do not. You can use the `#[multipart(memory_exempt)]` attribute
+/// to exclude an in-memory field from the memory limit as well.
+///
+/// The field is then only bounded by its own limit and the total limit, so a client can make the
+/// server buffer up to the total limit (50MiB by default) for that field. Only use this together
+/// with a field `limit` that you are comfortable holding in memory for every concurrent request.
+///
+/// ```
+/// use actix_multipart::form::{tempfile::TempFile, text::Text, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     #[multipart(memory_exempt, limit = "8 MiB")]
+///     manifest: Text<String>,
+///
+///     file: TempFile,
+/// }
+/// ```
+///
+/// # Field Content Types
+///
+/// You can use the `#[multipart(content_type = "<mime>")]` attribute, one or more times, to restrict
+/// the content types accepted for a field. The subtype, or both the type and subtype, may be a `*`
+/// wildcard. A field without a `Content-Type` header is treated as "text/plain".
+///
+/// A field with any other content type is rejected, before its data is read, with a
+/// `MultipartError::UnsupportedContentType` error.
+///
+/// ```
+/// use actix_multipart::form::{tempfile::TempFile, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     #[multipart(content_type = "image/png", content_type = "image/jpeg")]
+///     avatar: TempFile,
+///
+///     #[multipart(content_type = "video/*")]
+///     videos: Vec<TempFile>,
+/// }
+/// ```
+///
+/// # Custom Parsing
+///
+/// You can use the `#[multipart(with = "path::to::parse_fn")]` attribute to read a text field and
+/// parse it with a function of the form `fn(&str) -> Result<T, E>`, where `T` is the type of the
+/// struct field. This is useful for values that cannot be expressed by `Text`, such as
+/// comma-separated lists.
+///
+/// The field is read in the same way as `Text`, so it is subject to the `TextConfig` in app data.
+/// The field is required and duplicates are handled like any other field. Parse errors are
+/// returned as a `MultipartError::Field` error.
+///
+/// ```
+/// use std::num::ParseIntError;
+///
+/// use actix_multipart::form::MultipartForm;
+///
+/// fn parse_ids(text: &str) -> Result<Vec<u32>, ParseIntError> {
+///     text.split(',').map(str::parse).collect()
+/// }
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     #[multipart(with = "parse_ids")]
+///     ids: Vec<u32>,
+/// }
+/// ```
+///
+/// # Trimming Whitespace
+///
+/// You can use the `#[multipart(trim)]` attribute to remove leading and trailing whitespace from a
+/// `Text` field, or a field with a `with` attribute, before it is parsed. This is useful for
+/// numbers and other values that some clients send with stray line breaks. Text is never trimmed
+/// unless this attribute is present, so `Text<String>` fields receive the exact data sent by the
+/// client by default.
+///
+/// ```
+/// use actix_multipart::form::{text::Text, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     #[multipart(trim)]
+///     age: Text<u32>,
+///
+///     #[multipart(trim)]
+///     tags: Vec<Text<String>>,
+/// }
+/// ```
+///
+/// # Flattening
+///
+/// You can use the `#[multipart(flatten)]` attribute to collect a group of fields into a nested
+/// struct that also derives `MultipartForm`. Fields named `<field>.<name>`, where `<field>` is the
+/// (possibly renamed) name of the flattened field, are read by the nested struct as `<name>`.
+///
+/// The nested struct's own attributes, such as limits and `deny_unknown_fields`, apply to its
+/// fields. A field name that could be routed to more than one struct field, such as `address.city`
+/// alongside a flattened `address` field, is a compile-time error.
+///
+/// ```
+/// use actix_multipart::form::{text::Text, MultipartForm};
+///
+/// #[derive(MultipartForm)]
+/// struct Address {
+///     city: Text<String>,
+///     zip: Text<String>,
+/// }
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     name: Text<String>,
+///
+///     // reads the `address.city` and `address.zip` fields
+///     #[multipart(flatten)]
+///     address: Address,
+/// }
+/// ```
+///
 /// # Unknown Fields
 ///
 /// By default fields with an unknown name are ignored. They can be rejected using the
@@ -191,21 +389,99 @@ pub fn impl_multipart_form(input: proc_macro::TokenStream) -> proc_macro::TokenS
             let attrs = FieldAttrs::from_field(field).map_err(|err| err.write_errors())?;
             let serialization_name = attrs.rename.unwrap_or_else(|| rust_name.to_string());
 
-            let limit = match attrs.limit.map(|limit| match parse_size(&limit) {
-                Ok(size) => Ok(usize::try_from(size).unwrap()),
-                Err(err) => Err(syn::Error::new(
-                    field.ident.as_ref().unwrap().span(),
-                    format!("Could not parse size limit `{}`: {}", limit, err),
-                )),
-            }) {
-                Some(Err(err)) => return Err(compile_err(err)),
-                limit => limit.map(Result::unwrap),
+            if attrs.flatten
+                && (attrs.limit.is_some()
+                    || attrs.limit_each.is_some()
+                    || !attrs.content_type.is_empty()
+                    || attrs.with.is_some()
+                    || attrs.trim
+                    || attrs.memory_exempt
+                    || attrs.min_len.is_some()
+                    || attrs.max_len.is_some()
+                    || attrs.default.is_some())
+            {
+                return Err(compile_err(syn::Error::new(
+                    rust_name.span(),
+                    "`flatten` can only be combined with `rename`",
+                )));
+            }
+
+            let parse_limit = |limit: Option<String>| {
+                limit
+                    .map(|limit| match parse_size(&limit) {
+                        Ok(size) => Ok(usize::try_from(size).unwrap()),
+                        Err(err) => Err(compile_err(syn::Error::new(
+                            field.ident.as_ref().unwrap().span(),
+                            format!("Could not parse size limit `{}`: {}", limit, err),
+                        ))),
+                    })
+                    .transpose()
             };
 
+            let limit = parse_limit(attrs.limit)?;
+            let limit_each = parse_limit(attrs.limit_each)?;
+
+            if attrs.default.is_some()
+                && attrs.with.is_none()
+                && (is_type(&field.ty, "Option") || is_type(&field.ty, "Vec"))
+            {
+                return Err(compile_err(syn::Error::new(
+                    rust_name.span(),
+                    "`default` cannot be used on `Option` or `Vec` fields",
+                )));
+            }
+
+            if attrs.trim && attrs.with.is_none() && !is_text_field(&field.ty) {
+                return Err(compile_err(syn::Error::new(
+                    rust_name.span(),
+                    "`trim` can only be used on `Text` fields or fields with a `with` attribute",
+                )));
+            }
+
+            if (attrs.min_len.is_some() || attrs.max_len.is_some()) && !is_type(&field.ty, "Vec") {
+                return Err(compile_err(syn::Error::new(
+                    rust_name.span(),
+                    "`min_len` and `max_len` can only be used on `Vec` fields",
+                )));
+            }
+
+            if let (Some(min_len), Some(max_len)) = (attrs.min_len, attrs.max_len) {
+                if min_len > max_len {
+                    return Err(compile_err(syn::Error::new(
+                        rust_name.span(),
+                        "`min_len` must not be greater than `max_len`",
+                    )));
+                }
+            }
+
+            for content_type in &attrs.content_type {
+                let valid = match content_type.split_once('/') {
+                    Some(("*", subtype)) => subtype == "*",
+                    Some((ty, subtype)) => !ty.is_empty() && !subtype.is_empty(),
+                    None => false,
+                };
+
+                if !valid {
+                    return Err(compile_err(syn::Error::new(
+                        field.ident.as_ref().unwrap().span(),
+                        format!("Could not parse content type `{}`", content_type),
+                    )));
+                }
+            }
+
             Ok(ParsedField {
                 serialization_name,
                 rust_name,
                 limit,
+                limit_each,
+                content_types: attrs.content_type,
+                with: attrs.with,
+                flatten: attrs.flatten,
+                trim: attrs.trim,
+                memory_exempt: attrs.memory_exempt,
+                min_len: attrs.min_len,
+                max_len: attrs.max_len,
+                default: attrs.default,
                 ty: &field.ty,
             })
         })
@@ -226,10 +502,29 @@ pub fn impl_multipart_form(input: proc_macro::TokenStream) -> proc_macro::TokenS
         }
     }
 
+    // Check that no field name can also be routed to a flattened field
+    for flattened in parsed.iter().filter(|field| field.flatten) {
+        let prefix = format!("{}.", flattened.serialization_name);
+
+        for field in &parsed {
+            if field.serialization_name.starts_with(&prefix) {
+                return compile_err(syn::Error::new(
+                    field.rust_name.span(),
+                    format!(
+                        "Field name `{}` is ambiguous with flattened field `{}`",
+                        field.serialization_name, flattened.serialization_name,
+                    ),
+                ));
+            }
+        }
+    }
+
     // Return value when a field name is not supported by the form
     let unknown_field_result = if attrs.deny_unknown_fields {
         quote!(::std::result::Result::Err(
-            ::actix_multipart::MultipartError::UnknownField(field.name().unwrap().to_string())
+            ::actix_multipart::MultipartError::UnknownField(
+                ::actix_multipart::form::field_name(&field).to_owned()
+            )
         ))
     } else {
         quote!(::std::result::Result::Ok(()))
@@ -242,27 +537,103 @@ pub fn impl_multipart_form(input: proc_macro::TokenStream) -> proc_macro::TokenS
         DuplicateField::Replace => quote!(::actix_multipart::form::DuplicateField::Replace),
     };
 
-    // limit() implementation
+    // limit() and limit_each() implementations
     let mut limit_impl = quote!();
+    let mut flattened_limit_impl = quote!();
+    let mut limit_each_impl = quote!();
+    let mut flattened_limit_each_impl = quote!();
     for field in &parsed {
         let name = &field.serialization_name;
+
+        if field.flatten {
+            let prefix = format!("{name}.");
+            let ty = &field.ty;
+            flattened_limit_impl.extend(quote!(
+                if let ::std::option::Option::Some(field_name) = field_name.strip_prefix(#prefix) {
+                    return <#ty as ::actix_multipart::form::MultipartCollect>::limit(field_name);
+                }
+            ));
+            flattened_limit_each_impl.extend(quote!(
+                if let ::std::option::Option::Some(field_name) = field_name.strip_prefix(#prefix) {
+                    return <#ty as ::actix_multipart::form::MultipartCollect>::limit_each(field_name);
+                }
+            ));
+            continue;
+        }
+
         if let Some(value) = field.limit {
             limit_impl.extend(quote!(
                 #name => ::std::option::Option::Some(#value),
             ));
         }
+
+        if let Some(value) = field.limit_each {
+            limit_each_impl.extend(quote!(
+                #name => ::std::option::Option::Some(#value),
+            ));
+        }
     }
 
     // handle_field() implementation
     let mut handle_field_impl = quote!();
+    let mut flattened_handle_field_impl = quote!();
     for field in &parsed {
         let name = &field.serialization_name;
         let ty = &field.ty;
 
-        handle_field_impl.extend(quote!(
-            #name => ::std::boxed::Box::pin(
+        if field.flatten {
+            let prefix = format!("{name}.");
+            flattened_handle_field_impl.extend(quote!(
+                if ::actix_multipart::form::field_key(&field).starts_with(#prefix) {
+                    return ::actix_multipart::form::handle_flattened_field::<#ty>(req, field, limits, state, #name);
+                }
+            ));
+            continue;
+        }
+
+        let content_type_check = if field.content_types.is_empty() {
+            quote!()
+        } else {
+            let content_types = &field.content_types;
+            quote!(
+                if let ::std::result::Result::Err(err) = ::actix_multipart::form::check_content_type(&field, &[#(#content_types),*]) {
+                    return ::std::boxed::Box::pin(::std::future::ready(::std::result::Result::Err(err)));
+                }
+            )
+        };
+
+        let trim = if field.trim {
+            quote!(
+                let field = ::actix_multipart::form::text::trim_field(field);
+            )
+        } else {
+            quote!()
+        };
+
+        let memory_exempt = if field.memory_exempt {
+            quote!(
+                limits.exempt_field_from_memory_limit();
+            )
+        } else {
+            quote!()
+        };
+
+        let read_field = match &field.with {
+            Some(with) => quote!(
+                ::actix_multipart::form::text::handle_field_with(req, field, limits, state, #duplicate_field, #with)
+            ),
+            None => quote!(
                 <#ty as ::actix_multipart::form::FieldGroupReader>::handle_field(req, field, limits, state, #duplicate_field)
             ),
+        };
+
+        handle_field_impl.extend(quote!(
+            #name => {
+                #content_type_check
+                #trim
+                #memory_exempt
+                ::std::boxed::Box::pin(#read_field)
+            },
         ));
     }
 
@@ -272,27 +643,73 @@ pub fn impl_multipart_form(input: proc_macro::TokenStream) -> proc_macro::TokenS
         let name = &field.serialization_name;
         let rust_name = &field.rust_name;
         let ty = &field.ty;
+
+        let value = match &field.with {
+            _ if field.flatten => quote!(
+                ::actix_multipart::form::flattened_from_state::<#ty>(#name, &mut state)
+            ),
+            Some(_) => quote!(
+                ::actix_multipart::form::text::from_state_with::<#ty>(#name, &mut state)
+            ),
+            None => quote!(
+                <#ty as ::actix_multipart::form::FieldGroupReader>::from_state(#name, &mut state)
+            ),
+        };
+
+        let value = match &field.default {
+            Some(Override::Inherit) => quote!(
+                ::actix_multipart::form::default_if_missing(#name, #value, ::std::default::Default::default)?
+            ),
+            Some(Override::Explicit(default)) => quote!(
+                ::actix_multipart::form::default_if_missing(#name, #value, || #default)?
+            ),
+            None => quote!(#value?),
+        };
+
+        let value = if field.min_len.is_some() || field.max_len.is_some() {
+            let min_len = quote_option(field.min_len);
+            let max_len = quote_option(field.max_len);
+            quote!(
+                ::actix_multipart::form::check_field_count(#name, #value, #min_len, #max_len)?
+            )
+        } else {
+            value
+        };
+
         from_state_impl.extend(quote!(
-            #rust_name: <#ty as ::actix_multipart::form::FieldGroupReader>::from_state(#name, &mut state)?,
+            #rust_name: #value,
         ));
     }
 
     let gen = quote! {
         impl ::actix_multipart::form::MultipartCollect for #name {
             fn limit(field_name: &str) -> ::std::option::Option<usize> {
+                #flattened_limit_impl
+
                 match field_name {
                     #limit_impl
                     _ => None,
                 }
             }
 
+            fn limit_each(field_name: &str) -> ::std::option::Option<usize> {
+                #flattened_limit_each_impl
+
+                match field_name {
+                    #limit_each_impl
+                    _ => None,
+                }
+            }
+
             fn handle_field<'t>(
                 req: &'t ::actix_web::HttpRequest,
                 field: ::actix_multipart::Field,
                 limits: &'t mut ::actix_multipart::form::Limits,
                 state: &'t mut ::actix_multipart::form::State,
             ) -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = ::std::result::Result<(), ::actix_multipart::MultipartError>> + 't>> {
-                match field.name().unwrap() {
+                #flattened_handle_field_impl
+
+                match ::actix_multipart::form::field_key(&field) {
                     #handle_field_impl
                     _ => return ::std::boxed::Box::pin(::std::future::ready(#unknown_field_result)),
                 }
@@ -309,6 +726,48 @@ pub fn impl_multipart_form(input: proc_macro::TokenStream) -> proc_macro::TokenS
     gen.into()
 }
 
+/// Returns true if the type is syntactically the named type, such as `Vec`.
+fn is_type(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+/// Checks whether the type is `Text`, optionally wrapped in an `Option` or `Vec`.
+fn is_text_field(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+
+    if segment.ident != "Option" && segment.ident != "Vec" {
+        return segment.ident == "Text";
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => {
+            matches!(args.args.first(), Some(GenericArgument::Type(inner)) if is_type(inner, "Text"))
+        }
+        _ => false,
+    }
+}
+
+/// Generates tokens for an optional length.
+fn quote_option(value: Option<usize>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value)),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
 /// Transform a syn error into a token stream for returning.
 fn compile_err(err: syn::Error) -> TokenStream {
     TokenStream::from(err.to_compile_error())
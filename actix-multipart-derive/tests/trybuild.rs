@@ -13,4 +13,18 @@ fn compile_macros() {
 
     t.pass("tests/trybuild/size-limits.rs");
     t.compile_fail("tests/trybuild/size-limit-parse-fail.rs");
+
+    t.pass("tests/trybuild/content-type.rs");
+    t.pass("tests/trybuild/with.rs");
+    t.pass("tests/trybuild/trim.rs");
+    t.compile_fail("tests/trybuild/trim-fail.rs");
+
+    t.pass("tests/trybuild/flatten.rs");
+    t.compile_fail("tests/trybuild/flatten-ambiguous-fail.rs");
+
+    t.pass("tests/trybuild/list-length.rs");
+    t.compile_fail("tests/trybuild/list-length-fail.rs");
+
+    t.pass("tests/trybuild/default.rs");
+    t.compile_fail("tests/trybuild/default-fail.rs");
 }
@@ -0,0 +1,21 @@
+use actix_web::{web, App, Responder};
+
+use actix_multipart::form::{bytes::Bytes, tempfile::TempFile, MultipartForm};
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(content_type = "image/png", content_type = "image/jpeg")]
+    image: TempFile,
+
+    #[multipart(content_type = "text/*")]
+    notes: Vec<Bytes>,
+}
+
+async fn handler(_form: MultipartForm<Form>) -> impl Responder {
+    "Hello World!"
+}
+
+#[actix_web::main]
+async fn main() {
+    App::new().default_service(web::to(handler));
+}
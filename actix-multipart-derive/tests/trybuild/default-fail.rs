@@ -0,0 +1,15 @@
+use actix_multipart::form::{text::Text, MultipartForm};
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(default)]
+    description: Option<Text<String>>,
+}
+
+#[derive(MultipartForm)]
+struct Form2 {
+    #[multipart(default = "Vec::new()")]
+    tags: Vec<Text<String>>,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use actix_web::{web, App, Responder};
+
+use actix_multipart::form::{text::Text, MultipartForm};
+
+fn parse_ids(text: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+    text.split(',').map(str::parse).collect()
+}
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(default)]
+    title: Text<String>,
+
+    #[multipart(default = "Text(10)")]
+    page_size: Text<u32>,
+
+    #[multipart(with = "parse_ids", default)]
+    ids: Vec<u32>,
+}
+
+async fn handler(_form: MultipartForm<Form>) -> impl Responder {
+    "Hello World!"
+}
+
+#[actix_web::main]
+async fn main() {
+    App::new().default_service(web::to(handler));
+}
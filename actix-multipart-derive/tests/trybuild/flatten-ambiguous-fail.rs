@@ -0,0 +1,26 @@
+use actix_multipart::form::{text::Text, MultipartForm};
+
+#[derive(MultipartForm)]
+struct Address {
+    city: Text<String>,
+}
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(flatten)]
+    address: Address,
+
+    #[multipart(rename = "address.city")]
+    city: Text<String>,
+}
+
+#[derive(MultipartForm)]
+struct NestedForm {
+    #[multipart(flatten)]
+    address: Address,
+
+    #[multipart(flatten, rename = "address.billing")]
+    billing: Address,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use actix_web::{web, App, Responder};
+
+use actix_multipart::form::{tempfile::TempFile, text::Text, MultipartForm};
+
+#[derive(MultipartForm)]
+struct Address {
+    city: Text<String>,
+
+    #[multipart(limit = "1 KiB")]
+    zip: Option<Text<String>>,
+}
+
+#[derive(MultipartForm)]
+struct Form {
+    name: Text<String>,
+
+    #[multipart(flatten)]
+    address: Address,
+
+    #[multipart(flatten, rename = "billing-address")]
+    billing: Address,
+
+    files: Vec<TempFile>,
+}
+
+async fn handler(_form: MultipartForm<Form>) -> impl Responder {
+    "Hello World!"
+}
+
+#[actix_web::main]
+async fn main() {
+    App::new().default_service(web::to(handler));
+}
@@ -0,0 +1,15 @@
+use actix_multipart::form::{text::Text, MultipartForm};
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(min_len = 1)]
+    description: Text<String>,
+}
+
+#[derive(MultipartForm)]
+struct Form2 {
+    #[multipart(min_len = 5, max_len = 2)]
+    tags: Vec<Text<String>>,
+}
+
+fn main() {}
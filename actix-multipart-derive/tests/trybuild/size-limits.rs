@@ -9,6 +9,12 @@ struct Form {
 
     #[multipart(limit = "512 MiB")]
     files: Vec<TempFile>,
+
+    #[multipart(limit_each = "10 MiB")]
+    images: Vec<TempFile>,
+
+    #[multipart(limit_each = "1 MiB", limit = "4 MiB")]
+    thumbnails: Vec<TempFile>,
 }
 
 async fn handler(_form: MultipartForm<Form>) -> impl Responder {
@@ -0,0 +1,15 @@
+use actix_multipart::form::{bytes::Bytes, MultipartForm};
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(trim)]
+    file: Bytes,
+}
+
+#[derive(MultipartForm)]
+struct Form2 {
+    #[multipart(trim)]
+    files: Vec<Bytes>,
+}
+
+fn main() {}
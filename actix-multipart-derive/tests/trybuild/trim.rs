@@ -0,0 +1,31 @@
+use actix_web::{web, App, Responder};
+
+use actix_multipart::form::{text::Text, MultipartForm};
+
+fn parse_upper(text: &str) -> Result<String, &'static str> {
+    Ok(text.to_uppercase())
+}
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(trim)]
+    age: Text<u32>,
+
+    #[multipart(trim, rename = "tag")]
+    tags: Vec<Text<String>>,
+
+    #[multipart(trim)]
+    nickname: Option<Text<String>>,
+
+    #[multipart(trim, with = "parse_upper")]
+    code: String,
+}
+
+async fn handler(_form: MultipartForm<Form>) -> impl Responder {
+    "Hello World!"
+}
+
+#[actix_web::main]
+async fn main() {
+    App::new().default_service(web::to(handler));
+}
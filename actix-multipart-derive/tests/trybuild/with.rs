@@ -0,0 +1,33 @@
+use std::num::ParseIntError;
+
+use actix_web::{web, App, Responder};
+
+use actix_multipart::form::MultipartForm;
+
+fn parse_ids(text: &str) -> Result<Vec<u32>, ParseIntError> {
+    text.split(',').map(str::parse).collect()
+}
+
+mod parse {
+    pub fn upper(text: &str) -> Result<String, &'static str> {
+        Ok(text.to_uppercase())
+    }
+}
+
+#[derive(MultipartForm)]
+struct Form {
+    #[multipart(with = "parse_ids", limit = "1 KiB")]
+    ids: Vec<u32>,
+
+    #[multipart(with = "parse::upper", content_type = "text/plain")]
+    name: String,
+}
+
+async fn handler(_form: MultipartForm<Form>) -> impl Responder {
+    "Hello World!"
+}
+
+#[actix_web::main]
+async fn main() {
+    App::new().default_service(web::to(handler));
+}
@@ -1,14 +1,16 @@
 //! Error and Result module
 
+use std::error::Error as StdError;
+
 use actix_web::{
     error::{ParseError, PayloadError},
     http::StatusCode,
     ResponseError,
 };
-use derive_more::{Display, Error, From};
+use derive_more::{Display, From};
 
 /// A set of errors that can occur during parsing multipart streams.
-#[derive(Debug, Display, From, Error)]
+#[derive(Debug, Display, From)]
 #[non_exhaustive]
 pub enum Error {
     /// Could not find Content-Type header.
@@ -81,17 +83,94 @@ pub enum Error {
     /// Duplicate field found (for structure that opted-in to denying duplicate fields).
     #[display("Duplicate field found: {_0}")]
     #[from(ignore)]
-    DuplicateField(#[error(not(source))] String),
+    DuplicateField(String),
 
     /// Required field is missing.
     #[display("Required field is missing: {_0}")]
     #[from(ignore)]
-    MissingField(#[error(not(source))] String),
+    MissingField(String),
 
     /// Unknown field (for structure that opted-in to denying unknown fields).
     #[display("Unknown field: {_0}")]
     #[from(ignore)]
-    UnknownField(#[error(not(source))] String),
+    UnknownField(String),
+
+    /// Form contains more fields than allowed by [`MultipartFormConfig::max_parts`].
+    ///
+    /// [`MultipartFormConfig::max_parts`]: crate::form::MultipartFormConfig::max_parts
+    #[display("Too many fields in multipart form")]
+    TooManyFields,
+
+    /// Number of values received for a list field is outside of the bounds set by its `min_len`
+    /// and `max_len` attributes.
+    #[display("Invalid number of values for field {name}: got {len}, expected {}", expected_len(*min, *max))]
+    InvalidFieldCount {
+        /// Name of the field.
+        name: String,
+
+        /// Number of values received.
+        len: usize,
+
+        /// Minimum number of values, if any.
+        min: Option<usize>,
+
+        /// Maximum number of values, if any.
+        max: Option<usize>,
+    },
+
+    /// Field content type is not one of those accepted by the form.
+    #[display("Unsupported content type for field: {_0}")]
+    #[from(ignore)]
+    UnsupportedContentType(String),
+}
+
+/// Describes the expected number of values for [`Error::InvalidFieldCount`].
+fn expected_len(min: Option<usize>, max: Option<usize>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("between {min} and {max}"),
+        (Some(min), None) => format!("at least {min}"),
+        (None, Some(max)) => format!("at most {max}"),
+        (None, None) => "any number".to_owned(),
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Parse(err) => Some(err),
+            Error::Payload(err) => Some(err),
+            Error::Field { source, .. } => Some(field_error_source(source)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the error raised by a field reader as the source of [`Error::Field`].
+///
+/// `actix_web::Error` does not expose its cause through `source()`, so the errors produced by this
+/// crate's field readers are unwrapped here to keep their own sources reachable. Any other error,
+/// such as one from a custom field reader, is returned as the wrapping `actix_web::Error`; the
+/// original error can be recovered from it using [`as_error()`](actix_web::Error::as_error).
+fn field_error_source(err: &actix_web::Error) -> &(dyn StdError + 'static) {
+    use crate::form::{json::JsonFieldError, sink::SinkError, text::TextError};
+
+    #[cfg(feature = "tempfile")]
+    if let Some(err) = err.as_error::<crate::form::tempfile::TempFileError>() {
+        return err;
+    }
+
+    if let Some(err) = err.as_error::<JsonFieldError>() {
+        err
+    } else if let Some(err) = err.as_error::<TextError>() {
+        err
+    } else if let Some(err) = err.as_error::<SinkError>() {
+        err
+    } else if let Some(err) = err.as_error::<Error>() {
+        err
+    } else {
+        // unknown error types can not be unwrapped without knowing that they implement `Error`
+        err
+    }
 }
 
 /// Return `BadRequest` for `MultipartError`.
@@ -99,7 +178,9 @@ impl ResponseError for Error {
     fn status_code(&self) -> StatusCode {
         match &self {
             Error::Field { source, .. } => source.as_response_error().status_code(),
-            Error::ContentTypeIncompatible => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Error::ContentTypeIncompatible | Error::UnsupportedContentType(_) => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
             _ => StatusCode::BAD_REQUEST,
         }
     }
@@ -114,4 +195,65 @@ mod tests {
         let resp = Error::BoundaryMissing.error_response();
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
+
+    #[test]
+    fn test_invalid_field_count_display() {
+        let err = Error::InvalidFieldCount {
+            name: "photos".to_owned(),
+            len: 0,
+            min: Some(1),
+            max: Some(10),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid number of values for field photos: got 0, expected between 1 and 10"
+        );
+        assert_eq!(err.error_response().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[cfg(feature = "tempfile")]
+    #[test]
+    fn test_field_error_source_chain() {
+        use std::io;
+
+        use crate::form::tempfile::TempFileError;
+
+        let err = Error::Field {
+            name: "file".to_owned(),
+            source: TempFileError::FileIo(io::Error::other("disk full")).into(),
+        };
+
+        let source = err.source().unwrap();
+        assert!(source.is::<TempFileError>());
+
+        let root = source.source().unwrap();
+        assert_eq!(
+            root.downcast_ref::<io::Error>().unwrap().to_string(),
+            "disk full"
+        );
+        assert!(root.source().is_none());
+
+        let err = Error::Payload(PayloadError::Incomplete(None));
+        assert!(err.source().unwrap().is::<PayloadError>());
+    }
+
+    #[test]
+    fn test_field_error_custom_source() {
+        #[derive(Debug, derive_more::Display)]
+        #[display("custom reader failed")]
+        struct CustomError;
+
+        impl ResponseError for CustomError {}
+
+        let err = Error::Field {
+            name: "custom".to_owned(),
+            source: CustomError.into(),
+        };
+
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), "custom reader failed");
+
+        let source = source.downcast_ref::<actix_web::Error>().unwrap();
+        assert!(source.as_error::<CustomError>().is_some());
+    }
 }
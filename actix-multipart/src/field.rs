@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     cell::RefCell,
     cmp, fmt,
     future::poll_fn,
@@ -10,7 +11,7 @@ use std::{
 
 use actix_web::{
     error::PayloadError,
-    http::header::{self, ContentDisposition, HeaderMap},
+    http::header::{self, Charset, ContentDisposition, ExtendedValue, HeaderMap},
     web::{Bytes, BytesMut},
 };
 use derive_more::{Display, Error};
@@ -45,6 +46,12 @@ pub struct Field {
     // INVARIANT: always non-empty when request content-type is multipart/form-data.
     pub(crate) form_field_name: String,
 
+    /// Length of the prefix of `form_field_name` that has been consumed by flattened forms.
+    pub(crate) form_field_prefix_len: usize,
+
+    /// Whether text readers should trim leading and trailing whitespace before parsing.
+    pub(crate) trim_text: bool,
+
     /// Field's header map.
     headers: HeaderMap,
 
@@ -65,12 +72,19 @@ impl Field {
             content_type,
             content_disposition,
             form_field_name: form_field_name.unwrap_or_default(),
+            form_field_prefix_len: 0,
+            trim_text: false,
             headers,
             inner,
             safety,
         }
     }
 
+    /// Returns the form field name relative to the (possibly flattened) form being collected.
+    pub(crate) fn form_field_key(&self) -> &str {
+        &self.form_field_name[self.form_field_prefix_len..]
+    }
+
     /// Returns a reference to the field's header map.
     pub fn headers(&self) -> &HeaderMap {
         &self.headers
@@ -97,6 +111,10 @@ impl Field {
     ///
     /// As such, it is safe to `.unwrap()` calls `.content_disposition()` if you've verified.
     ///
+    /// For other kinds of multipart requests, the header is returned whatever its disposition type,
+    /// such as `attachment` or `inline`. All parameters are available, including extended (RFC 5987)
+    /// and unrecognized ones.
+    ///
     /// The [`name()`](Self::name) method is also provided as a convenience for obtaining the
     /// aforementioned name parameter.
     ///
@@ -105,6 +123,23 @@ impl Field {
         self.content_disposition.as_ref()
     }
 
+    /// Returns the file name of the field, if set.
+    ///
+    /// Clients send non-ASCII file names using the `filename*` parameter defined in
+    /// [RFC 5987](https://www.rfc-editor.org/rfc/rfc5987), which specifies the charset used to
+    /// encode the name. This method prefers a `filename*` encoded in UTF-8, ISO-8859-1, or
+    /// US-ASCII, decoding it accordingly, and otherwise falls back to the `filename` parameter.
+    ///
+    /// Other Content-Disposition parameters are available through
+    /// [`content_disposition()`](Self::content_disposition).
+    pub fn file_name(&self) -> Option<Cow<'_, str>> {
+        let cd = self.content_disposition()?;
+
+        cd.get_filename_ext()
+            .and_then(decode_ext_value)
+            .or_else(|| cd.get_filename().map(Cow::Borrowed))
+    }
+
     /// Returns the field's name, if set.
     ///
     /// See [`content_disposition()`](Self::content_disposition) regarding guarantees on presence of
@@ -190,6 +225,26 @@ impl Stream for Field {
     }
 }
 
+/// Decodes an RFC 5987 extended parameter value, if its charset is supported.
+fn decode_ext_value(value: &ExtendedValue) -> Option<Cow<'_, str>> {
+    match &value.charset {
+        Charset::Ext(charset) if charset.eq_ignore_ascii_case("UTF-8") => {
+            std::str::from_utf8(&value.value).ok().map(Cow::Borrowed)
+        }
+
+        Charset::Us_Ascii if value.value.is_ascii() => {
+            std::str::from_utf8(&value.value).ok().map(Cow::Borrowed)
+        }
+
+        // ISO-8859-1 bytes map directly to the first 256 Unicode code points
+        Charset::Iso_8859_1 => Some(Cow::Owned(
+            value.value.iter().copied().map(char::from).collect(),
+        )),
+
+        _ => None,
+    }
+}
+
 impl fmt::Debug for Field {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(ct) = &self.content_type {
@@ -381,7 +436,10 @@ impl InnerField {
             };
 
             match ready!(res) {
-                Some(Ok(bytes)) => return Poll::Ready(Some(Ok(bytes))),
+                Some(Ok(bytes)) => {
+                    payload.bytes_read.add_fields(bytes.len());
+                    return Poll::Ready(Some(Ok(bytes)));
+                }
                 Some(Err(err)) => return Poll::Ready(Some(Err(err))),
                 None => self.eof = true,
             }
@@ -498,4 +556,42 @@ mod tests {
             .expect("reading field data should not error");
         assert_eq!(field, "two+two+two");
     }
+
+    #[actix_rt::test]
+    async fn file_name() {
+        let body = Bytes::from(
+            "--abc\r\n\
+             Content-Disposition: form-data; name=\"a\"; filename=\"fallback.txt\"; \
+             filename*=UTF-8''r%C3%A9sum%C3%A9.txt\r\n\r\n\
+             one\r\n\
+             --abc\r\n\
+             Content-Disposition: form-data; name=\"b\"; filename*=ISO-8859-1''caf%E9.txt\r\n\r\n\
+             two\r\n\
+             --abc\r\n\
+             Content-Disposition: form-data; name=\"c\"; filename=\"plain.txt\"; \
+             filename*=Shift_JIS''unsupported.txt; creation-date=\"today\"\r\n\r\n\
+             three\r\n\
+             --abc--\r\n",
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("multipart/form-data; boundary=abc"),
+        );
+
+        let mut multipart = Multipart::new(&headers, stream::iter([Ok(body)]));
+        let mut names = Vec::new();
+
+        while let Some(field) = multipart.next().await {
+            let field = field.unwrap();
+            names.push(field.file_name().unwrap().into_owned());
+
+            if field.name() == Some("c") {
+                let cd = field.content_disposition().unwrap();
+                assert_eq!(cd.get_unknown("creation-date"), Some("today"));
+            }
+        }
+
+        assert_eq!(names, ["résumé.txt", "café.txt", "plain.txt"]);
+    }
 }
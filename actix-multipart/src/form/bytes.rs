@@ -1,6 +1,11 @@
-//! Reads a field into memory.
+//! Reads a field, or a part of it, into memory.
 
-use actix_web::{web::BytesMut, HttpRequest};
+use std::borrow::Cow;
+
+use actix_web::{
+    web::{Buf as _, BytesMut},
+    HttpRequest,
+};
 use futures_core::future::LocalBoxFuture;
 use futures_util::TryStreamExt as _;
 use mime::Mime;
@@ -38,12 +43,162 @@ impl<'t> FieldReader<'t> for Bytes {
             Ok(Bytes {
                 data: buf.freeze(),
                 content_type: field.content_type().map(ToOwned::to_owned),
-                file_name: field
-                    .content_disposition()
-                    .expect("multipart form fields should have a content-disposition header")
-                    .get_filename()
-                    .map(ToOwned::to_owned),
+                file_name: field.file_name().map(Cow::into_owned),
+            })
+        })
+    }
+}
+
+/// Read only the first `N` bytes of the field into memory.
+///
+/// The rest of the field is read and discarded, but still counts towards the total and field
+/// limits. Only the retained bytes count towards the memory limit. Useful for inspecting the header
+/// of a file, e.g., to detect its format.
+#[derive(Debug)]
+pub struct Prefix<const N: usize> {
+    /// The first `N` bytes of the field, or the entire field if it is shorter.
+    pub data: actix_web::web::Bytes,
+
+    /// The total size of the field in bytes.
+    pub size: usize,
+
+    /// The value of the `Content-Type` header.
+    pub content_type: Option<Mime>,
+
+    /// The `filename` value in the `Content-Disposition` header.
+    pub file_name: Option<String>,
+}
+
+impl<'t, const N: usize> FieldReader<'t> for Prefix<N> {
+    type Future = LocalBoxFuture<'t, Result<Self, MultipartError>>;
+
+    fn read_field(_: &'t HttpRequest, mut field: Field, limits: &'t mut Limits) -> Self::Future {
+        Box::pin(async move {
+            let mut buf = BytesMut::new();
+            let mut size = 0;
+
+            while let Some(chunk) = field.try_next().await? {
+                let retained = chunk.len().min(N - buf.len());
+                consume_limits(limits, chunk.len(), retained)?;
+
+                buf.extend_from_slice(&chunk[..retained]);
+                size += chunk.len();
+            }
+
+            Ok(Prefix {
+                data: buf.freeze(),
+                size,
+                content_type: field.content_type().map(ToOwned::to_owned),
+                file_name: field.file_name().map(Cow::into_owned),
+            })
+        })
+    }
+}
+
+/// Read only the last `N` bytes of the field into memory.
+///
+/// The rest of the field is read and discarded, but still counts towards the total and field
+/// limits. Only the retained bytes count towards the memory limit.
+#[derive(Debug)]
+pub struct Tail<const N: usize> {
+    /// The last `N` bytes of the field, or the entire field if it is shorter.
+    pub data: actix_web::web::Bytes,
+
+    /// The total size of the field in bytes.
+    pub size: usize,
+
+    /// The value of the `Content-Type` header.
+    pub content_type: Option<Mime>,
+
+    /// The `filename` value in the `Content-Disposition` header.
+    pub file_name: Option<String>,
+}
+
+impl<'t, const N: usize> FieldReader<'t> for Tail<N> {
+    type Future = LocalBoxFuture<'t, Result<Self, MultipartError>>;
+
+    fn read_field(_: &'t HttpRequest, mut field: Field, limits: &'t mut Limits) -> Self::Future {
+        Box::pin(async move {
+            let mut buf = BytesMut::new();
+            let mut size = 0;
+
+            while let Some(chunk) = field.try_next().await? {
+                let retained = (buf.len() + chunk.len()).min(N) - buf.len();
+                consume_limits(limits, chunk.len(), retained)?;
+
+                buf.extend_from_slice(&chunk[chunk.len().saturating_sub(N)..]);
+                if buf.len() > N {
+                    buf.advance(buf.len() - N);
+                }
+
+                size += chunk.len();
+            }
+
+            Ok(Tail {
+                data: buf.freeze(),
+                size,
+                content_type: field.content_type().map(ToOwned::to_owned),
+                file_name: field.file_name().map(Cow::into_owned),
             })
         })
     }
 }
+
+/// Consumes limits for a chunk of `len` bytes, of which `retained` bytes are newly kept in memory.
+fn consume_limits(limits: &mut Limits, len: usize, retained: usize) -> Result<(), MultipartError> {
+    if retained > 0 {
+        limits.try_consume_limits(retained, true)?;
+    }
+
+    if len > retained {
+        limits.try_consume_limits(len - retained, false)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test::TestRequest, FromRequest as _};
+
+    use super::{Prefix, Tail};
+    use crate::{
+        form::MultipartForm,
+        test::{MultipartTestBuilder, TestRequestExt as _},
+    };
+
+    #[derive(MultipartForm)]
+    struct WindowForm {
+        prefix: Prefix<4>,
+        tail: Tail<4>,
+        short: Prefix<4>,
+    }
+
+    #[actix_web::test]
+    async fn prefix_and_tail() {
+        let (req, mut payload) = TestRequest::post()
+            .set_multipart(
+                MultipartTestBuilder::new()
+                    .file("prefix", "a.png", mime::IMAGE_PNG, "0123456789")
+                    .text("tail", "0123456789")
+                    .text("short", "ab"),
+            )
+            .to_http_parts();
+
+        let form = MultipartForm::<WindowForm>::from_request(&req, &mut payload)
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(form.prefix.data, "0123");
+        assert_eq!(form.prefix.size, 10);
+        assert_eq!(form.prefix.file_name.as_deref(), Some("a.png"));
+        assert_eq!(form.prefix.content_type, Some(mime::IMAGE_PNG));
+
+        assert_eq!(form.tail.data, "6789");
+        assert_eq!(form.tail.size, 10);
+
+        assert_eq!(form.short.data, "ab");
+        assert_eq!(form.short.size, 2);
+    }
+}
@@ -2,14 +2,19 @@
 
 use std::sync::Arc;
 
-use actix_web::{http::StatusCode, web, Error, HttpRequest, ResponseError};
+use actix_web::{
+    http::StatusCode,
+    web::{self, BytesMut},
+    Error, HttpRequest, ResponseError,
+};
 use derive_more::{Deref, DerefMut, Display, Error};
 use futures_core::future::LocalBoxFuture;
+use futures_util::TryStreamExt as _;
 use serde::de::DeserializeOwned;
 
 use super::FieldErrorHandler;
 use crate::{
-    form::{bytes::Bytes, FieldReader, Limits},
+    form::{FieldReader, Limits},
     Field, MultipartError,
 };
 
@@ -29,15 +34,15 @@ where
 {
     type Future = LocalBoxFuture<'t, Result<Self, MultipartError>>;
 
-    fn read_field(req: &'t HttpRequest, field: Field, limits: &'t mut Limits) -> Self::Future {
+    fn read_field(req: &'t HttpRequest, mut field: Field, limits: &'t mut Limits) -> Self::Future {
         Box::pin(async move {
             let config = JsonConfig::from_req(req);
 
             if config.validate_content_type {
-                let valid = if let Some(mime) = field.content_type() {
-                    mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON)
-                } else {
-                    false
+                // parts without a content type are assumed to hold JSON
+                let valid = match field.content_type() {
+                    Some(mime) => mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON),
+                    None => true,
                 };
 
                 if !valid {
@@ -48,16 +53,28 @@ where
                 }
             }
 
-            let form_field_name = field.form_field_name.clone();
+            let mut buf = BytesMut::new();
+
+            while let Some(chunk) = field.try_next().await? {
+                if let Some(limit) = config.limit {
+                    if buf.len() + chunk.len() > limit {
+                        return Err(MultipartError::Field {
+                            name: field.form_field_name,
+                            source: config.map_error(req, JsonFieldError::Overflow { limit }),
+                        });
+                    }
+                }
 
-            let bytes = Bytes::read_field(req, field, limits).await?;
+                limits.try_consume_limits(chunk.len(), true)?;
+                buf.extend(chunk);
+            }
 
-            Ok(Json(serde_json::from_slice(bytes.data.as_ref()).map_err(
-                |err| MultipartError::Field {
-                    name: form_field_name,
+            Ok(Json(serde_json::from_slice(&buf).map_err(|err| {
+                MultipartError::Field {
+                    name: field.form_field_name,
                     source: config.map_error(req, JsonFieldError::Deserialize(err)),
-                },
-            )?))
+                }
+            })?))
         })
     }
 }
@@ -69,14 +86,21 @@ pub enum JsonFieldError {
     #[display("Json deserialize error: {}", _0)]
     Deserialize(serde_json::Error),
 
-    /// Content type error.
-    #[display("Content type error")]
+    /// Field has a content type other than JSON.
+    #[display("Content type error: expected application/json")]
     ContentType,
+
+    /// Field is larger than the limit set in [`JsonConfig`].
+    #[display("Json field is larger than {limit} bytes")]
+    Overflow { limit: usize },
 }
 
 impl ResponseError for JsonFieldError {
     fn status_code(&self) -> StatusCode {
-        StatusCode::BAD_REQUEST
+        match self {
+            JsonFieldError::Overflow { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            _ => StatusCode::BAD_REQUEST,
+        }
     }
 }
 
@@ -85,11 +109,13 @@ impl ResponseError for JsonFieldError {
 pub struct JsonConfig {
     err_handler: FieldErrorHandler<JsonFieldError>,
     validate_content_type: bool,
+    limit: Option<usize>,
 }
 
 const DEFAULT_CONFIG: JsonConfig = JsonConfig {
     err_handler: None,
     validate_content_type: true,
+    limit: None,
 };
 
 impl JsonConfig {
@@ -117,11 +143,26 @@ impl JsonConfig {
         }
     }
 
-    /// Sets whether or not the field must have a valid `Content-Type` header to be parsed.
+    /// Sets whether or not the field's `Content-Type` header is checked before it is parsed.
+    ///
+    /// When enabled, which is the default, fields with a JSON content type, such as
+    /// `application/json` or `application/problem+json`, and fields without a content type are
+    /// parsed; any other content type fails with [`JsonFieldError::ContentType`]. Disable this to
+    /// accept JSON from clients that send parts with an incorrect content type.
     pub fn validate_content_type(mut self, validate_content_type: bool) -> Self {
         self.validate_content_type = validate_content_type;
         self
     }
+
+    /// Sets maximum accepted size of each JSON field, in bytes. By default there is no limit.
+    ///
+    /// This applies in addition to, and independently of, the form and field level limits. It
+    /// allows JSON fields to be kept small while other fields in the same form accept much larger
+    /// uploads.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
 }
 
 impl Default for JsonConfig {
@@ -132,13 +173,16 @@ impl Default for JsonConfig {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, io::Cursor};
 
+    use actix_multipart_rfc7578::client::multipart;
     use actix_web::{http::StatusCode, web, web::Bytes, App, HttpResponse, Responder};
 
     use crate::form::{
-        json::{Json, JsonConfig},
-        MultipartForm,
+        json::{Json, JsonConfig, JsonFieldError},
+        tempfile::TempFile,
+        tests::send_form,
+        MultipartForm, MultipartFormConfig,
     };
 
     #[derive(MultipartForm)]
@@ -207,5 +251,80 @@ mod tests {
         *req.headers_mut() = headers;
         let res = req.send_body(body).await.unwrap();
         assert_eq!(res.status(), StatusCode::OK);
+
+        // Allow because no content type
+        let (body, headers) = crate::test::create_form_data_payload_and_headers(
+            "json",
+            None,
+            None,
+            Bytes::from_static(TEST_JSON.as_bytes()),
+        );
+        let mut req = srv.post("/");
+        *req.headers_mut() = headers;
+        let res = req.send_body(body).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn test_text_plain_rejected() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_json_route)));
+
+        let (body, headers) = crate::test::create_form_data_payload_and_headers(
+            "json",
+            None,
+            Some(mime::TEXT_PLAIN),
+            Bytes::from_static(TEST_JSON.as_bytes()),
+        );
+        let mut req = srv.post("/");
+        *req.headers_mut() = headers;
+        let mut res = req.send_body(body).await.unwrap();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let body = res.body().await.unwrap();
+        assert_eq!(body, "An error occurred processing field: json");
+
+        assert_eq!(
+            JsonFieldError::ContentType.to_string(),
+            "Content type error: expected application/json",
+        );
+    }
+
+    #[derive(MultipartForm)]
+    struct JsonWithFileForm {
+        json: Json<HashMap<String, String>>,
+
+        #[multipart(limit = "100MB")]
+        file: TempFile,
+    }
+
+    async fn test_json_with_file_route(form: MultipartForm<JsonWithFileForm>) -> impl Responder {
+        assert_eq!(form.json.len(), 2);
+        assert_eq!(form.file.size, 64 * 1024);
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_json_limit() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/", web::post().to(test_json_with_file_route))
+                .app_data(JsonConfig::default().limit(1024))
+                .app_data(MultipartFormConfig::default().total_limit(usize::MAX))
+        });
+
+        // Allow because JSON is within its limit and file is within the field limit
+        let mut form = multipart::Form::default();
+        form.add_reader_file_with_mime("json", Cursor::new(TEST_JSON), "", mime::APPLICATION_JSON);
+        form.add_reader_file("file", Cursor::new(vec![0; 64 * 1024]), "file.bin");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Deny because JSON is larger than its limit
+        let large_json = format!(r#"{{"key1": "{}", "key2": "value2"}}"#, "a".repeat(2048));
+        let mut form = multipart::Form::default();
+        form.add_reader_file_with_mime("json", Cursor::new(large_json), "", mime::APPLICATION_JSON);
+        form.add_reader_file("file", Cursor::new(vec![0; 64 * 1024]), "file.bin");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
     }
 }
@@ -1,4 +1,5 @@
-//! Extract and process typed data from fields of a `multipart/form-data` request.
+//! Extract and process typed data from fields of a `multipart/form-data` or `multipart/mixed`
+//! request.
 
 use std::{
     any::Any,
@@ -7,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use actix_web::{dev, error::PayloadError, web, Error, FromRequest, HttpRequest};
+use actix_web::{dev, error::PayloadError, web, Error, FromRequest, HttpMessage as _, HttpRequest};
 use derive_more::{Deref, DerefMut};
 use futures_core::future::LocalBoxFuture;
 use futures_util::{TryFutureExt as _, TryStreamExt as _};
@@ -16,6 +17,7 @@ use crate::{Field, Multipart, MultipartError};
 
 pub mod bytes;
 pub mod json;
+pub mod sink;
 #[cfg(feature = "tempfile")]
 pub mod tempfile;
 pub mod text;
@@ -80,7 +82,7 @@ where
         state: &'t mut State,
         duplicate_field: DuplicateField,
     ) -> Self::Future {
-        if state.contains_key(&field.form_field_name) {
+        if state.contains_key(field.form_field_key()) {
             match duplicate_field {
                 DuplicateField::Ignore => return Box::pin(ready(Ok(()))),
 
@@ -95,7 +97,7 @@ where
         }
 
         Box::pin(async move {
-            let field_name = field.form_field_name.clone();
+            let field_name = field.form_field_key().to_owned();
             let t = T::read_field(req, field, limits).await?;
             state.insert(field_name, Box::new(t));
             Ok(())
@@ -124,7 +126,7 @@ where
             // Note: Vec GroupReader always allows duplicates
 
             let vec = state
-                .entry(field.form_field_name.clone())
+                .entry(field.form_field_key().to_owned())
                 .or_insert_with(|| Box::<Vec<T>>::default())
                 .downcast_mut::<Vec<T>>()
                 .unwrap();
@@ -157,7 +159,7 @@ where
         state: &'t mut State,
         duplicate_field: DuplicateField,
     ) -> Self::Future {
-        if state.contains_key(&field.form_field_name) {
+        if state.contains_key(field.form_field_key()) {
             match duplicate_field {
                 DuplicateField::Ignore => return Box::pin(ready(Ok(()))),
 
@@ -172,7 +174,7 @@ where
         }
 
         Box::pin(async move {
-            let field_name = field.form_field_name.clone();
+            let field_name = field.form_field_key().to_owned();
             let t = T::read_field(req, field, limits).await?;
             state.insert(field_name, Box::new(t));
             Ok(())
@@ -195,6 +197,16 @@ pub trait MultipartCollect: Sized {
     /// across all fields sharing the same name.
     fn limit(field_name: &str) -> Option<usize>;
 
+    /// An optional limit in bytes to be applied to each field with the given name, independently
+    /// of other fields sharing the same name.
+    ///
+    /// When both this and [`limit`](Self::limit) are set, a field must fit within both. Defaults to
+    /// no limit.
+    fn limit_each(field_name: &str) -> Option<usize> {
+        let _ = field_name;
+        None
+    }
+
     /// The extractor will call this function for each incoming field, the state can be updated
     /// with the processed field data.
     fn handle_field<'t>(
@@ -221,11 +233,136 @@ pub enum DuplicateField {
     Replace,
 }
 
+/// Checks that the number of values collected for a list field is within `min` and `max`.
+#[doc(hidden)]
+pub fn check_field_count<T>(
+    name: &str,
+    values: Vec<T>,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Result<Vec<T>, MultipartError> {
+    let len = values.len();
+
+    if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+        return Err(MultipartError::InvalidFieldCount {
+            name: name.to_owned(),
+            len,
+            min,
+            max,
+        });
+    }
+
+    Ok(values)
+}
+
+/// Replaces a missing field error for the field `name` with the value returned by `default`.
+#[doc(hidden)]
+pub fn default_if_missing<T>(
+    name: &str,
+    value: Result<T, MultipartError>,
+    default: impl FnOnce() -> T,
+) -> Result<T, MultipartError> {
+    match value {
+        Err(MultipartError::MissingField(missing)) if missing == name => Ok(default()),
+        value => value,
+    }
+}
+
+/// Checks that the field's content type matches one of the `allowed` media types.
+///
+/// Allowed media types may use a `*` wildcard for the subtype, or for both the type and subtype.
+/// Fields without a `Content-Type` header are treated as "text/plain".
+#[doc(hidden)]
+pub fn check_content_type(field: &Field, allowed: &[&str]) -> Result<(), MultipartError> {
+    let content_type = field.content_type().unwrap_or(&mime::TEXT_PLAIN);
+
+    let matches = allowed.iter().any(|allowed| match allowed.split_once('/') {
+        Some(("*", "*")) => true,
+        Some((ty, "*")) => content_type.type_().as_str().eq_ignore_ascii_case(ty),
+        Some(_) => content_type.essence_str().eq_ignore_ascii_case(allowed),
+        None => false,
+    });
+
+    if matches {
+        Ok(())
+    } else {
+        Err(MultipartError::UnsupportedContentType(
+            field.form_field_name.clone(),
+        ))
+    }
+}
+
+/// Returns the name of the field relative to the form being collected.
+///
+/// For fields of a flattened form, this excludes the prefix used to route them to that form.
+#[doc(hidden)]
+pub fn field_key(field: &Field) -> &str {
+    field.form_field_key()
+}
+
+/// Returns the full name of the field, or its position for `multipart/mixed` forms.
+#[doc(hidden)]
+pub fn field_name(field: &Field) -> &str {
+    &field.form_field_name
+}
+
+/// Routes a field named `<prefix>.<name>` to the flattened form `T`, which sees it as `<name>`.
+///
+/// Fields of a flattened form are collected into their own state, stored under `prefix`.
+#[doc(hidden)]
+pub fn handle_flattened_field<'t, T: MultipartCollect>(
+    req: &'t HttpRequest,
+    mut field: Field,
+    limits: &'t mut Limits,
+    state: &'t mut State,
+    prefix: &str,
+) -> LocalBoxFuture<'t, Result<(), MultipartError>> {
+    field.form_field_prefix_len += prefix.len() + 1;
+
+    let state = state
+        .entry(prefix.to_owned())
+        .or_insert_with(|| Box::<State>::default())
+        .downcast_mut::<State>()
+        .unwrap();
+
+    T::handle_field(req, field, limits, state)
+}
+
+/// Constructs the flattened form `T` from the fields stored under `prefix`.
+#[doc(hidden)]
+pub fn flattened_from_state<T: MultipartCollect>(
+    prefix: &str,
+    state: &mut State,
+) -> Result<T, MultipartError> {
+    let state = state
+        .remove(prefix)
+        .map(|m| *m.downcast::<State>().unwrap())
+        .unwrap_or_default();
+
+    T::from_state(state).map_err(|err| match err {
+        MultipartError::MissingField(name) => {
+            MultipartError::MissingField(format!("{prefix}.{name}"))
+        }
+        err => err,
+    })
+}
+
 /// Used to keep track of the remaining limits for the form and current field.
+///
+/// Besides the remaining limits, this tracks the progress of the form being read, so it can only be
+/// constructed with [`Limits::new()`].
+#[non_exhaustive]
 pub struct Limits {
     pub total_limit_remaining: usize,
     pub memory_limit_remaining: usize,
     pub field_limit_remaining: Option<usize>,
+    total_limit: usize,
+    memory_limit: usize,
+    field_name: String,
+    field_bytes_read: usize,
+    total_bytes_read: usize,
+    memory_exempt: bool,
+    progress_handler: ProgressHandler,
 }
 
 impl Limits {
@@ -234,16 +371,43 @@ impl Limits {
             total_limit_remaining: total_limit,
             memory_limit_remaining: memory_limit,
             field_limit_remaining: None,
+            total_limit,
+            memory_limit,
+            field_name: String::new(),
+            field_bytes_read: 0,
+            total_bytes_read: 0,
+            memory_exempt: false,
+            progress_handler: None,
         }
     }
 
+    /// Resets per-field progress tracking for the next field to be read.
+    fn start_field(&mut self, field_name: &str) {
+        self.field_name.clear();
+        self.field_name.push_str(field_name);
+        self.field_bytes_read = 0;
+        self.memory_exempt = false;
+    }
+
+    /// Exempts the field currently being read from the memory limit.
+    ///
+    /// Used by the `MultipartForm` derive macro for fields with a `memory_exempt` attribute.
+    #[doc(hidden)]
+    pub fn exempt_field_from_memory_limit(&mut self) {
+        self.memory_exempt = true;
+    }
+
     /// This function should be called within a [`FieldReader`] when reading each chunk of a field
     /// to ensure that the form limits are not exceeded.
     ///
+    /// When a limit is exceeded while a form is being extracted, the overflow error is wrapped in a
+    /// [`MultipartError::Field`] error naming the field being read.
+    ///
     /// # Arguments
     ///
     /// * `bytes` - The number of bytes being read from this chunk
-    /// * `in_memory` - Whether to consume from the memory limits
+    /// * `in_memory` - Whether to consume from the memory limits; ignored for fields that are exempt
+    ///   from the memory limit
     pub fn try_consume_limits(
         &mut self,
         bytes: usize,
@@ -252,25 +416,76 @@ impl Limits {
         self.total_limit_remaining = self
             .total_limit_remaining
             .checked_sub(bytes)
-            .ok_or(MultipartError::Payload(PayloadError::Overflow))?;
+            .ok_or_else(|| self.overflow_error(self.total_limit, self.total_bytes_read + bytes))?;
 
-        if in_memory {
+        if in_memory && !self.memory_exempt {
             self.memory_limit_remaining = self
                 .memory_limit_remaining
                 .checked_sub(bytes)
-                .ok_or(MultipartError::Payload(PayloadError::Overflow))?;
+                .ok_or_else(|| {
+                    let memory_bytes_read = self
+                        .memory_limit
+                        .saturating_sub(self.memory_limit_remaining);
+                    self.overflow_error(self.memory_limit, memory_bytes_read + bytes)
+                })?;
         }
 
         if let Some(field_limit) = self.field_limit_remaining {
-            self.field_limit_remaining = Some(
-                field_limit
-                    .checked_sub(bytes)
-                    .ok_or(MultipartError::Payload(PayloadError::Overflow))?,
+            self.field_limit_remaining = Some(field_limit.checked_sub(bytes).ok_or_else(|| {
+                self.overflow_error(
+                    self.field_bytes_read + field_limit,
+                    self.field_bytes_read + bytes,
+                )
+            })?);
+        }
+
+        self.field_bytes_read += bytes;
+        self.total_bytes_read += bytes;
+
+        if let Some(ref progress_handler) = self.progress_handler {
+            (progress_handler)(
+                &self.field_name,
+                self.field_bytes_read,
+                self.total_bytes_read,
             );
         }
 
         Ok(())
     }
+
+    /// Returns the number of bytes that can still be consumed before a limit is exceeded.
+    ///
+    /// This is the smallest of the remaining total, memory, and field limits; the memory limit is
+    /// only considered when `in_memory` is true, matching [`try_consume_limits`]. Custom
+    /// [`FieldReader`]s can use this to decide how to read the rest of a field, or to fail early.
+    ///
+    /// [`try_consume_limits`]: Self::try_consume_limits
+    pub fn remaining(&self, in_memory: bool) -> usize {
+        let mut remaining = self.total_limit_remaining;
+
+        if in_memory && !self.memory_exempt {
+            remaining = remaining.min(self.memory_limit_remaining);
+        }
+
+        if let Some(field_limit) = self.field_limit_remaining {
+            remaining = remaining.min(field_limit);
+        }
+
+        remaining
+    }
+
+    fn overflow_error(&self, limit: usize, read: usize) -> MultipartError {
+        let err = MultipartError::Payload(PayloadError::OverflowWithSize { limit, read });
+
+        if self.field_name.is_empty() {
+            return err;
+        }
+
+        MultipartError::Field {
+            name: self.field_name.clone(),
+            source: err.into(),
+        }
+    }
 }
 
 /// Typed `multipart/form-data` extractor.
@@ -280,9 +495,46 @@ impl Limits {
 /// for your struct.
 ///
 /// Note that this extractor rejects requests with any other Content-Type such as `multipart/mixed`,
-/// `multipart/related`, or non-multipart media types.
+/// `multipart/related`, or non-multipart media types. Use [`MultipartMixed`] for
+/// `multipart/mixed` requests.
+///
+/// A request that is not `multipart/form-data` is rejected with
+/// [`MultipartError::ContentTypeIncompatible`] (or [`MultipartError::ContentTypeMissing`] if it
+/// has no Content-Type at all) before any of the body is read, whereas a malformed
+/// `multipart/form-data` request is rejected with other errors, such as
+/// [`MultipartError::BoundaryMissing`].
 ///
 /// Add a [`MultipartFormConfig`] to your app data to configure extraction.
+///
+/// After a form is extracted successfully, the number of bytes read for the form is stored in the
+/// request extensions as a [`FormBytesRead`].
+///
+/// # Accepting JSON Too
+/// To also accept a JSON body at the same endpoint, use an [`Either`] extractor with a JSON
+/// extractor as the fallback. Note that `Either` buffers the whole payload in memory, subject to
+/// the [`PayloadConfig`] limit, before trying each extractor.
+///
+/// ```
+/// use actix_multipart::form::{text::Text, MultipartForm};
+/// use actix_web::{web, Either, Responder};
+///
+/// #[derive(MultipartForm)]
+/// struct Upload {
+///     name: Text<String>,
+/// }
+///
+/// async fn upload(
+///     body: Either<MultipartForm<Upload>, web::Json<serde_json::Value>>,
+/// ) -> impl Responder {
+///     match body {
+///         Either::Left(form) => format!("Hello {}!", form.name.as_str()),
+///         Either::Right(json) => format!("Hello {}!", json["name"]),
+///     }
+/// }
+/// ```
+///
+/// [`Either`]: actix_web::Either
+/// [`PayloadConfig`]: actix_web::web::PayloadConfig
 #[derive(Deref, DerefMut)]
 pub struct MultipartForm<T: MultipartCollect>(pub T);
 
@@ -302,67 +554,244 @@ where
 
     #[inline]
     fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
-        let mut multipart = Multipart::from_req(req, payload);
+        Box::pin(collect_form(req, payload, FormKind::FormData).map_ok(MultipartForm))
+    }
+}
 
-        let content_type = match multipart.content_type_or_bail() {
-            Ok(content_type) => content_type,
-            Err(err) => return Box::pin(ready(Err(err.into()))),
-        };
+/// Typed `multipart/mixed` extractor.
+///
+/// Parts of a `multipart/mixed` payload, such as email-style messages or API batch requests, are
+/// not named. This extractor collects them into `T` by position instead: each part is keyed by its
+/// zero-based index, so fields of a [`macro@MultipartForm`] struct are mapped to parts using
+/// `#[multipart(rename = "0")]`, `#[multipart(rename = "1")]`, and so on.
+///
+/// Requests with any other Content-Type, including `multipart/form-data`, are rejected with
+/// [`MultipartError::ContentTypeIncompatible`]. Extraction is otherwise configured and behaves
+/// like [`struct@MultipartForm`].
+///
+/// # Examples
+/// ```
+/// use actix_multipart::form::{bytes::Bytes, json::Json, MultipartForm, MultipartMixed};
+/// use actix_web::Responder;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Metadata {
+///     title: String,
+/// }
+///
+/// #[derive(MultipartForm)]
+/// struct Batch {
+///     #[multipart(rename = "0")]
+///     metadata: Json<Metadata>,
+///
+///     #[multipart(rename = "1")]
+///     attachment: Bytes,
+/// }
+///
+/// async fn batch(batch: MultipartMixed<Batch>) -> impl Responder {
+///     format!(
+///         "{}: {} bytes",
+///         batch.metadata.title,
+///         batch.attachment.data.len(),
+///     )
+/// }
+/// ```
+#[derive(Deref, DerefMut)]
+pub struct MultipartMixed<T: MultipartCollect>(pub T);
 
-        if content_type.subtype() != mime::FORM_DATA {
-            // this extractor only supports multipart/form-data
-            return Box::pin(ready(Err(MultipartError::ContentTypeIncompatible.into())));
-        };
+impl<T: MultipartCollect> MultipartMixed<T> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
 
-        let config = MultipartFormConfig::from_req(req);
-        let mut limits = Limits::new(config.total_limit, config.memory_limit);
+impl<T> FromRequest for MultipartMixed<T>
+where
+    T: MultipartCollect + 'static,
+{
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
 
-        let req = req.clone();
-        let req2 = req.clone();
-        let err_handler = config.err_handler.clone();
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+        Box::pin(collect_form(req, payload, FormKind::Mixed).map_ok(MultipartMixed))
+    }
+}
 
-        Box::pin(
-            async move {
-                let mut state = State::default();
+/// Multipart media types supported by the typed extractors.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FormKind {
+    /// `multipart/form-data`; parts are keyed by name.
+    FormData,
 
-                // ensure limits are shared for all fields with this name
-                let mut field_limits = HashMap::<String, Option<usize>>::new();
+    /// `multipart/mixed`; parts are keyed by position.
+    Mixed,
+}
 
-                while let Some(field) = multipart.try_next().await? {
-                    debug_assert!(
-                        !field.form_field_name.is_empty(),
-                        "multipart form fields should have names",
-                    );
+impl FormKind {
+    fn subtype(self) -> &'static str {
+        match self {
+            FormKind::FormData => "form-data",
+            FormKind::Mixed => "mixed",
+        }
+    }
+}
 
-                    // Retrieve the limit for this field
-                    let entry = field_limits
-                        .entry(field.form_field_name.clone())
-                        .or_insert_with(|| T::limit(&field.form_field_name));
+/// Collects the fields of a multipart request of the given kind into `T`.
+fn collect_form<T>(
+    req: &HttpRequest,
+    payload: &mut dev::Payload,
+    kind: FormKind,
+) -> LocalBoxFuture<'static, Result<T, Error>>
+where
+    T: MultipartCollect + 'static,
+{
+    let mut multipart = Multipart::from_req(req, payload);
 
-                    limits.field_limit_remaining.clone_from(entry);
+    let content_type = match multipart.content_type_or_bail() {
+        Ok(content_type) => content_type,
+        Err(err) => return Box::pin(ready(Err(err.into()))),
+    };
 
-                    T::handle_field(&req, field, &mut limits, &mut state).await?;
+    if content_type.subtype() != kind.subtype() {
+        return Box::pin(ready(Err(MultipartError::ContentTypeIncompatible.into())));
+    };
 
-                    // Update the stored limit
-                    *entry = limits.field_limit_remaining;
+    let config = MultipartFormConfig::from_req(req);
+    let mut limits = Limits::new(config.total_limit, config.memory_limit);
+    limits.progress_handler.clone_from(&config.progress_handler);
+
+    let max_parts = config.max_parts;
+
+    let req = req.clone();
+    let req2 = req.clone();
+    let err_handler = config.err_handler.clone();
+    let field_err_handlers = config.field_err_handlers.clone();
+
+    Box::pin(
+        async move {
+            let mut state = State::default();
+
+            // ensure limits are shared for all fields with this name
+            let mut field_limits = HashMap::<String, Option<usize>>::new();
+
+            let mut parts = 0;
+
+            while let Some(mut field) = multipart.try_next().await? {
+                parts += 1;
+
+                if max_parts.is_some_and(|max_parts| parts > max_parts) {
+                    return Err(MultipartError::TooManyFields.into());
                 }
 
-                let inner = T::from_state(state)?;
-                Ok(MultipartForm(inner))
+                if kind == FormKind::Mixed {
+                    // parts of multipart/mixed payloads are keyed by their position
+                    field.form_field_name = (parts - 1).to_string();
+                }
+
+                debug_assert!(
+                    !field.form_field_name.is_empty(),
+                    "multipart form fields should have names",
+                );
+
+                // Retrieve the limit for this field
+                let entry = field_limits
+                    .entry(field.form_field_name.clone())
+                    .or_insert_with(|| T::limit(&field.form_field_name));
+
+                limits.field_limit_remaining = match (*entry, T::limit_each(&field.form_field_name))
+                {
+                    (Some(shared), Some(each)) => Some(shared.min(each)),
+                    (shared, each) => shared.or(each),
+                };
+                limits.start_field(&field.form_field_name);
+
+                let field_name = field.form_field_name.clone();
+
+                T::handle_field(&req, field, &mut limits, &mut state)
+                    .await
+                    .map_err(|err| FormError::Field(err, field_name))?;
+
+                // Update the stored limit
+                if let Some(remaining) = entry {
+                    *remaining -= limits.field_bytes_read;
+                }
             }
-            .map_err(move |err| {
-                if let Some(handler) = err_handler {
-                    (*handler)(err, &req2)
-                } else {
-                    err.into()
+
+            let inner = T::from_state(state)?;
+
+            req.extensions_mut()
+                .insert(FormBytesRead(limits.total_bytes_read));
+
+            Ok::<_, FormError>(inner)
+        }
+        .map_err(move |err| {
+            let (err, handler) = match err {
+                FormError::Field(err, field_name) => {
+                    let handler = field_err_handlers
+                        .iter()
+                        .flat_map(|handlers| handlers.iter())
+                        .find(|(name, _)| *name == field_name)
+                        .map(|(_, handler)| handler)
+                        .or(err_handler.as_ref());
+
+                    (err, handler)
                 }
-            }),
-        )
+                FormError::Form(err) => (err, err_handler.as_ref()),
+            };
+
+            if let Some(handler) = handler {
+                (*handler)(err, &req2)
+            } else {
+                err.into()
+            }
+        }),
+    )
+}
+
+/// Number of bytes of field data read while extracting a [`struct@MultipartForm`].
+///
+/// Inserted into request extensions by the [`struct@MultipartForm`] extractor.
+///
+/// # Examples
+/// ```
+/// use actix_multipart::form::{text::Text, FormBytesRead, MultipartForm};
+/// use actix_web::{HttpMessage as _, HttpRequest, Responder};
+///
+/// #[derive(MultipartForm)]
+/// struct Form {
+///     name: Text<String>,
+/// }
+///
+/// async fn handler(req: HttpRequest, form: MultipartForm<Form>) -> impl Responder {
+///     let bytes_read = req.extensions().get::<FormBytesRead>().map_or(0, |read| read.0);
+///     format!("read {bytes_read} bytes for {}", form.name.as_str())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormBytesRead(pub usize);
+
+/// Error while extracting a form, tracking whether it occurred while reading a specific field.
+enum FormError {
+    Form(MultipartError),
+    Field(MultipartError, String),
+}
+
+impl From<MultipartError> for FormError {
+    fn from(err: MultipartError) -> Self {
+        Self::Form(err)
     }
 }
 
-type MultipartFormErrorHandler =
-    Option<Arc<dyn Fn(MultipartError, &HttpRequest) -> Error + Send + Sync>>;
+type ErrorHandlerFn = Arc<dyn Fn(MultipartError, &HttpRequest) -> Error + Send + Sync>;
+
+type MultipartFormErrorHandler = Option<ErrorHandlerFn>;
+
+type FieldErrorHandlers = Option<Arc<Vec<(String, ErrorHandlerFn)>>>;
+
+type ProgressHandler = Option<Arc<dyn Fn(&str, usize, usize) + Send + Sync>>;
 
 /// [`struct@MultipartForm`] extractor configuration.
 ///
@@ -371,7 +800,10 @@ type MultipartFormErrorHandler =
 pub struct MultipartFormConfig {
     total_limit: usize,
     memory_limit: usize,
+    max_parts: Option<usize>,
     err_handler: MultipartFormErrorHandler,
+    field_err_handlers: FieldErrorHandlers,
+    progress_handler: ProgressHandler,
 }
 
 impl MultipartFormConfig {
@@ -382,11 +814,21 @@ impl MultipartFormConfig {
     }
 
     /// Sets maximum accepted data that will be read into memory. By default this limit is 2MiB.
+    ///
+    /// Fields with a `#[multipart(memory_exempt)]` attribute do not count towards this limit.
     pub fn memory_limit(mut self, memory_limit: usize) -> Self {
         self.memory_limit = memory_limit;
         self
     }
 
+    /// Sets maximum number of fields accepted in the form. By default there is no limit.
+    ///
+    /// Both known and unknown fields count towards this limit.
+    pub fn max_parts(mut self, max_parts: usize) -> Self {
+        self.max_parts = Some(max_parts);
+        self
+    }
+
     /// Sets custom error handler.
     pub fn error_handler<F>(mut self, f: F) -> Self
     where
@@ -396,6 +838,47 @@ impl MultipartFormConfig {
         self
     }
 
+    /// Sets custom error handler for errors that occur while reading the field named `name`.
+    ///
+    /// Takes precedence over the [`error_handler`](Self::error_handler) for those errors. Errors
+    /// that are not caused by reading a single field, such as a missing field, are still passed to
+    /// the form's error handler.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_multipart::form::MultipartFormConfig;
+    /// use actix_web::error;
+    ///
+    /// MultipartFormConfig::default()
+    ///     .field_error_handler("avatar", |err, _req| error::ErrorUnprocessableEntity(err))
+    ///     .field_error_handler("document", |err, _req| error::ErrorPayloadTooLarge(err));
+    /// ```
+    pub fn field_error_handler<F>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(MultipartError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        let name = name.into();
+
+        let handlers = Arc::make_mut(self.field_err_handlers.get_or_insert_with(Default::default));
+        handlers.retain(|(existing, _)| *existing != name);
+        handlers.push((name, Arc::new(f)));
+
+        self
+    }
+
+    /// Sets a handler that is called as field data is read.
+    ///
+    /// The handler receives the field name, the number of bytes read so far for the current field,
+    /// and the number of bytes read so far for the entire form. It is called once per chunk, after
+    /// the chunk has been checked against the form limits.
+    pub fn progress_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, usize, usize) + Send + Sync + 'static,
+    {
+        self.progress_handler = Some(Arc::new(f));
+        self
+    }
+
     /// Extracts payload config from app data. Check both `T` and `Data<T>`, in that order, and fall
     /// back to the default payload config.
     fn from_req(req: &HttpRequest) -> &Self {
@@ -408,7 +891,10 @@ impl MultipartFormConfig {
 const DEFAULT_CONFIG: MultipartFormConfig = MultipartFormConfig {
     total_limit: 52_428_800, // 50 MiB
     memory_limit: 2_097_152, // 2 MiB
+    max_parts: None,
     err_handler: None,
+    field_err_handlers: None,
+    progress_handler: None,
 };
 
 impl Default for MultipartFormConfig {
@@ -419,20 +905,30 @@ impl Default for MultipartFormConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        convert::Infallible,
+        io::Cursor,
+        sync::{Arc, Mutex},
+    };
+
     use actix_http::encoding::Decoder;
     use actix_multipart_rfc7578::client::multipart;
     use actix_test::TestServer;
     use actix_web::{
-        dev::Payload, http::StatusCode, web, App, HttpRequest, HttpResponse, Resource, Responder,
+        dev::Payload,
+        error::PayloadError,
+        http::{header, StatusCode},
+        web, App, HttpMessage as _, HttpRequest, HttpResponse, Resource, Responder,
     };
     use awc::{Client, ClientResponse};
     use futures_core::future::LocalBoxFuture;
     use futures_util::TryStreamExt as _;
 
-    use super::MultipartForm;
+    use super::{MultipartForm, MultipartMixed};
     use crate::{
         form::{
-            bytes::Bytes, tempfile::TempFile, text::Text, FieldReader, Limits, MultipartFormConfig,
+            bytes::Bytes, tempfile::TempFile, text::Text, FieldReader, FormBytesRead, Limits,
+            MultipartFormConfig,
         },
         Field, MultipartError,
     };
@@ -686,6 +1182,47 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[derive(MultipartForm)]
+    struct TestMemoryExemptLimits {
+        #[multipart(memory_exempt)]
+        exempt: Text<String>,
+        field: Text<String>,
+    }
+
+    async fn test_upload_limits_memory_exempt(
+        form: MultipartForm<TestMemoryExemptLimits>,
+    ) -> impl Responder {
+        assert_eq!(form.exempt.len(), 28);
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_memory_exempt() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/", web::post().to(test_upload_limits_memory_exempt))
+                .app_data(
+                    MultipartFormConfig::default()
+                        .memory_limit(20)
+                        .total_limit(usize::MAX),
+                )
+        });
+
+        // Exempt field is not counted towards the 20 byte memory limit
+        let mut form = multipart::Form::default();
+        form.add_text("exempt", "this string is 28 bytes long");
+        form.add_text("field", "7 bytes");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Other fields are still limited
+        let mut form = multipart::Form::default();
+        form.add_text("exempt", "this string is 28 bytes long");
+        form.add_text("field", "this string is 28 bytes long");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[actix_rt::test]
     async fn test_total_limit() {
         let srv = actix_test::start(|| {
@@ -718,6 +1255,151 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[actix_rt::test]
+    async fn test_limit_error_field_name() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/text", web::post().to(test_upload_limits_memory))
+                .app_data(MultipartFormConfig::default().total_limit(20).error_handler(
+                    |err, _req| {
+                        assert!(
+                            matches!(err, MultipartError::Field { ref name, .. } if name == "field"),
+                            "unexpected error: {err:?}",
+                        );
+                        actix_web::error::ErrorPayloadTooLarge(err)
+                    },
+                ))
+        });
+
+        let mut form = multipart::Form::default();
+        form.add_text("field", "this string is 28 bytes long");
+        let response = send_form(&srv, form, "/text").await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[actix_rt::test]
+    async fn test_max_parts() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/", web::post().to(test_allow_unknown_route))
+                .app_data(MultipartFormConfig::default().max_parts(2))
+        });
+
+        let mut form = multipart::Form::default();
+        form.add_text("field1", "value");
+        form.add_text("field2", "value");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut form = multipart::Form::default();
+        form.add_text("field1", "value");
+        form.add_text("field2", "value");
+        form.add_text("field3", "value");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[derive(MultipartForm)]
+    struct TestFieldErrorHandlers {
+        #[multipart(limit = "4B")]
+        avatar: Option<Bytes>,
+        #[multipart(limit = "4B")]
+        document: Option<Bytes>,
+        #[multipart(limit = "4B")]
+        other: Option<Bytes>,
+    }
+
+    async fn test_field_error_handlers_route(
+        _: MultipartForm<TestFieldErrorHandlers>,
+    ) -> impl Responder {
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_field_error_handlers() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/", web::post().to(test_field_error_handlers_route))
+                .app_data(
+                    MultipartFormConfig::default()
+                        .error_handler(|err, _req| actix_web::error::ErrorConflict(err))
+                        .field_error_handler("avatar", |err, _req| {
+                            actix_web::error::ErrorUnprocessableEntity(err)
+                        })
+                        .field_error_handler("document", |err, _req| {
+                            actix_web::error::ErrorPayloadTooLarge(err)
+                        }),
+                )
+        });
+
+        for (field, status) in [
+            ("avatar", StatusCode::UNPROCESSABLE_ENTITY),
+            ("document", StatusCode::PAYLOAD_TOO_LARGE),
+            ("other", StatusCode::CONFLICT),
+        ] {
+            let mut form = multipart::Form::default();
+            form.add_text(field, "too many bytes");
+            let response = send_form(&srv, form, "/").await;
+            assert_eq!(response.status(), status);
+        }
+
+        let mut form = multipart::Form::default();
+        form.add_text("avatar", "ok");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    async fn test_bytes_read_route(
+        req: HttpRequest,
+        _: MultipartForm<TestFieldLevelLimits>,
+    ) -> impl Responder {
+        assert_eq!(
+            req.extensions().get::<FormBytesRead>(),
+            Some(&FormBytesRead(15)),
+        );
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_bytes_read() {
+        let srv =
+            actix_test::start(|| App::new().route("/", web::post().to(test_bytes_read_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("field", "7 bytes");
+        form.add_text("field", "8 bytes!");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[derive(MultipartForm)]
+    struct TestBytesMetadata {
+        field: Bytes,
+    }
+
+    async fn test_bytes_metadata_route(form: MultipartForm<TestBytesMetadata>) -> impl Responder {
+        assert_eq!(form.field.data, "png data");
+        assert_eq!(form.field.file_name.as_deref(), Some("image.png"));
+        assert_eq!(form.field.content_type, Some(mime::IMAGE_PNG));
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_bytes_metadata() {
+        let srv =
+            actix_test::start(|| App::new().route("/", web::post().to(test_bytes_metadata_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_reader_file_with_mime(
+            "field",
+            Cursor::new("png data"),
+            "image.png",
+            mime::IMAGE_PNG,
+        );
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[derive(MultipartForm)]
     struct TestFieldLevelLimits {
         #[multipart(limit = "30B")]
@@ -770,6 +1452,155 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[derive(MultipartForm)]
+    struct TestFieldLevelLimitEach {
+        #[multipart(limit_each = "30B")]
+        field: Vec<Bytes>,
+
+        #[multipart(limit_each = "30B", limit = "40B")]
+        both: Vec<Bytes>,
+    }
+
+    async fn test_field_level_limit_each_route(
+        form: MultipartForm<TestFieldLevelLimitEach>,
+    ) -> impl Responder {
+        assert!(!form.field.is_empty());
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_field_level_limit_each() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/", web::post().to(test_field_level_limit_each_route))
+                .app_data(
+                    MultipartFormConfig::default()
+                        .memory_limit(usize::MAX)
+                        .total_limit(usize::MAX),
+                )
+        });
+
+        // Each value is within the 30 byte limit, even though their total is not
+        let mut form = multipart::Form::default();
+        form.add_text("field", "this string is 28 bytes long");
+        form.add_text("field", "this string is 28 bytes long");
+        form.add_text("field", "this string is 28 bytes long");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // A single value exceeds the 30 byte limit
+        let mut form = multipart::Form::default();
+        form.add_text("field", "7 bytes");
+        form.add_text("field", "this string is more than 30 bytes long");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Each value is within 30 bytes and their total within 40 bytes
+        let mut form = multipart::Form::default();
+        form.add_text("field", "7 bytes");
+        form.add_text("both", "this string is 28 bytes long");
+        form.add_text("both", "7 bytes");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Each value is within 30 bytes but their total exceeds 40 bytes
+        let mut form = multipart::Form::default();
+        form.add_text("field", "7 bytes");
+        form.add_text("both", "this string is 28 bytes long");
+        form.add_text("both", "this string is 28 bytes long");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // The form total limit still applies across values
+        let srv = actix_test::start(|| {
+            App::new()
+                .route("/", web::post().to(test_field_level_limit_each_route))
+                .app_data(MultipartFormConfig::default().total_limit(50))
+        });
+
+        let mut form = multipart::Form::default();
+        form.add_text("field", "this string is 28 bytes long");
+        form.add_text("field", "this string is 28 bytes long");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[derive(MultipartForm)]
+    struct TestContentTypes {
+        #[multipart(content_type = "image/png", content_type = "text/*")]
+        field: Bytes,
+    }
+
+    async fn test_content_types_route(form: MultipartForm<TestContentTypes>) -> impl Responder {
+        assert!(!form.field.data.is_empty());
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_content_types() {
+        let srv =
+            actix_test::start(|| App::new().route("/", web::post().to(test_content_types_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_reader_file_with_mime("field", Cursor::new("png"), "a.png", mime::IMAGE_PNG);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // wildcard subtype
+        let mut form = multipart::Form::default();
+        form.add_reader_file_with_mime("field", Cursor::new("csv"), "a.csv", mime::TEXT_CSV);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // missing content type is treated as text/plain
+        let mut form = multipart::Form::default();
+        form.add_text("field", "text");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut form = multipart::Form::default();
+        form.add_reader_file_with_mime("field", Cursor::new("jpg"), "a.jpg", mime::IMAGE_JPEG);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[actix_rt::test]
+    async fn test_progress_handler() {
+        let progress = Arc::new(Mutex::new(Vec::new()));
+
+        let srv = actix_test::start({
+            let progress = Arc::clone(&progress);
+
+            move || {
+                let progress = Arc::clone(&progress);
+
+                App::new()
+                    .route("/", web::post().to(test_field_level_limits_route))
+                    .app_data(MultipartFormConfig::default().progress_handler(
+                        move |name, field_read, total_read| {
+                            progress.lock().unwrap().push((
+                                name.to_owned(),
+                                field_read,
+                                total_read,
+                            ));
+                        },
+                    ))
+            }
+        });
+
+        let mut form = multipart::Form::default();
+        form.add_text("field", "7 bytes");
+        form.add_text("field", "8 bytes!");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let progress = progress.lock().unwrap();
+        assert_eq!(
+            *progress,
+            [("field".to_owned(), 7, 7), ("field".to_owned(), 8, 15)],
+        );
+    }
+
     #[actix_rt::test]
     async fn non_multipart_form_data() {
         #[derive(MultipartForm)]
@@ -805,6 +1636,54 @@ mod tests {
         assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
     }
 
+    #[derive(MultipartForm)]
+    struct TestMixed {
+        #[multipart(rename = "0")]
+        first: Text<String>,
+
+        #[multipart(rename = "1")]
+        second: Bytes,
+    }
+
+    async fn test_mixed_route(form: MultipartMixed<TestMixed>) -> impl Responder {
+        assert_eq!(&*form.first, "first part");
+        assert_eq!(form.second.data, "second part");
+        assert_eq!(
+            form.second.content_type,
+            Some(mime::APPLICATION_OCTET_STREAM)
+        );
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_multipart_mixed() {
+        let srv =
+            actix_test::start(|| App::new().route("/mixed", web::post().to(test_mixed_route)));
+
+        let body = "\
+            --abc\r\n\
+            Content-Type: text/plain\r\n\r\n\
+            first part\r\n\
+            --abc\r\n\
+            Content-Type: application/octet-stream\r\n\r\n\
+            second part\r\n\
+            --abc--\r\n";
+
+        let res = Client::default()
+            .post(srv.url("/mixed"))
+            .content_type("multipart/mixed; boundary=abc")
+            .send_body(body)
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // multipart/form-data requests are not accepted by the mixed extractor
+        let mut form = multipart::Form::default();
+        form.add_text("0", "first part");
+        let res = send_form(&srv, form, "/mixed").await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
     #[should_panic(expected = "called `Result::unwrap()` on an `Err` value: Connect(Disconnected)")]
     #[actix_web::test]
     async fn field_try_next_panic() {
@@ -849,4 +1728,227 @@ mod tests {
         // panics with Err(Connect(Disconnected)) due to form NullSink panic
         let _res = send_form(&srv, form, "/").await;
     }
+
+    #[derive(MultipartForm)]
+    struct AddressForm {
+        city: Text<String>,
+        zip: Option<Text<String>>,
+    }
+
+    #[derive(MultipartForm)]
+    struct FlattenForm {
+        name: Text<String>,
+
+        #[multipart(flatten)]
+        address: AddressForm,
+
+        #[multipart(flatten, rename = "billing")]
+        billing_address: AddressForm,
+    }
+
+    async fn test_flatten_route(form: MultipartForm<FlattenForm>) -> impl Responder {
+        let form = form.into_inner();
+        assert_eq!(&*form.name, "Alice");
+        assert_eq!(&*form.address.city, "London");
+        assert_eq!(form.address.zip.as_deref().map(String::as_str), Some("N1"));
+        assert_eq!(&*form.billing_address.city, "Paris");
+        assert!(form.billing_address.zip.is_none());
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_flatten() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_flatten_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("name", "Alice");
+        form.add_text("address.city", "London");
+        form.add_text("address.zip", "N1");
+        form.add_text("billing.city", "Paris");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // missing nested fields are reported with their full name
+        let mut form = multipart::Form::default();
+        form.add_text("name", "Alice");
+        form.add_text("address.city", "London");
+        let mut response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = response.body().await.unwrap();
+        assert_eq!(body, "Required field is missing: billing.city");
+    }
+
+    #[derive(MultipartForm)]
+    struct NameForm {
+        name: Text<String>,
+    }
+
+    async fn test_json_fallback_route(
+        form: actix_web::Either<MultipartForm<NameForm>, web::Json<serde_json::Value>>,
+    ) -> impl Responder {
+        match form {
+            actix_web::Either::Left(form) => format!("form: {}", form.name.as_str()),
+            actix_web::Either::Right(json) => format!("json: {}", json["name"]),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_json_fallback() {
+        let srv =
+            actix_test::start(|| App::new().route("/", web::post().to(test_json_fallback_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("name", "Alice");
+        let mut response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body().await.unwrap(), "form: Alice");
+
+        let mut response = srv
+            .post("/")
+            .send_json(&serde_json::json!({ "name": "Bob" }))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body().await.unwrap(), "json: \"Bob\"");
+
+        // neither multipart nor JSON
+        let response = srv
+            .post("/")
+            .insert_header((header::CONTENT_TYPE, "text/plain"))
+            .send_body("Carol")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[derive(MultipartForm)]
+    struct ListLengthForm {
+        #[multipart(min_len = 1, max_len = 2)]
+        tags: Vec<Text<String>>,
+    }
+
+    async fn test_list_length_route(form: MultipartForm<ListLengthForm>) -> impl Responder {
+        HttpResponse::Ok().body(form.tags.len().to_string())
+    }
+
+    #[actix_rt::test]
+    async fn test_list_length() {
+        let srv =
+            actix_test::start(|| App::new().route("/", web::post().to(test_list_length_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("tags", "one");
+        form.add_text("tags", "two");
+        let mut response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body().await.unwrap(), "2");
+
+        let mut form = multipart::Form::default();
+        form.add_text("other", "value");
+        let mut response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.body().await.unwrap(),
+            "Invalid number of values for field tags: got 0, expected between 1 and 2"
+        );
+
+        let mut form = multipart::Form::default();
+        form.add_text("tags", "one");
+        form.add_text("tags", "two");
+        form.add_text("tags", "three");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    fn parse_words(text: &str) -> Result<Vec<String>, Infallible> {
+        Ok(text.split(' ').map(ToOwned::to_owned).collect())
+    }
+
+    #[derive(MultipartForm)]
+    struct DefaultForm {
+        #[multipart(default)]
+        title: Text<String>,
+
+        #[multipart(default = "Text(10)")]
+        page_size: Text<u32>,
+
+        #[multipart(with = "parse_words", default = "vec![\"none\".to_owned()]")]
+        words: Vec<String>,
+    }
+
+    async fn test_default_route(form: MultipartForm<DefaultForm>) -> impl Responder {
+        HttpResponse::Ok().body(format!(
+            "{:?} {} {:?}",
+            form.title.as_str(),
+            *form.page_size,
+            form.words,
+        ))
+    }
+
+    #[actix_rt::test]
+    async fn test_default_values() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_default_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("other", "value");
+        let mut response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body().await.unwrap(), "\"\" 10 [\"none\"]");
+
+        let mut form = multipart::Form::default();
+        form.add_text("title", "Hello");
+        form.add_text("page_size", "25");
+        form.add_text("words", "a b");
+        let mut response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.body().await.unwrap(),
+            "\"Hello\" 25 [\"a\", \"b\"]"
+        );
+
+        // a field that is present but invalid is not replaced by the default
+        let mut form = multipart::Form::default();
+        form.add_text("page_size", "many");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_limits_remaining() {
+        let mut limits = Limits::new(100, 50);
+        assert_eq!(limits.remaining(false), 100);
+        assert_eq!(limits.remaining(true), 50);
+
+        limits.field_limit_remaining = Some(20);
+        assert_eq!(limits.remaining(false), 20);
+
+        limits.try_consume_limits(15, true).unwrap();
+        assert_eq!(limits.remaining(false), 5);
+
+        limits.field_limit_remaining = None;
+        assert_eq!(limits.remaining(false), 85);
+        assert_eq!(limits.remaining(true), 35);
+    }
+
+    #[test]
+    fn test_limits_overflow_sizes() {
+        let mut limits = Limits::new(100, 50);
+        limits.try_consume_limits(40, true).unwrap();
+
+        match limits.try_consume_limits(20, true).unwrap_err() {
+            MultipartError::Payload(PayloadError::OverflowWithSize { limit, read }) => {
+                assert_eq!(limit, 50);
+                assert_eq!(read, 60);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+
+        match limits.try_consume_limits(70, false).unwrap_err() {
+            MultipartError::Payload(PayloadError::OverflowWithSize { limit, read }) => {
+                assert_eq!(limit, 100);
+                assert_eq!(read, 110);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
 }
@@ -0,0 +1,233 @@
+//! Streams a field into a user-supplied writer.
+
+use std::{borrow::Cow, io, sync::Arc};
+
+use actix_web::{http::StatusCode, web, Error, HttpRequest, ResponseError};
+use derive_more::{Display, Error};
+use futures_core::future::LocalBoxFuture;
+use futures_util::TryStreamExt as _;
+use mime::Mime;
+use tokio::io::{AsyncWrite, AsyncWriteExt as _};
+
+use super::FieldErrorHandler;
+use crate::{
+    form::{FieldReader, Limits},
+    Field, MultipartError,
+};
+
+/// Stream the field into a writer created by the registered [`SinkConfig<W>`].
+///
+/// Each chunk is written as soon as it is received, so the field is never fully buffered in memory
+/// or on disk. Once the field has been read, the writer is shut down and returned.
+///
+/// A `SinkConfig<W>` must be added to app data; reading the field fails otherwise.
+#[derive(Debug)]
+pub struct Sink<W> {
+    /// The writer that the field was streamed into.
+    pub writer: W,
+
+    /// The value of the `content-type` header.
+    pub content_type: Option<Mime>,
+
+    /// The `filename` value in the `content-disposition` header.
+    pub file_name: Option<String>,
+
+    /// The number of bytes written.
+    pub size: usize,
+}
+
+impl<'t, W> FieldReader<'t> for Sink<W>
+where
+    W: AsyncWrite + Unpin + 'static,
+{
+    type Future = LocalBoxFuture<'t, Result<Self, MultipartError>>;
+
+    fn read_field(req: &'t HttpRequest, mut field: Field, limits: &'t mut Limits) -> Self::Future {
+        Box::pin(async move {
+            let config = match SinkConfig::<W>::from_req(req) {
+                Some(config) => config,
+                None => {
+                    return Err(MultipartError::Field {
+                        name: field.form_field_name,
+                        source: SinkError::NotConfigured.into(),
+                    })
+                }
+            };
+
+            let mut size = 0;
+
+            let mut writer = (config.writer_factory)(req, &field)
+                .map_err(|err| config.map_error(req, &field.form_field_name, SinkError::Io(err)))?;
+
+            while let Some(chunk) = field.try_next().await? {
+                limits.try_consume_limits(chunk.len(), false)?;
+                size += chunk.len();
+                writer.write_all(chunk.as_ref()).await.map_err(|err| {
+                    config.map_error(req, &field.form_field_name, SinkError::Io(err))
+                })?;
+            }
+
+            writer
+                .shutdown()
+                .await
+                .map_err(|err| config.map_error(req, &field.form_field_name, SinkError::Io(err)))?;
+
+            Ok(Sink {
+                writer,
+                content_type: field.content_type().map(ToOwned::to_owned),
+                file_name: field.file_name().map(Cow::into_owned),
+                size,
+            })
+        })
+    }
+}
+
+/// Error type returned by the [`Sink`] field reader.
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum SinkError {
+    /// No `SinkConfig` was found in app data for this writer type.
+    #[display("Sink writer is not configured")]
+    NotConfigured,
+
+    /// Writer I/O Error
+    #[display("Sink I/O error: {}", _0)]
+    Io(io::Error),
+}
+
+impl ResponseError for SinkError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+type WriterFactory<W> = Arc<dyn Fn(&HttpRequest, &Field) -> io::Result<W> + Send + Sync>;
+
+/// Configuration for the [`Sink`] field reader.
+///
+/// # Examples
+/// ```
+/// use actix_multipart::form::{
+///     sink::{Sink, SinkConfig},
+///     MultipartForm,
+/// };
+/// use actix_web::{web, App, Responder};
+///
+/// #[derive(MultipartForm)]
+/// struct Upload {
+///     file: Sink<Vec<u8>>,
+/// }
+///
+/// async fn upload(form: MultipartForm<Upload>) -> impl Responder {
+///     format!("received {} bytes", form.file.size)
+/// }
+///
+/// App::new()
+///     .app_data(SinkConfig::<Vec<u8>>::new(|_req, _field| Ok(Vec::new())))
+///     .route("/", web::post().to(upload));
+/// ```
+pub struct SinkConfig<W> {
+    writer_factory: WriterFactory<W>,
+    err_handler: FieldErrorHandler<SinkError>,
+}
+
+impl<W> Clone for SinkConfig<W> {
+    fn clone(&self) -> Self {
+        Self {
+            writer_factory: Arc::clone(&self.writer_factory),
+            err_handler: self.err_handler.clone(),
+        }
+    }
+}
+
+impl<W: 'static> SinkConfig<W> {
+    /// Constructs new config using `writer_factory` to create a writer for each field.
+    pub fn new<F>(writer_factory: F) -> Self
+    where
+        F: Fn(&HttpRequest, &Field) -> io::Result<W> + Send + Sync + 'static,
+    {
+        Self {
+            writer_factory: Arc::new(writer_factory),
+            err_handler: None,
+        }
+    }
+
+    /// Sets custom error handler.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(SinkError, &HttpRequest) -> Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Extracts sink config from app data. Check both `T` and `Data<T>`, in that order.
+    fn from_req(req: &HttpRequest) -> Option<&Self> {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+    }
+
+    fn map_error(&self, req: &HttpRequest, field_name: &str, err: SinkError) -> MultipartError {
+        let source = if let Some(ref err_handler) = self.err_handler {
+            (err_handler)(err, req)
+        } else {
+            err.into()
+        };
+
+        MultipartError::Field {
+            name: field_name.to_owned(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use actix_multipart_rfc7578::client::multipart;
+    use actix_web::{http::StatusCode, web, App, HttpResponse, Responder};
+
+    use super::{Sink, SinkConfig};
+    use crate::form::{tests::send_form, MultipartForm};
+
+    #[derive(MultipartForm)]
+    struct SinkForm {
+        file: Sink<Vec<u8>>,
+    }
+
+    async fn test_sink_route(form: MultipartForm<SinkForm>) -> impl Responder {
+        let form = form.into_inner();
+        assert_eq!(form.file.writer, b"Hello, world!");
+        assert_eq!(form.file.size, 13);
+        assert_eq!(form.file.file_name.unwrap(), "testfile.txt");
+        assert_eq!(form.file.content_type.unwrap(), mime::TEXT_PLAIN);
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_sink_upload() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .app_data(SinkConfig::<Vec<u8>>::new(|_req, _field| Ok(Vec::new())))
+                .route("/", web::post().to(test_sink_route))
+        });
+
+        let mut form = multipart::Form::default();
+        let bytes = Cursor::new("Hello, world!");
+        form.add_reader_file_with_mime("file", bytes, "testfile.txt", mime::TEXT_PLAIN);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn test_sink_not_configured() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_sink_route)));
+
+        let mut form = multipart::Form::default();
+        let bytes = Cursor::new("Hello, world!");
+        form.add_reader_file_with_mime("file", bytes, "testfile.txt", mime::TEXT_PLAIN);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
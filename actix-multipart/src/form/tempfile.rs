@@ -1,6 +1,8 @@
 //! Writes a field to a temporary file on disk.
 
 use std::{
+    borrow::Cow,
+    fs::File,
     io,
     path::{Path, PathBuf},
     sync::Arc,
@@ -36,6 +38,27 @@ pub struct TempFile {
     pub size: usize,
 }
 
+impl TempFile {
+    /// Persists the temp file at `path`, replacing any existing file, so that it is not deleted
+    /// when dropped.
+    ///
+    /// When `path` is on the same filesystem as the temp file, this is a rename rather than a copy.
+    /// Persisting across filesystems fails; use [`TempFileConfig::directory`] to create temp files
+    /// on the same filesystem as their final destination.
+    ///
+    /// The temp file is deleted if persisting fails. See [`NamedTempFile::persist`] for details.
+    pub fn persist(self, path: impl AsRef<Path>) -> io::Result<File> {
+        self.file.persist(path).map_err(io::Error::from)
+    }
+
+    /// Persists the temp file at `path`, failing if a file already exists there.
+    ///
+    /// See [`persist`](Self::persist) and [`NamedTempFile::persist_noclobber`] for details.
+    pub fn persist_noclobber(self, path: impl AsRef<Path>) -> io::Result<File> {
+        self.file.persist_noclobber(path).map_err(io::Error::from)
+    }
+}
+
 impl<'t> FieldReader<'t> for TempFile {
     type Future = LocalBoxFuture<'t, Result<Self, MultipartError>>;
 
@@ -67,11 +90,7 @@ impl<'t> FieldReader<'t> for TempFile {
             Ok(TempFile {
                 file,
                 content_type: field.content_type().map(ToOwned::to_owned),
-                file_name: field
-                    .content_disposition()
-                    .expect("multipart form fields should have a content-disposition header")
-                    .get_filename()
-                    .map(ToOwned::to_owned),
+                file_name: field.file_name().map(Cow::into_owned),
                 size,
             })
         })
@@ -97,14 +116,26 @@ impl ResponseError for TempFileError {
 pub struct TempFileConfig {
     err_handler: FieldErrorHandler<TempFileError>,
     directory: Option<PathBuf>,
+    prefix: Option<String>,
+    suffix: Option<String>,
 }
 
 impl TempFileConfig {
     fn create_tempfile(&self) -> io::Result<NamedTempFile> {
+        let mut builder = tempfile::Builder::new();
+
+        if let Some(ref prefix) = self.prefix {
+            builder.prefix(prefix);
+        }
+
+        if let Some(ref suffix) = self.suffix {
+            builder.suffix(suffix);
+        }
+
         if let Some(ref dir) = self.directory {
-            NamedTempFile::new_in(dir)
+            builder.tempfile_in(dir)
         } else {
-            NamedTempFile::new()
+            builder.tempfile()
         }
     }
 }
@@ -147,11 +178,30 @@ impl TempFileConfig {
         self.directory = Some(dir.as_ref().to_owned());
         self
     }
+
+    /// Sets the prefix of temp file names.
+    ///
+    /// File names are made up of the prefix, a random string, and the suffix. The default prefix is
+    /// `.tmp`.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_owned());
+        self
+    }
+
+    /// Sets the suffix of temp file names, such as a file extension.
+    ///
+    /// The default suffix is empty.
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = Some(suffix.to_owned());
+        self
+    }
 }
 
 const DEFAULT_CONFIG: TempFileConfig = TempFileConfig {
     err_handler: None,
     directory: None,
+    prefix: None,
+    suffix: None,
 };
 
 impl Default for TempFileConfig {
@@ -167,7 +217,11 @@ mod tests {
     use actix_multipart_rfc7578::client::multipart;
     use actix_web::{http::StatusCode, web, App, HttpResponse, Responder};
 
-    use crate::form::{tempfile::TempFile, tests::send_form, MultipartForm};
+    use crate::form::{
+        tempfile::{TempFile, TempFileConfig},
+        tests::send_form,
+        MultipartForm,
+    };
 
     #[derive(MultipartForm)]
     struct FileForm {
@@ -194,4 +248,50 @@ mod tests {
         let response = send_form(&srv, form, "/").await;
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    async fn test_file_name_route(form: MultipartForm<FileForm>) -> impl Responder {
+        let file_name = form.file.file.path().file_name().unwrap().to_str().unwrap();
+        assert!(file_name.starts_with("upload-"));
+        assert!(file_name.ends_with(".bin"));
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_file_name_prefix_suffix() {
+        let srv = actix_test::start(|| {
+            App::new()
+                .app_data(TempFileConfig::default().prefix("upload-").suffix(".bin"))
+                .route("/", web::post().to(test_file_name_route))
+        });
+
+        let mut form = multipart::Form::default();
+        let bytes = Cursor::new("Hello, world!");
+        form.add_reader_file_with_mime("file", bytes, "testfile.txt", mime::TEXT_PLAIN);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    async fn test_persist_route(form: MultipartForm<FileForm>) -> impl Responder {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("upload.txt");
+
+        let form = form.into_inner();
+        let temp_path = form.file.file.path().to_owned();
+        form.file.persist(&path).unwrap();
+
+        assert!(!temp_path.exists());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Hello, world!");
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_file_persist() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_persist_route)));
+
+        let mut form = multipart::Form::default();
+        let bytes = Cursor::new("Hello, world!");
+        form.add_reader_file_with_mime("file", bytes, "testfile.txt", mime::TEXT_PLAIN);
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
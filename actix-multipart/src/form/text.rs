@@ -1,15 +1,15 @@
 //! Deserializes a field from plain text.
 
-use std::{str, sync::Arc};
+use std::{error::Error as StdError, future::ready, str, sync::Arc};
 
 use actix_web::{http::StatusCode, web, Error, HttpRequest, ResponseError};
-use derive_more::{Deref, DerefMut, Display, Error};
+use derive_more::{Deref, DerefMut, Display};
 use futures_core::future::LocalBoxFuture;
 use serde::de::DeserializeOwned;
 
 use super::FieldErrorHandler;
 use crate::{
-    form::{bytes::Bytes, FieldReader, Limits},
+    form::{bytes::Bytes, DuplicateField, FieldReader, Limits, State},
     Field, MultipartError,
 };
 
@@ -17,7 +17,7 @@ use crate::{
 ///
 /// Internally this uses [`serde_plain`] for deserialization, which supports primitive types
 /// including strings, numbers, and simple enums.
-#[derive(Debug, Deref, DerefMut)]
+#[derive(Debug, Default, Deref, DerefMut)]
 pub struct Text<T: DeserializeOwned>(pub T);
 
 impl<T: DeserializeOwned> Text<T> {
@@ -35,45 +35,120 @@ where
 
     fn read_field(req: &'t HttpRequest, field: Field, limits: &'t mut Limits) -> Self::Future {
         Box::pin(async move {
-            let config = TextConfig::from_req(req);
-
-            if config.validate_content_type {
-                let valid = if let Some(mime) = field.content_type() {
-                    mime.subtype() == mime::PLAIN || mime.suffix() == Some(mime::PLAIN)
-                } else {
-                    // https://datatracker.ietf.org/doc/html/rfc7578#section-4.4
-                    // content type defaults to text/plain, so None should be considered valid
-                    true
-                };
-
-                if !valid {
-                    return Err(MultipartError::Field {
-                        name: field.form_field_name,
-                        source: config.map_error(req, TextError::ContentType),
-                    });
-                }
-            }
+            read_text(req, field, limits, |text| {
+                serde_plain::from_str(text).map_err(TextError::Deserialize)
+            })
+            .await
+            .map(Text)
+        })
+    }
+}
+
+/// Reads the field as UTF-8 text and parses it using `parse`.
+async fn read_text<T>(
+    req: &HttpRequest,
+    field: Field,
+    limits: &mut Limits,
+    parse: impl FnOnce(&str) -> Result<T, TextError>,
+) -> Result<T, MultipartError> {
+    let config = TextConfig::from_req(req);
+
+    if config.validate_content_type {
+        let valid = if let Some(mime) = field.content_type() {
+            mime.subtype() == mime::PLAIN || mime.suffix() == Some(mime::PLAIN)
+        } else {
+            // https://datatracker.ietf.org/doc/html/rfc7578#section-4.4
+            // content type defaults to text/plain, so None should be considered valid
+            true
+        };
+
+        if !valid {
+            return Err(MultipartError::Field {
+                name: field.form_field_name,
+                source: config.map_error(req, TextError::ContentType),
+            });
+        }
+    }
 
-            let form_field_name = field.form_field_name.clone();
+    let form_field_name = field.form_field_name.clone();
+    let trim = field.trim_text;
 
-            let bytes = Bytes::read_field(req, field, limits).await?;
+    let bytes = Bytes::read_field(req, field, limits).await?;
 
-            let text = str::from_utf8(&bytes.data).map_err(|err| MultipartError::Field {
-                name: form_field_name.clone(),
-                source: config.map_error(req, TextError::Utf8Error(err)),
-            })?;
+    let text = str::from_utf8(&bytes.data).map_err(|err| MultipartError::Field {
+        name: form_field_name.clone(),
+        source: config.map_error(req, TextError::Utf8Error(err)),
+    })?;
 
-            Ok(Text(serde_plain::from_str(text).map_err(|err| {
-                MultipartError::Field {
-                    name: form_field_name,
-                    source: config.map_error(req, TextError::Deserialize(err)),
-                }
-            })?))
-        })
+    let text = if trim { text.trim() } else { text };
+
+    parse(text).map_err(|err| MultipartError::Field {
+        name: form_field_name,
+        source: config.map_error(req, err),
+    })
+}
+
+/// Reads a text field using a custom parse function and stores the result in the form state.
+///
+/// Used by the `MultipartForm` derive macro for fields with a `with` attribute.
+#[doc(hidden)]
+pub fn handle_field_with<'t, T, E, F>(
+    req: &'t HttpRequest,
+    field: Field,
+    limits: &'t mut Limits,
+    state: &'t mut State,
+    duplicate_field: DuplicateField,
+    parse: F,
+) -> LocalBoxFuture<'t, Result<(), MultipartError>>
+where
+    T: 'static,
+    E: Into<Box<dyn StdError + Send + Sync>>,
+    F: FnOnce(&str) -> Result<T, E> + 't,
+{
+    if state.contains_key(field.form_field_key()) {
+        match duplicate_field {
+            DuplicateField::Ignore => return Box::pin(ready(Ok(()))),
+
+            DuplicateField::Deny => {
+                return Box::pin(ready(Err(MultipartError::DuplicateField(
+                    field.form_field_name,
+                ))))
+            }
+
+            DuplicateField::Replace => {}
+        }
     }
+
+    Box::pin(async move {
+        let field_name = field.form_field_key().to_owned();
+        let t = read_text(req, field, limits, |text| {
+            parse(text).map_err(|err| TextError::Parse(err.into()))
+        })
+        .await?;
+        state.insert(field_name, Box::new(t));
+        Ok(())
+    })
 }
 
-#[derive(Debug, Display, Error)]
+/// Marks the field so that its text is trimmed before it is parsed.
+///
+/// Used by the `MultipartForm` derive macro for fields with a `trim` attribute.
+#[doc(hidden)]
+pub fn trim_field(mut field: Field) -> Field {
+    field.trim_text = true;
+    field
+}
+
+/// Takes the value stored by [`handle_field_with`] from the form state.
+#[doc(hidden)]
+pub fn from_state_with<T: 'static>(name: &str, state: &mut State) -> Result<T, MultipartError> {
+    state
+        .remove(name)
+        .map(|m| *m.downcast::<T>().unwrap())
+        .ok_or_else(|| MultipartError::MissingField(name.to_owned()))
+}
+
+#[derive(Debug, Display)]
 #[non_exhaustive]
 pub enum TextError {
     /// UTF-8 decoding error.
@@ -84,11 +159,27 @@ pub enum TextError {
     #[display("Plain text deserialize error: {}", _0)]
     Deserialize(serde_plain::Error),
 
+    /// Custom parse function error.
+    #[display("Plain text parse error: {}", _0)]
+    Parse(Box<dyn StdError + Send + Sync>),
+
     /// Content type error.
     #[display("Content type error")]
     ContentType,
 }
 
+// `Box<dyn StdError>` does not implement `StdError`, so the source chain is implemented manually
+impl StdError for TextError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Utf8Error(err) => Some(err),
+            Self::Deserialize(err) => Some(err),
+            Self::Parse(err) => Some(err.as_ref()),
+            Self::ContentType => None,
+        }
+    }
+}
+
 impl ResponseError for TextError {
     fn status_code(&self) -> StatusCode {
         StatusCode::BAD_REQUEST
@@ -194,4 +285,86 @@ mod tests {
         let response = send_form(&srv, form, "/").await;
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    fn parse_ids(text: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+        text.split(',').map(str::parse).collect()
+    }
+
+    #[derive(MultipartForm)]
+    struct TextWithForm {
+        #[multipart(with = "parse_ids")]
+        ids: Vec<u32>,
+    }
+
+    async fn test_text_with_route(form: MultipartForm<TextWithForm>) -> impl Responder {
+        assert_eq!(form.ids, [1, 2, 3]);
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_text_with() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_text_with_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("ids", "1,2,3");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Deny because parse function fails
+        let mut form = multipart::Form::default();
+        form.add_text("ids", "1,two,3");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_text_error_source_chain() {
+        use std::{error::Error as _, num::ParseIntError};
+
+        use crate::{form::text::TextError, MultipartError};
+
+        let parse_err = "two".parse::<u32>().unwrap_err();
+
+        let err = MultipartError::Field {
+            name: "ids".to_owned(),
+            source: TextError::Parse(Box::new(parse_err.clone())).into(),
+        };
+
+        let source = err.source().unwrap();
+        assert!(source.is::<TextError>());
+
+        let root = source.source().unwrap();
+        assert_eq!(root.downcast_ref::<ParseIntError>(), Some(&parse_err));
+        assert!(root.source().is_none());
+    }
+
+    #[derive(MultipartForm)]
+    struct TextTrimForm {
+        #[multipart(trim)]
+        number: Text<i32>,
+
+        #[multipart(trim)]
+        trimmed: Text<String>,
+
+        untrimmed: Text<String>,
+    }
+
+    async fn test_text_trim_route(form: MultipartForm<TextTrimForm>) -> impl Responder {
+        assert_eq!(*form.number, 1025);
+        assert_eq!(*form.trimmed, "hello");
+        assert_eq!(*form.untrimmed, " hello\r\n");
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_rt::test]
+    async fn test_text_trim() {
+        let srv = actix_test::start(|| App::new().route("/", web::post().to(test_text_trim_route)));
+
+        let mut form = multipart::Form::default();
+        form.add_text("number", " 1025\n");
+        form.add_text("trimmed", " hello\r\n");
+        form.add_text("untrimmed", " hello\r\n");
+        let response = send_form(&srv, form, "/").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
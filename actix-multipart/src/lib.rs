@@ -6,7 +6,9 @@
 //! client.
 //!
 //! Due to additional requirements for `multipart/form-data` requests, the higher level
-//! [`MultipartForm`] extractor and derive macro only supports this media type.
+//! [`MultipartForm`] extractor and derive macro only supports this media type. The
+//! [`MultipartMixed`] extractor collects the unnamed parts of `multipart/mixed` requests into the
+//! same derived types by position.
 //!
 //! # Examples
 //!
@@ -55,6 +57,7 @@
 //! ```
 //!
 //! [`MultipartForm`]: struct@form::MultipartForm
+//! [`MultipartMixed`]: form::MultipartMixed
 
 #![doc(html_logo_url = "https://actix.rs/img/logo.png")]
 #![doc(html_favicon_url = "https://actix.rs/favicon.ico")]
@@ -76,5 +79,5 @@ pub mod test;
 pub use self::{
     error::Error as MultipartError,
     field::{Field, LimitExceeded},
-    multipart::Multipart,
+    multipart::{Multipart, MultipartBytesRead},
 };
@@ -1,7 +1,7 @@
 //! Multipart response payload support.
 
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     pin::Pin,
     rc::Rc,
     task::{Context, Poll},
@@ -12,7 +12,7 @@ use actix_web::{
     error::{ParseError, PayloadError},
     http::header::{self, ContentDisposition, HeaderMap, HeaderName, HeaderValue},
     web::Bytes,
-    HttpRequest,
+    HttpMessage as _, HttpRequest,
 };
 use futures_core::stream::Stream;
 use mime::Mime;
@@ -35,6 +35,77 @@ const MAX_HEADERS: usize = 32;
 pub struct Multipart {
     flow: Flow,
     safety: Safety,
+    bytes_read: MultipartBytesRead,
+}
+
+/// Live byte counters of a [`Multipart`] stream: multipart body bytes vs. field content bytes.
+///
+/// [`payload`](Self::payload) counts every byte of the multipart body handed to the parser,
+/// including boundaries and part headers. [`fields`](Self::fields) counts only the field content
+/// yielded by [`Field`]s. The difference between the two is the multipart framing overhead.
+///
+/// Neither counter is an on-the-wire size. The body is counted after the server has removed any
+/// transfer encoding (e.g., chunked framing) and after any content decoding done by middleware, so
+/// transport overhead is not included.
+///
+/// This is a shared handle that keeps updating as the stream is read. The `Multipart` and
+/// `MultipartForm` extractors insert it into the request extensions, so that middleware can record
+/// the final counts once the handler has completed.
+///
+/// # Examples
+/// ```
+/// use actix_multipart::MultipartBytesRead;
+/// use actix_web::{
+///     body::MessageBody,
+///     dev::{ServiceRequest, ServiceResponse},
+///     middleware::Next,
+///     HttpMessage as _,
+/// };
+///
+/// async fn log_upload_size(
+///     req: ServiceRequest,
+///     next: Next<impl MessageBody>,
+/// ) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+///     let res = next.call(req).await?;
+///
+///     if let Some(read) = res.request().extensions().get::<MultipartBytesRead>() {
+///         log::info!("multipart: {} payload bytes, {} field bytes", read.payload(), read.fields());
+///     }
+///
+///     Ok(res)
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultipartBytesRead {
+    counts: Rc<ByteCounts>,
+}
+
+#[derive(Debug, Default)]
+struct ByteCounts {
+    payload: Cell<u64>,
+    fields: Cell<u64>,
+}
+
+impl MultipartBytesRead {
+    /// Returns the number of bytes of the multipart body read so far, including framing.
+    pub fn payload(&self) -> u64 {
+        self.counts.payload.get()
+    }
+
+    /// Returns the number of bytes of field content yielded so far.
+    pub fn fields(&self) -> u64 {
+        self.counts.fields.get()
+    }
+
+    pub(crate) fn add_payload(&self, len: usize) {
+        let len = len as u64;
+        self.counts.payload.set(self.payload().saturating_add(len));
+    }
+
+    pub(crate) fn add_fields(&self, len: usize) {
+        let len = len as u64;
+        self.counts.fields.set(self.fields().saturating_add(len));
+    }
 }
 
 enum Flow {
@@ -57,11 +128,17 @@ impl Multipart {
     }
 
     /// Creates multipart instance from parts.
+    ///
+    /// The stream's [`MultipartBytesRead`] counters are inserted into the request extensions.
     pub(crate) fn from_req(req: &HttpRequest, payload: &mut dev::Payload) -> Self {
-        match Self::find_ct_and_boundary(req.headers()) {
+        let multipart = match Self::find_ct_and_boundary(req.headers()) {
             Ok((ct, boundary)) => Self::from_ct_and_boundary(ct, boundary, payload.take()),
             Err(err) => Self::from_error(err),
-        }
+        };
+
+        req.extensions_mut().insert(multipart.bytes_read());
+
+        multipart
     }
 
     /// Extract Content-Type and boundary info from headers.
@@ -92,26 +169,78 @@ impl Multipart {
     where
         S: Stream<Item = Result<Bytes, PayloadError>> + 'static,
     {
+        let payload = PayloadBuffer::new(stream);
+
         Multipart {
             safety: Safety::new(),
+            bytes_read: payload.bytes_read.clone(),
             flow: Flow::InFlight(Inner {
-                payload: PayloadRef::new(PayloadBuffer::new(stream)),
+                payload: PayloadRef::new(payload),
                 content_type: ct,
                 boundary,
+                lenient: false,
                 state: State::FirstBoundary,
                 item: Item::None,
             }),
         }
     }
 
+    /// Enables lenient boundary handling for clients that deviate from RFC 2046.
+    ///
+    /// When enabled:
+    /// - leading and trailing whitespace is trimmed from the boundary parameter;
+    /// - boundary delimiter lines may have trailing whitespace before the line break;
+    /// - boundary delimiter lines may end with a bare `LF` instead of `CRLF`.
+    ///
+    /// A boundary that is empty after trimming is still rejected with
+    /// [`BoundaryMissing`](Error::BoundaryMissing). Must be called before reading any fields.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_multipart::Multipart;
+    /// use actix_web::{web, HttpRequest};
+    ///
+    /// async fn handler(req: HttpRequest, payload: web::Payload) -> String {
+    ///     let multipart = Multipart::new(req.headers(), payload).lenient_boundary(true);
+    ///     // ...
+    ///     # let _ = multipart;
+    ///     # String::new()
+    /// }
+    /// ```
+    pub fn lenient_boundary(mut self, lenient: bool) -> Self {
+        if let Flow::InFlight(ref mut inner) = self.flow {
+            inner.lenient = lenient;
+
+            if lenient {
+                let trimmed = inner.boundary.trim();
+
+                if trimmed.is_empty() {
+                    return Self::from_error(Error::BoundaryMissing);
+                }
+
+                inner.boundary = trimmed.to_owned();
+            }
+        }
+
+        self
+    }
+
     /// Constructs a new multipart reader from given `MultipartError`.
     pub(crate) fn from_error(err: Error) -> Multipart {
         Multipart {
             flow: Flow::Error(Some(err)),
             safety: Safety::new(),
+            bytes_read: MultipartBytesRead::default(),
         }
     }
 
+    /// Returns a handle to the byte counters of this stream.
+    ///
+    /// The handle keeps updating as fields are read. See [`MultipartBytesRead`].
+    pub fn bytes_read(&self) -> MultipartBytesRead {
+        self.bytes_read.clone()
+    }
+
     /// Return requests parsed Content-Type or raise the stored error.
     pub(crate) fn content_type_or_bail(&mut self) -> Result<mime::Mime, Error> {
         match self.flow {
@@ -183,6 +312,9 @@ struct Inner {
     /// Field boundary.
     boundary: String,
 
+    /// Whether to accept common deviations in boundary delimiter lines.
+    lenient: bool,
+
     state: State,
     item: Item,
 }
@@ -238,7 +370,11 @@ impl Inner {
     /// - `Ok(Some(false))` - field boundary read
     /// - `Ok(None)` - boundary not found, more data needs reading
     /// - `Err(BoundaryMissing)` - multipart boundary is missing
-    fn read_boundary(payload: &mut PayloadBuffer, boundary: &str) -> Result<Option<bool>, Error> {
+    fn read_boundary(
+        payload: &mut PayloadBuffer,
+        boundary: &str,
+        lenient: bool,
+    ) -> Result<Option<bool>, Error> {
         // TODO: need to read epilogue
         let chunk = match payload.readline_or_eof()? {
             // TODO: this might be okay as a let Some() else return Ok(None)
@@ -246,6 +382,12 @@ impl Inner {
             Some(chunk) => chunk,
         };
 
+        if lenient {
+            return Inner::lenient_delimiter(&chunk, boundary)
+                .map(Some)
+                .ok_or(Error::BoundaryMissing);
+        }
+
         const BOUNDARY_MARKER: &[u8] = b"--";
         const LINE_BREAK: &[u8] = b"\r\n";
 
@@ -283,15 +425,43 @@ impl Inner {
         Err(Error::BoundaryMissing)
     }
 
+    /// Parses a boundary delimiter line, allowing trailing whitespace and a bare `LF` line break.
+    ///
+    /// Returns `Some(true)` for the final delimiter, `Some(false)` for an in-between delimiter, and
+    /// `None` if the line is not a delimiter for `boundary`.
+    fn lenient_delimiter(line: &[u8], boundary: &str) -> Option<bool> {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let trimmed_len = line
+            .iter()
+            .rposition(|&b| !matches!(b, b' ' | b'\t' | b'\r'))
+            .map_or(0, |pos| pos + 1);
+
+        match line[..trimmed_len]
+            .strip_prefix(b"--")?
+            .strip_prefix(boundary.as_bytes())?
+        {
+            b"" => Some(false),
+            b"--" => Some(true),
+            _ => None,
+        }
+    }
+
     fn skip_until_boundary(
         payload: &mut PayloadBuffer,
         boundary: &str,
+        lenient: bool,
     ) -> Result<Option<bool>, Error> {
         let mut eof = false;
 
         loop {
             match payload.readline()? {
                 Some(chunk) => {
+                    if lenient {
+                        if let Some(is_final) = Inner::lenient_delimiter(&chunk, boundary) {
+                            eof = is_final;
+                            break;
+                        }
+                    }
                     if chunk.is_empty() {
                         return Err(Error::BoundaryMissing);
                     }
@@ -356,7 +526,11 @@ impl Inner {
                 match self.state {
                     // read until first boundary
                     State::FirstBoundary => {
-                        match Inner::skip_until_boundary(&mut payload, &self.boundary)? {
+                        match Inner::skip_until_boundary(
+                            &mut payload,
+                            &self.boundary,
+                            self.lenient,
+                        )? {
                             None => return Poll::Pending,
                             Some(eof) => {
                                 if eof {
@@ -370,17 +544,19 @@ impl Inner {
                     }
 
                     // read boundary
-                    State::Boundary => match Inner::read_boundary(&mut payload, &self.boundary)? {
-                        None => return Poll::Pending,
-                        Some(eof) => {
-                            if eof {
-                                self.state = State::Eof;
-                                return Poll::Ready(None);
-                            } else {
-                                self.state = State::Headers;
+                    State::Boundary => {
+                        match Inner::read_boundary(&mut payload, &self.boundary, self.lenient)? {
+                            None => return Poll::Pending,
+                            Some(eof) => {
+                                if eof {
+                                    self.state = State::Eof;
+                                    return Poll::Ready(None);
+                                } else {
+                                    self.state = State::Headers;
+                                }
                             }
                         }
-                    },
+                    }
 
                     _ => {}
                 }
@@ -403,20 +579,17 @@ impl Inner {
 
             let field_content_disposition = field_headers
                 .get(&header::CONTENT_DISPOSITION)
-                .and_then(|cd| ContentDisposition::from_raw(cd).ok())
-                .filter(|content_disposition| {
-                    matches!(
-                        content_disposition.disposition,
-                        header::DispositionType::FormData,
-                    )
-                });
+                .and_then(|cd| ContentDisposition::from_raw(cd).ok());
 
             let form_field_name = if self.content_type.subtype() == mime::FORM_DATA {
                 // According to RFC 7578 §4.2, which relates to "multipart/form-data" requests
                 // specifically, fields must have a Content-Disposition header, its disposition
                 // type must be set as "form-data", and it must have a name parameter.
 
-                let Some(cd) = &field_content_disposition else {
+                let Some(cd) = field_content_disposition
+                    .as_ref()
+                    .filter(|cd| cd.is_form_data())
+                else {
                     return Poll::Ready(Some(Err(Error::ContentDispositionMissing)));
                 };
 
@@ -589,6 +762,59 @@ mod tests {
         (bytes, headers)
     }
 
+    // boundary with trailing whitespace, as sent by some mobile HTTP clients
+    fn create_lenient_request_with_header() -> (Bytes, HeaderMap) {
+        let bytes = Bytes::from(
+            "--Boundary+0xAbCdEfGbOuNdArY\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"fn.txt\"\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\r\n\
+             test\r\n\
+             --Boundary+0xAbCdEfGbOuNdArY \t\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"fn.txt\"\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\r\n\
+             data\r\n\
+             --Boundary+0xAbCdEfGbOuNdArY--\n",
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static(
+                "multipart/form-data; boundary=\"Boundary+0xAbCdEfGbOuNdArY \"",
+            ),
+        );
+        (bytes, headers)
+    }
+
+    #[actix_rt::test]
+    async fn test_multipart_lenient_boundary() {
+        let (bytes, headers) = create_lenient_request_with_header();
+        let payload = stream::iter([Ok::<_, PayloadError>(bytes.clone())]);
+        let mut multipart = Multipart::new(&headers, payload);
+        assert!(multipart.next().await.unwrap().is_err());
+
+        let payload = stream::iter([Ok::<_, PayloadError>(bytes)]);
+        let mut multipart = Multipart::new(&headers, payload).lenient_boundary(true);
+
+        let mut field = multipart.next().await.unwrap().unwrap();
+        assert_eq!(get_whole_field(&mut field).await, "test");
+        drop(field);
+
+        let mut field = multipart.next().await.unwrap().unwrap();
+        assert_eq!(get_whole_field(&mut field).await, "data");
+        drop(field);
+
+        assert!(multipart.next().await.is_none());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("multipart/form-data; boundary=\" \""),
+        );
+        let payload = stream::iter([Ok::<_, PayloadError>(Bytes::new())]);
+        let mut multipart = Multipart::new(&headers, payload).lenient_boundary(true);
+        assert_matches!(multipart.next().await.unwrap(), Err(Error::BoundaryMissing));
+    }
+
     #[actix_rt::test]
     async fn test_multipart_no_end_crlf() {
         let (sender, payload) = create_stream();
@@ -718,6 +944,37 @@ mod tests {
         }
     }
 
+    #[actix_rt::test]
+    async fn test_bytes_read() {
+        use actix_web::FromRequest as _;
+
+        let (bytes, headers) = create_double_request_with_header();
+        let len = bytes.len() as u64;
+        let payload = stream::iter(bytes)
+            .map(|byte| Ok(Bytes::copy_from_slice(&[byte])))
+            .interleave_pending();
+
+        let mut multipart = Multipart::new(&headers, payload);
+        let bytes_read = multipart.bytes_read();
+        assert_eq!((bytes_read.payload(), bytes_read.fields()), (0, 0));
+
+        while let Some(field) = multipart.next().await {
+            get_whole_field(&mut field.unwrap()).await;
+        }
+
+        assert_eq!(bytes_read.payload(), len);
+        assert_eq!(bytes_read.fields(), 8);
+
+        let (req, mut payload) = TestRequest::default()
+            .insert_header((
+                header::CONTENT_TYPE,
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            ))
+            .to_http_parts();
+        let _multipart = Multipart::from_request(&req, &mut payload).await.unwrap();
+        assert!(req.extensions().get::<MultipartBytesRead>().is_some());
+    }
+
     #[actix_rt::test]
     async fn test_multipart_from_error() {
         let err = Error::ContentTypeMissing;
@@ -12,7 +12,7 @@ use actix_web::{
 };
 use futures_core::stream::{LocalBoxStream, Stream};
 
-use crate::{error::Error, safety::Safety};
+use crate::{error::Error, safety::Safety, MultipartBytesRead};
 
 pub(crate) struct PayloadRef {
     payload: Rc<RefCell<PayloadBuffer>>,
@@ -48,6 +48,8 @@ pub(crate) struct PayloadBuffer {
     pub(crate) buf: BytesMut,
     /// EOF flag. If true, no more payload reads will be attempted.
     pub(crate) eof: bool,
+    /// Counters shared with the owning `Multipart`.
+    pub(crate) bytes_read: MultipartBytesRead,
 }
 
 impl PayloadBuffer {
@@ -60,6 +62,7 @@ impl PayloadBuffer {
             stream: Box::pin(stream),
             buf: BytesMut::with_capacity(1_024), // pre-allocate 1KiB
             eof: false,
+            bytes_read: MultipartBytesRead::default(),
         }
     }
 
@@ -67,6 +70,7 @@ impl PayloadBuffer {
         loop {
             match Pin::new(&mut self.stream).poll_next(cx) {
                 Poll::Ready(Some(Ok(data))) => {
+                    self.bytes_read.add_payload(data.len());
                     self.buf.extend_from_slice(&data);
                     // try to read more data
                     continue;
@@ -1,7 +1,9 @@
 //! Multipart testing utilities.
 
 use actix_web::{
+    dev::Payload,
     http::header::{self, HeaderMap},
+    test::TestRequest,
     web::{BufMut as _, Bytes, BytesMut},
 };
 use mime::Mime;
@@ -85,10 +87,199 @@ pub fn create_form_data_payload_and_headers_with_boundary(
     let mut buf = BytesMut::with_capacity(file.len() + 128);
 
     let boundary_str = [BOUNDARY_PREFIX, boundary].concat();
-    let boundary = boundary_str.as_bytes();
 
+    write_part(
+        &mut buf,
+        &boundary_str,
+        name,
+        filename.as_deref(),
+        content_type.as_ref(),
+        &file,
+    );
+    write_terminator(&mut buf, &boundary_str);
+
+    (buf.freeze(), form_data_headers(&boundary_str))
+}
+
+/// Builder for `multipart/form-data` payloads containing any number of fields.
+///
+/// The resulting headers and payload can be passed directly to [`Multipart::new`], making it
+/// possible to test [`FieldReader`] implementations and handlers without a running server.
+///
+/// Multipart boundary used is a random alphanumeric string unless one is set using
+/// [`with_boundary`](Self::with_boundary).
+///
+/// # Examples
+/// ```
+/// use actix_multipart::{test::MultipartTestBuilder, Multipart};
+/// use futures_util::TryStreamExt as _;
+///
+/// # actix_web::rt::System::new().block_on(async {
+/// let (headers, payload) = MultipartTestBuilder::new()
+///     .text("name", "Lorem")
+///     .file("file", "lorem.txt", mime::TEXT_PLAIN, "Lorem ipsum.")
+///     .build_payload();
+///
+/// let mut multipart = Multipart::new(&headers, payload);
+///
+/// let mut field = multipart.try_next().await.unwrap().unwrap();
+/// assert_eq!(field.name(), Some("name"));
+/// assert_eq!(field.try_next().await.unwrap().unwrap(), "Lorem");
+///
+/// // fields must be dropped before the next one can be read
+/// drop(field);
+///
+/// let field = multipart.try_next().await.unwrap().unwrap();
+/// assert_eq!(field.content_disposition().unwrap().get_filename(), Some("lorem.txt"));
+/// assert_eq!(field.content_type(), Some(&mime::TEXT_PLAIN));
+/// # });
+/// ```
+///
+/// [`Multipart::new`]: crate::Multipart::new
+/// [`FieldReader`]: crate::form::FieldReader
+#[derive(Debug)]
+pub struct MultipartTestBuilder {
+    boundary: String,
+    buf: BytesMut,
+}
+
+impl MultipartTestBuilder {
+    /// Constructs new builder with a random boundary.
+    pub fn new() -> Self {
+        Self::with_boundary(&Alphanumeric.sample_string(&mut rand::rng(), 32))
+    }
+
+    /// Constructs new builder with a fixed boundary.
+    pub fn with_boundary(boundary: &str) -> Self {
+        Self {
+            boundary: [BOUNDARY_PREFIX, boundary].concat(),
+            buf: BytesMut::new(),
+        }
+    }
+
+    /// Appends a text field.
+    pub fn text(mut self, name: &str, value: impl AsRef<str>) -> Self {
+        write_part(
+            &mut self.buf,
+            &self.boundary,
+            name,
+            None,
+            None,
+            value.as_ref().as_bytes(),
+        );
+        self
+    }
+
+    /// Appends a file field with the given file name and content type.
+    pub fn file(
+        mut self,
+        name: &str,
+        filename: &str,
+        content_type: Mime,
+        data: impl AsRef<[u8]>,
+    ) -> Self {
+        write_part(
+            &mut self.buf,
+            &self.boundary,
+            name,
+            Some(filename),
+            Some(&content_type),
+            data.as_ref(),
+        );
+        self
+    }
+
+    /// Appends a field with the given content type and no file name.
+    pub fn bytes_with_mime(
+        mut self,
+        name: &str,
+        content_type: Mime,
+        data: impl AsRef<[u8]>,
+    ) -> Self {
+        write_part(
+            &mut self.buf,
+            &self.boundary,
+            name,
+            None,
+            Some(&content_type),
+            data.as_ref(),
+        );
+        self
+    }
+
+    /// Finishes the payload, returning a header map containing its content type and the payload
+    /// bytes.
+    pub fn build(mut self) -> (HeaderMap, Bytes) {
+        write_terminator(&mut self.buf, &self.boundary);
+        (form_data_headers(&self.boundary), self.buf.freeze())
+    }
+
+    /// Finishes the payload, returning a header map containing its content type and the payload
+    /// as a stream.
+    pub fn build_payload(self) -> (HeaderMap, Payload) {
+        let (headers, body) = self.build();
+        (headers, Payload::from(body))
+    }
+}
+
+impl Default for MultipartTestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extension trait for setting a multipart body on a [`TestRequest`].
+pub trait TestRequestExt {
+    /// Sets the content type header and body of the request from a finished multipart payload.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_multipart::{
+    ///     form::{text::Text, MultipartForm},
+    ///     test::{MultipartTestBuilder, TestRequestExt as _},
+    /// };
+    /// use actix_web::{test::TestRequest, FromRequest as _};
+    ///
+    /// #[derive(MultipartForm)]
+    /// struct Upload {
+    ///     name: Text<String>,
+    /// }
+    ///
+    /// # actix_web::rt::System::new().block_on(async {
+    /// let (req, mut payload) = TestRequest::post()
+    ///     .set_multipart(MultipartTestBuilder::new().text("name", "Lorem"))
+    ///     .to_http_parts();
+    ///
+    /// let form = MultipartForm::<Upload>::from_request(&req, &mut payload)
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(form.name.as_str(), "Lorem");
+    /// # });
+    /// ```
+    fn set_multipart(self, form: MultipartTestBuilder) -> Self;
+}
+
+impl TestRequestExt for TestRequest {
+    fn set_multipart(self, form: MultipartTestBuilder) -> Self {
+        let (headers, body) = form.build();
+
+        headers
+            .into_iter()
+            .fold(self, |req, hdr| req.insert_header(hdr))
+            .set_payload(body)
+    }
+}
+
+fn write_part(
+    buf: &mut BytesMut,
+    boundary: &str,
+    name: &str,
+    filename: Option<&str>,
+    content_type: Option<&Mime>,
+    data: &[u8],
+) {
     buf.put(HYPHENS);
-    buf.put(boundary);
+    buf.put(boundary.as_bytes());
     buf.put(CRLF);
 
     buf.put(format!("Content-Disposition: form-data; name=\"{name}\"").as_bytes());
@@ -102,26 +293,29 @@ pub fn create_form_data_payload_and_headers_with_boundary(
         buf.put(CRLF);
     }
 
-    buf.put(format!("Content-Length: {}", file.len()).as_bytes());
+    buf.put(format!("Content-Length: {}", data.len()).as_bytes());
     buf.put(CRLF_CRLF);
 
-    buf.put(file);
+    buf.put(data);
     buf.put(CRLF);
+}
 
+fn write_terminator(buf: &mut BytesMut, boundary: &str) {
     buf.put(HYPHENS);
-    buf.put(boundary);
+    buf.put(boundary.as_bytes());
     buf.put(HYPHENS);
     buf.put(CRLF);
+}
 
+fn form_data_headers(boundary: &str) -> HeaderMap {
     let mut headers = HeaderMap::new();
     headers.insert(
         header::CONTENT_TYPE,
-        format!("multipart/form-data; boundary=\"{boundary_str}\"")
+        format!("multipart/form-data; boundary=\"{boundary}\"")
             .parse()
             .unwrap(),
     );
-
-    (buf.freeze(), headers)
+    headers
 }
 
 #[cfg(test)]
@@ -214,4 +408,97 @@ mod tests {
         assert_eq!(field.content_type(), None);
         assert!(field.bytes().await.unwrap().starts_with(b"Lorem"));
     }
+
+    #[test]
+    fn builder_wire_format() {
+        let (headers, pl) = MultipartTestBuilder::with_boundary("qWeRtYuIoP")
+            .text("foo", "bar")
+            .bytes_with_mime("json", mime::APPLICATION_JSON, "{}")
+            .file("file", "Lorem.txt", mime::TEXT_PLAIN, "Lorem")
+            .build();
+
+        assert_eq!(
+            find_boundary(&headers),
+            "------------------------qWeRtYuIoP",
+        );
+
+        assert_eq!(
+            std::str::from_utf8(&pl).unwrap(),
+            "--------------------------qWeRtYuIoP\r\n\
+            Content-Disposition: form-data; name=\"foo\"\r\n\
+            Content-Length: 3\r\n\
+            \r\n\
+            bar\r\n\
+            --------------------------qWeRtYuIoP\r\n\
+            Content-Disposition: form-data; name=\"json\"\r\n\
+            Content-Type: application/json\r\n\
+            Content-Length: 2\r\n\
+            \r\n\
+            {}\r\n\
+            --------------------------qWeRtYuIoP\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"Lorem.txt\"\r\n\
+            Content-Type: text/plain\r\n\
+            Content-Length: 5\r\n\
+            \r\n\
+            Lorem\r\n\
+            --------------------------qWeRtYuIoP--\r\n",
+        );
+    }
+
+    #[actix_web::test]
+    async fn builder_payload() {
+        use futures_util::TryStreamExt as _;
+
+        use crate::Multipart;
+
+        let (headers, payload) = MultipartTestBuilder::new()
+            .text("foo", "bar")
+            .file("file", "Lorem.txt", mime::TEXT_PLAIN, "Lorem")
+            .build_payload();
+
+        let mut multipart = Multipart::new(&headers, payload);
+
+        let mut field = multipart.try_next().await.unwrap().unwrap();
+        assert_eq!(field.name(), Some("foo"));
+        assert_eq!(field.try_next().await.unwrap().unwrap(), "bar");
+        drop(field);
+
+        let mut field = multipart.try_next().await.unwrap().unwrap();
+        assert_eq!(field.name(), Some("file"));
+        assert_eq!(field.content_type(), Some(&mime::TEXT_PLAIN));
+        assert_eq!(field.try_next().await.unwrap().unwrap(), "Lorem");
+        drop(field);
+
+        assert!(multipart.try_next().await.unwrap().is_none());
+    }
+
+    #[actix_web::test]
+    async fn set_multipart() {
+        use actix_web::FromRequest as _;
+
+        use crate::form::{bytes::Bytes as FormBytes, text::Text, MultipartForm};
+
+        #[derive(MultipartForm)]
+        struct Upload {
+            name: Text<String>,
+            file: FormBytes,
+        }
+
+        let (req, mut payload) = TestRequest::post()
+            .set_multipart(MultipartTestBuilder::new().text("name", "Lorem").file(
+                "file",
+                "lorem.txt",
+                mime::TEXT_PLAIN,
+                "Lorem ipsum.",
+            ))
+            .to_http_parts();
+
+        let form = MultipartForm::<Upload>::from_request(&req, &mut payload)
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(form.name.as_str(), "Lorem");
+        assert_eq!(form.file.data, "Lorem ipsum.");
+        assert_eq!(form.file.file_name.as_deref(), Some("lorem.txt"));
+    }
 }
@@ -19,6 +19,8 @@ pub use actix_service::{
 
 #[doc(hidden)]
 pub use crate::handler::Handler;
+#[cfg(feature = "msgpack")]
+pub use crate::types::MsgPackBody;
 pub use crate::{
     config::{AppConfig, AppService},
     info::{ConnectionInfo, PeerAddr},
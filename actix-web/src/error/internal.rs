@@ -32,6 +32,7 @@ pub struct InternalError<T> {
 enum InternalErrorType {
     Status(StatusCode),
     Response(RefCell<Option<HttpResponse>>),
+    ResponseFn(Box<dyn Fn() -> HttpResponse>),
 }
 
 impl<T> InternalError<T> {
@@ -44,12 +45,30 @@ impl<T> InternalError<T> {
     }
 
     /// Constructs an `InternalError` with pre-defined response.
+    ///
+    /// The response can only be taken once; if [`error_response`](ResponseError::error_response)
+    /// is called again, a bare 500 Internal Server Error response is returned instead. Use
+    /// [`from_response_fn`](Self::from_response_fn) if the error may be converted more than once.
     pub fn from_response(cause: T, response: HttpResponse) -> Self {
         InternalError {
             cause,
             status: InternalErrorType::Response(RefCell::new(Some(response))),
         }
     }
+
+    /// Constructs an `InternalError` with a response built by `response_fn`.
+    ///
+    /// The response is rebuilt each time [`error_response`](ResponseError::error_response) is
+    /// called.
+    pub fn from_response_fn<F>(cause: T, response_fn: F) -> Self
+    where
+        F: Fn() -> HttpResponse + 'static,
+    {
+        InternalError {
+            cause,
+            status: InternalErrorType::ResponseFn(Box::new(response_fn)),
+        }
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for InternalError<T> {
@@ -78,6 +97,7 @@ where
                     StatusCode::INTERNAL_SERVER_ERROR
                 }
             }
+            InternalErrorType::ResponseFn(ref response_fn) => response_fn().status(),
         }
     }
 
@@ -101,6 +121,8 @@ where
                     HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR)
                 }
             }
+
+            InternalErrorType::ResponseFn(ref response_fn) => response_fn(),
         }
     }
 }
@@ -192,6 +214,23 @@ mod tests {
         let err = InternalError::from_response(ParseError::Method, HttpResponse::Ok().finish());
         let resp: HttpResponse = err.error_response();
         assert_eq!(resp.status(), StatusCode::OK);
+
+        // response is only available once
+        let resp: HttpResponse = err.error_response();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_internal_error_response_fn() {
+        let err = InternalError::from_response_fn(ParseError::Method, || {
+            HttpResponse::Conflict().body("conflict")
+        });
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+
+        for _ in 0..2 {
+            let resp: HttpResponse = err.error_response();
+            assert_eq!(resp.status(), StatusCode::CONFLICT);
+        }
     }
 
     #[test]
@@ -5,13 +5,16 @@
 // expanded manually.
 //
 // See <https://github.com/rust-lang/rust/issues/83375>
-pub use actix_http::error::{ContentTypeError, DispatchError, HttpError, ParseError, PayloadError};
+pub use actix_http::{
+    error::{ContentTypeError, DispatchError, HttpError, ParseError, PayloadError},
+    ws::HandshakeError as WsHandshakeError,
+};
 use derive_more::{Display, Error, From};
 use serde_json::error::Error as JsonError;
 use serde_urlencoded::{de::Error as FormDeError, ser::Error as FormError};
 use url::ParseError as UrlParseError;
 
-use crate::http::StatusCode;
+use crate::{http::StatusCode, HttpResponse};
 
 #[allow(clippy::module_inception)]
 mod error;
@@ -35,6 +38,58 @@ pub struct BlockingError;
 
 impl ResponseError for crate::error::BlockingError {}
 
+/// An error returned by [`HttpResponse::add_cookies()`] when a cookie results in a malformed
+/// `Set-Cookie` header.
+#[cfg(feature = "cookies")]
+#[derive(Debug, Display, Error)]
+#[display("malformed cookie at index {}: {}", index, source)]
+pub struct AddCookiesError {
+    index: usize,
+    source: HttpError,
+}
+
+#[cfg(feature = "cookies")]
+impl AddCookiesError {
+    pub(crate) fn new(index: usize, source: HttpError) -> Self {
+        Self { index, source }
+    }
+
+    /// Returns the index of the malformed cookie.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Unwraps into the header error of the malformed cookie.
+    pub fn into_inner(self) -> HttpError {
+        self.source
+    }
+}
+
+#[cfg(feature = "cookies")]
+impl ResponseError for AddCookiesError {}
+
+/// An error returned when a human-readable size limit, such as `"2MiB"`, can not be parsed.
+#[cfg(feature = "parse-size")]
+#[derive(Debug, Display, Error)]
+#[display("invalid size limit {:?}: {}", input, source)]
+pub struct SizeLimitError {
+    input: String,
+    source: parse_size::Error,
+}
+
+#[cfg(feature = "parse-size")]
+impl SizeLimitError {
+    /// Parses a human-readable size limit into a number of bytes.
+    pub(crate) fn parse(input: &str) -> Result<usize, Self> {
+        parse_size::parse_size(input)
+            .and_then(|size| usize::try_from(size).map_err(|_| parse_size::Error::PosOverflow))
+            .map_err(|source| Self {
+                input: input.to_owned(),
+                source,
+            })
+    }
+}
+
 /// Errors which can occur when attempting to generate resource uri.
 #[derive(Debug, PartialEq, Eq, Display, Error, From)]
 #[non_exhaustive]
@@ -62,7 +117,9 @@ pub enum UrlencodedError {
     #[display("Can not decode chunked transfer encoding.")]
     Chunked,
 
-    /// Payload size is larger than allowed. (default limit: 256kB).
+    /// Payload size is larger than allowed.
+    ///
+    /// The limit is configured using [`FormConfig::limit`](crate::web::FormConfig::limit).
     #[display(
         "URL encoded payload is larger ({} bytes) than allowed (limit: {} bytes).",
         size,
@@ -146,6 +203,47 @@ impl From<PayloadError> for JsonPayloadError {
     }
 }
 
+impl JsonPayloadError {
+    /// Creates a response with a structured JSON body describing this error.
+    ///
+    /// The body has the form `{ "error": "...", "detail": "..." }`, where `error` is a short
+    /// description of the kind of error and `detail` is the full error message. For deserialize
+    /// errors, `detail` includes the line and column at which parsing failed.
+    ///
+    /// The response uses the same status code as [`ResponseError::status_code`]. This can be used
+    /// from a [`JsonConfig`](crate::web::JsonConfig) error handler to send JSON errors to clients.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{error, web};
+    ///
+    /// let json_cfg = web::JsonConfig::default().error_handler(|err, _req| {
+    ///     let res = err.json_error_response();
+    ///     error::InternalError::from_response(err, res).into()
+    /// });
+    /// ```
+    pub fn json_error_response(&self) -> HttpResponse {
+        let error = match self {
+            Self::OverflowKnownLength { .. } | Self::Overflow { .. } => "payload too large",
+            Self::ContentType => "content type error",
+            Self::Deserialize(_) => "deserialize error",
+            Self::Serialize(_) => "serialize error",
+            Self::Payload(_) => "payload error",
+        };
+
+        let detail = match self {
+            Self::Deserialize(err) | Self::Serialize(err) => err.to_string(),
+            Self::Payload(err) => err.to_string(),
+            _ => self.to_string(),
+        };
+
+        HttpResponse::build(self.status_code()).json(serde_json::json!({
+            "error": error,
+            "detail": detail,
+        }))
+    }
+}
+
 impl ResponseError for JsonPayloadError {
     fn status_code(&self) -> StatusCode {
         match self {
@@ -161,6 +259,61 @@ impl ResponseError for JsonPayloadError {
     }
 }
 
+/// A set of errors that can occur during parsing MessagePack payloads.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum MsgPackPayloadError {
+    /// Payload size is bigger than allowed & content length header set. (default: 2MB)
+    #[display(
+        "MessagePack payload ({} bytes) is larger than allowed (limit: {} bytes).",
+        length,
+        limit
+    )]
+    OverflowKnownLength { length: usize, limit: usize },
+
+    /// Payload size is bigger than allowed but no content length header set. (default: 2MB)
+    #[display("MessagePack payload has exceeded limit ({} bytes).", limit)]
+    Overflow { limit: usize },
+
+    /// Content type error
+    #[display("Content type error")]
+    ContentType,
+
+    /// Deserialize error
+    #[display("MessagePack deserialize error: {}", _0)]
+    Deserialize(rmp_serde::decode::Error),
+
+    /// Serialize error
+    #[display("MessagePack serialize error: {}", _0)]
+    Serialize(rmp_serde::encode::Error),
+
+    /// Payload error
+    #[display("Error that occur during reading payload: {}", _0)]
+    Payload(PayloadError),
+}
+
+#[cfg(feature = "msgpack")]
+impl From<PayloadError> for MsgPackPayloadError {
+    fn from(err: PayloadError) -> Self {
+        Self::Payload(err)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl ResponseError for MsgPackPayloadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::OverflowKnownLength { .. } | Self::Overflow { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            Self::Serialize(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Payload(err) => err.status_code(),
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
 /// A set of errors that can occur during parsing request paths
 #[derive(Debug, Display, Error)]
 #[non_exhaustive]
@@ -184,11 +337,26 @@ pub enum QueryPayloadError {
     /// Query deserialize error.
     #[display("Query deserialize error: {}", _0)]
     Deserialize(serde::de::value::Error),
+
+    /// Query string is longer than the limit set in [`QueryConfig`](crate::web::QueryConfig).
+    #[display("Query string is too long (length: {length} bytes, limit: {limit} bytes)")]
+    #[from(ignore)]
+    Overflow {
+        /// Length of the query string, in bytes.
+        length: usize,
+
+        /// The configured limit, in bytes.
+        limit: usize,
+    },
 }
 
+/// Return `UriTooLong` for `QueryPayloadError::Overflow` and `BadRequest` otherwise.
 impl ResponseError for QueryPayloadError {
     fn status_code(&self) -> StatusCode {
-        StatusCode::BAD_REQUEST
+        match self {
+            Self::Overflow { .. } => StatusCode::URI_TOO_LONG,
+            _ => StatusCode::BAD_REQUEST,
+        }
     }
 }
 
@@ -250,6 +418,37 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[actix_rt::test]
+    async fn test_json_payload_error_json_response() {
+        use crate::body::to_bytes;
+
+        let err = serde_json::from_str::<serde_json::Value>("{\"a\":\n  tru}").unwrap_err();
+        let res = JsonPayloadError::Deserialize(err).json_error_response();
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            res.headers()
+                .get(crate::http::header::CONTENT_TYPE)
+                .unwrap(),
+            "application/json"
+        );
+
+        let body = to_bytes(res.into_body()).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["error"], "deserialize error");
+        assert_eq!(body["detail"], "expected ident at line 2 column 6");
+
+        let res = JsonPayloadError::Overflow { limit: 10 }.json_error_response();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let body = to_bytes(res.into_body()).await.unwrap();
+        let body = serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        assert_eq!(body["error"], "payload too large");
+        assert_eq!(
+            body["detail"],
+            "JSON payload has exceeded limit (10 bytes)."
+        );
+    }
+
     #[test]
     fn test_query_payload_error() {
         let resp = QueryPayloadError::Deserialize(
@@ -15,7 +15,7 @@ use crate::{
     error::{downcast_dyn, downcast_get_type_id},
     helpers,
     http::{
-        header::{self, TryIntoHeaderValue},
+        header::{self, HeaderMap, TryIntoHeaderValue},
         StatusCode,
     },
     HttpResponse,
@@ -32,10 +32,51 @@ pub trait ResponseError: fmt::Debug + fmt::Display {
         StatusCode::INTERNAL_SERVER_ERROR
     }
 
+    /// Returns additional headers to include in the error response.
+    ///
+    /// No headers are added by default. Headers returned here replace any default headers of the
+    /// same name. If [error_response](Self::error_response) is also implemented and does not call
+    /// `self.headers()`, then this will not be used.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{
+    ///     http::{
+    ///         header::{self, HeaderMap, HeaderValue},
+    ///         StatusCode,
+    ///     },
+    ///     ResponseError,
+    /// };
+    ///
+    /// #[derive(Debug, derive_more::Display)]
+    /// #[display("service is down for maintenance")]
+    /// struct Maintenance;
+    ///
+    /// impl ResponseError for Maintenance {
+    ///     fn status_code(&self) -> StatusCode {
+    ///         StatusCode::SERVICE_UNAVAILABLE
+    ///     }
+    ///
+    ///     fn headers(&self) -> HeaderMap {
+    ///         let mut headers = HeaderMap::new();
+    ///         headers.insert(header::RETRY_AFTER, HeaderValue::from_static("120"));
+    ///         headers
+    ///     }
+    /// }
+    ///
+    /// let res = Maintenance.error_response();
+    /// assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    /// assert_eq!(res.headers().get(header::RETRY_AFTER).unwrap(), "120");
+    /// ```
+    fn headers(&self) -> HeaderMap {
+        HeaderMap::new()
+    }
+
     /// Creates full response for error.
     ///
     /// By default, the generated response uses a 500 Internal Server Error status code, a
-    /// `Content-Type` of `text/plain`, and the body is set to `Self`'s `Display` impl.
+    /// `Content-Type` of `text/plain`, any headers returned from [headers](Self::headers), and the
+    /// body is set to `Self`'s `Display` impl.
     fn error_response(&self) -> HttpResponse<BoxBody> {
         let mut res = HttpResponse::new(self.status_code());
 
@@ -45,6 +86,16 @@ pub trait ResponseError: fmt::Debug + fmt::Display {
         let mime = mime::TEXT_PLAIN_UTF_8.try_into_value().unwrap();
         res.headers_mut().insert(header::CONTENT_TYPE, mime);
 
+        let headers = self.headers();
+
+        for name in headers.keys() {
+            res.headers_mut().remove(name);
+        }
+
+        for (name, value) in headers {
+            res.headers_mut().append(name, value);
+        }
+
         res.set_body(BoxBody::new(buf))
     }
 
@@ -117,11 +168,23 @@ impl ResponseError for actix_http::error::ParseError {
     }
 }
 
+/// Returns `PayloadTooLarge` for `Overflow`, `LengthRequired` for `UnknownLength`,
+/// `RequestTimeout` for `Timeout`, and `BadRequest` for incomplete or corrupted payloads. Transport
+/// errors (I/O and HTTP/2) return `InternalServerError`.
 impl ResponseError for actix_http::error::PayloadError {
     fn status_code(&self) -> StatusCode {
+        use actix_http::error::PayloadError;
+
         match *self {
-            actix_http::error::PayloadError::Overflow => StatusCode::PAYLOAD_TOO_LARGE,
-            _ => StatusCode::BAD_REQUEST,
+            PayloadError::Incomplete(_) | PayloadError::EncodingCorrupted => {
+                StatusCode::BAD_REQUEST
+            }
+            PayloadError::Overflow | PayloadError::OverflowWithSize { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            PayloadError::UnknownLength => StatusCode::LENGTH_REQUIRED,
+            PayloadError::Timeout => StatusCode::REQUEST_TIMEOUT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
@@ -140,6 +203,12 @@ impl ResponseError for actix_http::ws::HandshakeError {
     }
 }
 
+impl ResponseError for actix_http::ws::SubprotocolHandshakeError {
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        Response::from(self).map_into_boxed_body().into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +226,85 @@ mod tests {
         let not_err = resp_err.downcast_ref::<ContentTypeError>();
         assert!(not_err.is_none());
     }
+
+    #[test]
+    fn test_overflow_with_size() {
+        use actix_http::error::PayloadError;
+
+        let err = PayloadError::OverflowWithSize {
+            limit: 1024,
+            read: 2048,
+        };
+        assert_eq!(
+            err.to_string(),
+            "payload reached size limit (limit: 1024 bytes, read: 2048 bytes)"
+        );
+        assert_eq!(err.status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn test_payload_error_status() {
+        use std::io;
+
+        use actix_http::error::PayloadError;
+
+        let cases = [
+            (PayloadError::Incomplete(None), StatusCode::BAD_REQUEST),
+            (PayloadError::EncodingCorrupted, StatusCode::BAD_REQUEST),
+            (PayloadError::Overflow, StatusCode::PAYLOAD_TOO_LARGE),
+            (
+                PayloadError::OverflowWithSize { limit: 1, read: 2 },
+                StatusCode::PAYLOAD_TOO_LARGE,
+            ),
+            (PayloadError::UnknownLength, StatusCode::LENGTH_REQUIRED),
+            (PayloadError::Timeout, StatusCode::REQUEST_TIMEOUT),
+            (
+                PayloadError::Io(io::Error::other("reset")),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (err, status) in cases {
+            assert_eq!(err.status_code(), status);
+            assert_eq!(err.error_response().status(), status);
+        }
+    }
+
+    #[test]
+    fn test_headers() {
+        use crate::http::header::HeaderValue;
+
+        #[derive(Debug, derive_more::Display)]
+        #[display("unauthorized")]
+        struct Unauthorized;
+
+        impl ResponseError for Unauthorized {
+            fn status_code(&self) -> StatusCode {
+                StatusCode::UNAUTHORIZED
+            }
+
+            fn headers(&self) -> HeaderMap {
+                let mut headers = HeaderMap::new();
+                headers.append(
+                    header::WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Basic realm=\"a\""),
+                );
+                headers.append(
+                    header::WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Bearer realm=\"b\""),
+                );
+                headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/html"));
+                headers
+            }
+        }
+
+        let res = Unauthorized.error_response();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(res.headers().get_all(header::WWW_AUTHENTICATE).count(), 2);
+        assert_eq!(res.headers().get_all(header::CONTENT_TYPE).count(), 1);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html"
+        );
+    }
 }
@@ -44,6 +44,15 @@ common_header! {
     ///     CacheDirective::Extension("foo".to_owned(), Some("bar".to_owned())),
     /// ]));
     /// ```
+    ///
+    /// Directives can also be added fluently:
+    /// ```
+    /// use actix_web::HttpResponse;
+    /// use actix_web::http::header::CacheControl;
+    ///
+    /// let mut builder = HttpResponse::Ok();
+    /// builder.insert_header(CacheControl::new().public().max_age(3600));
+    /// ```
     (CacheControl, header::CACHE_CONTROL) => (CacheDirective)+
 
     test_parse_and_format {
@@ -89,6 +98,94 @@ common_header! {
                 Some(CacheControl(vec![CacheDirective::MaxAge(200)]))
             )
         }
+
+        #[test]
+        fn builder_round_trip() {
+            let cc = CacheControl::new()
+                .public()
+                .max_age(3600)
+                .s_maxage(60)
+                .must_revalidate()
+                .directive(CacheDirective::Extension("immutable".to_owned(), None));
+            assert_eq!(
+                cc.to_string(),
+                "public, max-age=3600, s-maxage=60, must-revalidate, immutable"
+            );
+
+            let req = test::TestRequest::default()
+                .insert_header(cc.clone())
+                .finish();
+            assert_eq!(Header::parse(&req).ok(), Some(cc));
+
+            let cc = CacheControl::new().no_store().no_cache().private();
+            assert_eq!(cc.to_string(), "no-store, no-cache, private");
+        }
+    }
+}
+
+impl CacheControl {
+    /// Constructs a `Cache-Control` header with no directives.
+    ///
+    /// At least one directive should be added before sending the header.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Adds a directive.
+    pub fn directive(mut self, directive: CacheDirective) -> Self {
+        self.0.push(directive);
+        self
+    }
+
+    /// Adds the `no-cache` directive.
+    pub fn no_cache(self) -> Self {
+        self.directive(CacheDirective::NoCache)
+    }
+
+    /// Adds the `no-store` directive.
+    pub fn no_store(self) -> Self {
+        self.directive(CacheDirective::NoStore)
+    }
+
+    /// Adds the `no-transform` directive.
+    pub fn no_transform(self) -> Self {
+        self.directive(CacheDirective::NoTransform)
+    }
+
+    /// Adds the `max-age` directive, in seconds.
+    pub fn max_age(self, secs: u32) -> Self {
+        self.directive(CacheDirective::MaxAge(secs))
+    }
+
+    /// Adds the `must-revalidate` directive.
+    pub fn must_revalidate(self) -> Self {
+        self.directive(CacheDirective::MustRevalidate)
+    }
+
+    /// Adds the `public` directive.
+    pub fn public(self) -> Self {
+        self.directive(CacheDirective::Public)
+    }
+
+    /// Adds the `private` directive.
+    pub fn private(self) -> Self {
+        self.directive(CacheDirective::Private)
+    }
+
+    /// Adds the `proxy-revalidate` directive.
+    pub fn proxy_revalidate(self) -> Self {
+        self.directive(CacheDirective::ProxyRevalidate)
+    }
+
+    /// Adds the `s-maxage` directive, in seconds.
+    pub fn s_maxage(self, secs: u32) -> Self {
+        self.directive(CacheDirective::SMaxAge(secs))
+    }
+}
+
+impl Default for CacheControl {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
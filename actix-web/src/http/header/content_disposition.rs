@@ -18,7 +18,7 @@ use regex::Regex;
 #[cfg(not(feature = "unicode"))]
 use regex_lite::Regex;
 
-use super::{ExtendedValue, Header, TryIntoHeaderValue, Writer};
+use super::{Charset, ExtendedValue, Header, HeaderWriter, TryIntoHeaderValue};
 use crate::http::header;
 
 /// Split at the index of the first `needle` if it exists or at the end.
@@ -322,6 +322,9 @@ pub struct ContentDisposition {
 impl ContentDisposition {
     /// Constructs a Content-Disposition header suitable for downloads.
     ///
+    /// If the file name contains non-ASCII characters, a percent-encoded `filename*` parameter is
+    /// also included, as described in [RFC 6266 §4.3].
+    ///
     /// # Examples
     /// ```
     /// use actix_web::http::header::{ContentDisposition, TryIntoHeaderValue as _};
@@ -330,11 +333,49 @@ impl ContentDisposition {
     ///
     /// let cd_val = cd.try_into_value().unwrap();
     /// assert_eq!(cd_val, "attachment; filename=\"files.zip\"");
+    ///
+    /// let cd = ContentDisposition::attachment("résumé.pdf");
+    /// assert_eq!(
+    ///     cd.to_string(),
+    ///     "attachment; filename=\"résumé.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf",
+    /// );
     /// ```
+    ///
+    /// [RFC 6266 §4.3]: https://datatracker.ietf.org/doc/html/rfc6266#section-4.3
     pub fn attachment(filename: impl Into<String>) -> Self {
+        Self::with_filename(DispositionType::Attachment, filename.into())
+    }
+
+    /// Constructs a Content-Disposition header suitable for displaying a file in the browser.
+    ///
+    /// The file name is encoded in the same way as [`attachment`](Self::attachment).
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::http::header::ContentDisposition;
+    ///
+    /// let cd = ContentDisposition::inline("report.pdf");
+    /// assert_eq!(cd.to_string(), "inline; filename=\"report.pdf\"");
+    /// ```
+    pub fn inline(filename: impl Into<String>) -> Self {
+        Self::with_filename(DispositionType::Inline, filename.into())
+    }
+
+    fn with_filename(disposition: DispositionType, filename: String) -> Self {
+        let mut parameters = Vec::with_capacity(2);
+
+        let filename_ext = (!filename.is_ascii()).then(|| ExtendedValue {
+            charset: Charset::Ext(String::from("UTF-8")),
+            language_tag: None,
+            value: filename.as_bytes().to_vec(),
+        });
+
+        parameters.push(DispositionParam::Filename(filename));
+        parameters.extend(filename_ext.map(DispositionParam::FilenameExt));
+
         Self {
-            disposition: DispositionType::Attachment,
-            parameters: vec![DispositionParam::Filename(filename.into())],
+            disposition,
+            parameters,
         }
     }
 
@@ -486,7 +527,7 @@ impl TryIntoHeaderValue for ContentDisposition {
     type Error = header::InvalidHeaderValue;
 
     fn try_into_value(self) -> Result<header::HeaderValue, Self::Error> {
-        let mut writer = Writer::new();
+        let mut writer = HeaderWriter::new();
         let _ = write!(&mut writer, "{}", self);
         header::HeaderValue::from_maybe_shared(writer.take())
     }
@@ -934,6 +975,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_constructors() {
+        let cd = ContentDisposition::attachment("files.zip");
+        assert!(cd.is_attachment());
+        assert_eq!(cd.get_filename(), Some("files.zip"));
+        assert!(cd.get_filename_ext().is_none());
+
+        let cd = ContentDisposition::inline("résumé.pdf");
+        assert!(cd.is_inline());
+        assert_eq!(cd.get_filename(), Some("résumé.pdf"));
+        assert_eq!(
+            cd.get_filename_ext().unwrap().value,
+            "résumé.pdf".as_bytes(),
+        );
+
+        let val = HeaderValue::from_str(&cd.to_string()).unwrap();
+        assert_eq!(ContentDisposition::from_raw(&val).unwrap(), cd);
+    }
+
     #[test]
     fn test_display_quote() {
         let as_string = "form-data; name=upload; filename=\"Quote\\\"here.png\"";
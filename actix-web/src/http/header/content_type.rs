@@ -103,4 +103,22 @@ impl ContentType {
     pub fn octet_stream() -> ContentType {
         ContentType(mime::APPLICATION_OCTET_STREAM)
     }
+
+    /// Constructs a `Content-Type: text/csv; charset=utf-8` header.
+    #[inline]
+    pub fn csv() -> ContentType {
+        ContentType(mime::TEXT_CSV_UTF_8)
+    }
+
+    /// Constructs a `Content-Type: application/pdf` header.
+    #[inline]
+    pub fn pdf() -> ContentType {
+        ContentType(mime::APPLICATION_PDF)
+    }
+
+    /// Constructs a `Content-Type: application/wasm` header.
+    #[inline]
+    pub fn wasm() -> ContentType {
+        ContentType("application/wasm".parse().unwrap())
+    }
 }
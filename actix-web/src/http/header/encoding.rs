@@ -32,6 +32,10 @@ impl Encoding {
     pub const fn zstd() -> Self {
         Self::Known(ContentEncoding::Zstd)
     }
+
+    pub const fn lz4() -> Self {
+        Self::Known(ContentEncoding::Lz4)
+    }
 }
 
 impl fmt::Display for Encoding {
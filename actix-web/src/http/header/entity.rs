@@ -3,7 +3,7 @@ use std::{
     str::FromStr,
 };
 
-use super::{HeaderValue, InvalidHeaderValue, TryIntoHeaderValue, Writer};
+use super::{HeaderValue, HeaderWriter, InvalidHeaderValue, TryIntoHeaderValue};
 
 /// check that each char in the slice is either:
 /// 1. `%x21`, or
@@ -177,7 +177,7 @@ impl TryIntoHeaderValue for EntityTag {
     type Error = InvalidHeaderValue;
 
     fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
-        let mut wrt = Writer::new();
+        let mut wrt = HeaderWriter::new();
         write!(wrt, "{}", self).unwrap();
         HeaderValue::from_maybe_shared(wrt.take())
     }
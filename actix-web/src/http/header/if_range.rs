@@ -1,8 +1,8 @@
 use std::fmt::{self, Display, Write};
 
 use super::{
-    from_one_raw_str, EntityTag, Header, HeaderName, HeaderValue, HttpDate, InvalidHeaderValue,
-    TryIntoHeaderValue, Writer,
+    from_one_raw_str, EntityTag, Header, HeaderName, HeaderValue, HeaderWriter, HttpDate,
+    InvalidHeaderValue, TryIntoHeaderValue,
 };
 use crate::{error::ParseError, http::header, HttpMessage};
 
@@ -98,7 +98,7 @@ impl TryIntoHeaderValue for IfRange {
     type Error = InvalidHeaderValue;
 
     fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
-        let mut writer = Writer::new();
+        let mut writer = HeaderWriter::new();
         let _ = write!(&mut writer, "{}", self);
         HeaderValue::from_maybe_shared(writer.take())
     }
@@ -131,7 +131,7 @@ macro_rules! common_header {
             #[inline]
             fn try_into_value(self) -> Result<$crate::http::header::HeaderValue, Self::Error> {
                 use ::core::fmt::Write;
-                let mut writer = $crate::http::header::Writer::new();
+                let mut writer = $crate::http::header::HeaderWriter::new();
                 let _ = write!(&mut writer, "{}", self);
                 $crate::http::header::HeaderValue::from_maybe_shared(writer.take())
             }
@@ -178,7 +178,7 @@ macro_rules! common_header {
             #[inline]
             fn try_into_value(self) -> Result<$crate::http::header::HeaderValue, Self::Error> {
                 use ::core::fmt::Write;
-                let mut writer = $crate::http::header::Writer::new();
+                let mut writer = $crate::http::header::HeaderWriter::new();
                 let _ = write!(&mut writer, "{}", self);
                 $crate::http::header::HeaderValue::from_maybe_shared(writer.take())
             }
@@ -273,7 +273,7 @@ macro_rules! common_header {
             #[inline]
             fn try_into_value(self) -> Result<$crate::http::header::HeaderValue, Self::Error> {
                 use ::core::fmt::Write;
-                let mut writer = $crate::http::header::Writer::new();
+                let mut writer = $crate::http::header::HeaderWriter::new();
                 let _ = write!(&mut writer, "{}", self);
                 $crate::http::header::HeaderValue::from_maybe_shared(writer.take())
             }
@@ -72,16 +72,35 @@ pub use self::{
     range::{ByteRangeSpec, Range},
 };
 
-/// Format writer ([`fmt::Write`]) for a [`BytesMut`].
+/// Format writer ([`fmt::Write`]) for serializing header values.
+///
+/// This is the buffer used by the typed headers in this module to implement
+/// [`TryIntoHeaderValue`]. Third-party typed headers can use it in the same way: write the value
+/// using [`write!`], then convert the [taken](Self::take) bytes into a [`HeaderValue`].
+///
+/// # Examples
+/// ```
+/// use std::fmt::Write as _;
+///
+/// use actix_web::http::header::{HeaderValue, HeaderWriter, InvalidHeaderValue};
+///
+/// fn serialize(min: u32, max: u32) -> Result<HeaderValue, InvalidHeaderValue> {
+///     let mut writer = HeaderWriter::new();
+///     let _ = write!(writer, "min={min}, max={max}");
+///     HeaderValue::from_maybe_shared(writer.take())
+/// }
+///
+/// assert_eq!(serialize(1, 10).unwrap(), "min=1, max=10");
+/// ```
 #[derive(Debug, Default)]
-struct Writer {
+pub struct HeaderWriter {
     buf: BytesMut,
 }
 
-impl Writer {
-    /// Constructs new bytes writer.
-    pub fn new() -> Writer {
-        Writer::default()
+impl HeaderWriter {
+    /// Constructs new header writer.
+    pub fn new() -> HeaderWriter {
+        HeaderWriter::default()
     }
 
     /// Splits bytes out of writer, leaving writer buffer empty.
@@ -90,7 +109,7 @@ impl Writer {
     }
 }
 
-impl fmt::Write for Writer {
+impl fmt::Write for HeaderWriter {
     #[inline]
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.buf.extend_from_slice(s.as_bytes());
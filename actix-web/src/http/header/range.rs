@@ -6,7 +6,9 @@ use std::{
 
 use actix_http::{error::ParseError, header, HttpMessage};
 
-use super::{Header, HeaderName, HeaderValue, InvalidHeaderValue, TryIntoHeaderValue, Writer};
+use super::{
+    Header, HeaderName, HeaderValue, HeaderWriter, InvalidHeaderValue, TryIntoHeaderValue,
+};
 
 /// `Range` header, defined
 /// in [RFC 7233 §3.1](https://datatracker.ietf.org/doc/html/rfc7233#section-3.1)
@@ -278,7 +280,7 @@ impl TryIntoHeaderValue for Range {
     type Error = InvalidHeaderValue;
 
     fn try_into_value(self) -> Result<HeaderValue, Self::Error> {
-        let mut wrt = Writer::new();
+        let mut wrt = HeaderWriter::new();
         let _ = write!(wrt, "{}", self);
         HeaderValue::from_maybe_shared(wrt.take())
     }
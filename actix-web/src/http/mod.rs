@@ -2,4 +2,7 @@
 
 pub mod header;
 
-pub use actix_http::{uri, ConnectionType, Error, KeepAlive, Method, StatusCode, Uri, Version};
+pub use actix_http::{
+    uri, ConnectionType, DateHeader, Error, KeepAlive, KeepAliveTimeout, Method, StatusCode,
+    Trailers, Uri, Version,
+};
@@ -63,12 +63,15 @@
 //! - `compress-brotli` - brotli content encoding compression support (enabled by default)
 //! - `compress-gzip` - gzip and deflate content encoding compression support (enabled by default)
 //! - `compress-zstd` - zstd content encoding compression support (enabled by default)
+//! - `compress-lz4` - lz4 content encoding compression support
 //! - `openssl` - HTTPS support via `openssl` crate, supports `HTTP/2`
 //! - `rustls` - HTTPS support via `rustls` 0.20 crate, supports `HTTP/2`
 //! - `rustls-0_21` - HTTPS support via `rustls` 0.21 crate, supports `HTTP/2`
 //! - `rustls-0_22` - HTTPS support via `rustls` 0.22 crate, supports `HTTP/2`
 //! - `rustls-0_23` - HTTPS support via `rustls` 0.23 crate, supports `HTTP/2`
 //! - `secure-cookies` - secure cookies support
+//! - `msgpack` - MessagePack extractor and responder using `rmp-serde`
+//! - `parse-size` - human-readable payload size limits, such as `"2MiB"`, using `parse-size`
 
 #![doc(html_logo_url = "https://actix.rs/img/logo.png")]
 #![doc(html_favicon_url = "https://actix.rs/favicon.ico")]
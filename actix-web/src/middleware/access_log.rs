@@ -0,0 +1,500 @@
+//! For middleware documentation, see [`AccessLog`].
+
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    future::Future,
+    marker::PhantomData,
+    net::SocketAddr,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use actix_http::{error::PayloadError, Payload};
+use actix_service::{Service, Transform};
+use actix_utils::future::{ready, Ready};
+use bytes::Bytes;
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+use serde::ser::{Serialize, SerializeMap as _, Serializer};
+
+use crate::{
+    body::{BodySize, MessageBody},
+    http::{Method, StatusCode},
+    service::{ServiceRequest, ServiceResponse},
+    Error, HttpMessage as _,
+};
+
+/// A field that can be included in an [`AccessLogRecord`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AccessLogField {
+    /// Request method.
+    Method,
+
+    /// Request path, without the query string.
+    Path,
+
+    /// Response status code.
+    Status,
+
+    /// Time taken to serve the request, including sending the response body.
+    Duration,
+
+    /// Number of request body bytes read by the application.
+    BytesIn,
+
+    /// Number of response body bytes sent.
+    BytesOut,
+
+    /// Peer socket address.
+    RemoteAddr,
+}
+
+impl AccessLogField {
+    const ALL: [AccessLogField; 7] = [
+        AccessLogField::Method,
+        AccessLogField::Path,
+        AccessLogField::Status,
+        AccessLogField::Duration,
+        AccessLogField::BytesIn,
+        AccessLogField::BytesOut,
+        AccessLogField::RemoteAddr,
+    ];
+}
+
+/// A structured summary of a request and its response, produced by the [`AccessLog`] middleware.
+///
+/// Fields that were not selected, or that are not known for the request, are `None`.
+///
+/// Records implement [`Serialize`] as a map containing only the fields that are set, using the
+/// keys `method`, `path`, `status`, `duration_ms`, `bytes_in`, `bytes_out`, and `remote_addr`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AccessLogRecord {
+    /// Request method.
+    pub method: Option<Method>,
+
+    /// Request path, without the query string.
+    pub path: Option<String>,
+
+    /// Response status code.
+    pub status: Option<StatusCode>,
+
+    /// Time taken to serve the request, including sending the response body.
+    pub duration: Option<Duration>,
+
+    /// Number of request body bytes read by the application.
+    pub bytes_in: Option<u64>,
+
+    /// Number of response body bytes sent.
+    pub bytes_out: Option<u64>,
+
+    /// Peer socket address.
+    pub remote_addr: Option<SocketAddr>,
+}
+
+impl Serialize for AccessLogRecord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        if let Some(method) = &self.method {
+            map.serialize_entry("method", method.as_str())?;
+        }
+
+        if let Some(path) = &self.path {
+            map.serialize_entry("path", path)?;
+        }
+
+        if let Some(status) = self.status {
+            map.serialize_entry("status", &status.as_u16())?;
+        }
+
+        if let Some(duration) = self.duration {
+            map.serialize_entry("duration_ms", &(duration.as_secs_f64() * 1000.0))?;
+        }
+
+        if let Some(bytes_in) = self.bytes_in {
+            map.serialize_entry("bytes_in", &bytes_in)?;
+        }
+
+        if let Some(bytes_out) = self.bytes_out {
+            map.serialize_entry("bytes_out", &bytes_out)?;
+        }
+
+        if let Some(remote_addr) = self.remote_addr {
+            map.serialize_entry("remote_addr", &remote_addr.to_string())?;
+        }
+
+        map.end()
+    }
+}
+
+/// Middleware for emitting a structured record for each request.
+///
+/// Where [`Logger`](super::Logger) renders a format string, `AccessLog` produces an
+/// [`AccessLogRecord`] holding the selected fields and passes it to a handler, so records can be
+/// serialized or routed to any logging system.
+///
+/// A record is emitted once the response body has been sent, or dropped, so that the duration and
+/// number of bytes sent cover the whole response.
+///
+/// The [`default`](Self::default) middleware includes all fields and logs each record as a JSON
+/// object using the `log` crate, at the INFO level and with the `actix_web::middleware::access_log`
+/// target.
+///
+/// # Examples
+/// Routing records to [`tracing`](https://docs.rs/tracing):
+/// ```
+/// use actix_web::{
+///     middleware::{AccessLog, AccessLogField},
+///     App,
+/// };
+///
+/// let access_log = AccessLog::new(|record| {
+///     tracing::info!(
+///         method = ?record.method,
+///         path = record.path.as_deref(),
+///         status = record.status.map(|status| status.as_u16()),
+///         "request served",
+///     );
+/// })
+/// .fields([AccessLogField::Method, AccessLogField::Path, AccessLogField::Status]);
+///
+/// let app = App::new().wrap(access_log);
+/// ```
+pub struct AccessLog(Rc<Inner>);
+
+struct Inner {
+    fields: HashSet<AccessLogField>,
+    handler: Box<dyn Fn(&AccessLogRecord)>,
+}
+
+impl AccessLog {
+    /// Constructs new `AccessLog` middleware that passes each record to `handler`.
+    ///
+    /// All fields are included until a selection is made using [`fields`](Self::fields).
+    pub fn new(handler: impl Fn(&AccessLogRecord) + 'static) -> Self {
+        Self(Rc::new(Inner {
+            fields: HashSet::from(AccessLogField::ALL),
+            handler: Box::new(handler),
+        }))
+    }
+
+    /// Sets the fields to include in each record, replacing the current selection.
+    pub fn fields(mut self, fields: impl IntoIterator<Item = AccessLogField>) -> Self {
+        let inner = Rc::get_mut(&mut self.0).unwrap();
+        inner.fields = fields.into_iter().collect();
+        self
+    }
+}
+
+impl Default for AccessLog {
+    /// Constructs new `AccessLog` middleware that logs each record as a JSON object.
+    fn default() -> Self {
+        Self::new(|record| match serde_json::to_string(record) {
+            Ok(json) => log::info!("{json}"),
+            Err(err) => log::warn!("Failed to serialize access log record: {err}"),
+        })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for AccessLog
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    type Response = ServiceResponse<AccessLogBody<B>>;
+    type Error = Error;
+    type Transform = AccessLogMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AccessLogMiddleware {
+            service,
+            inner: Rc::clone(&self.0),
+        }))
+    }
+}
+
+/// Access log middleware service.
+pub struct AccessLogMiddleware<S> {
+    inner: Rc<Inner>,
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for AccessLogMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    type Response = ServiceResponse<AccessLogBody<B>>;
+    type Error = Error;
+    type Future = AccessLogResponse<S, B>;
+
+    actix_service::forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let fields = &self.inner.fields;
+
+        let mut record = AccessLogRecord::default();
+
+        if fields.contains(&AccessLogField::Method) {
+            record.method = Some(req.method().clone());
+        }
+
+        if fields.contains(&AccessLogField::Path) {
+            record.path = Some(req.path().to_owned());
+        }
+
+        if fields.contains(&AccessLogField::RemoteAddr) {
+            record.remote_addr = req.peer_addr();
+        }
+
+        let bytes_in = if fields.contains(&AccessLogField::BytesIn) {
+            let counter = Rc::new(Cell::new(0));
+
+            let payload = CountedPayload {
+                payload: req.take_payload(),
+                counter: Rc::clone(&counter),
+            };
+            req.set_payload(Payload::Stream {
+                payload: Box::pin(payload),
+            });
+
+            Some(counter)
+        } else {
+            None
+        };
+
+        AccessLogResponse {
+            fut: self.service.call(req),
+            inner: Some(Rc::clone(&self.inner)),
+            record: Some(record),
+            bytes_in,
+            start: Instant::now(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+pin_project! {
+    pub struct AccessLogResponse<S, B>
+    where
+        S: Service<ServiceRequest>,
+    {
+        #[pin]
+        fut: S::Future,
+        inner: Option<Rc<Inner>>,
+        record: Option<AccessLogRecord>,
+        bytes_in: Option<Rc<Cell<u64>>>,
+        start: Instant,
+        _phantom: PhantomData<B>,
+    }
+}
+
+impl<S, B> Future for AccessLogResponse<S, B>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    type Output = Result<ServiceResponse<AccessLogBody<B>>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let res = ready!(this.fut.poll(cx))?;
+
+        let inner = this.inner.take().unwrap();
+        let mut record = this.record.take().unwrap();
+
+        if inner.fields.contains(&AccessLogField::Status) {
+            record.status = Some(res.status());
+        }
+
+        let bytes_in = this.bytes_in.take();
+        let start = *this.start;
+
+        Poll::Ready(Ok(res.map_body(move |_, body| AccessLogBody {
+            body,
+            inner,
+            record,
+            bytes_in,
+            bytes_out: 0,
+            start,
+        })))
+    }
+}
+
+pin_project! {
+    /// Response body wrapper that emits an access log record when dropped.
+    pub struct AccessLogBody<B> {
+        #[pin]
+        body: B,
+        inner: Rc<Inner>,
+        record: AccessLogRecord,
+        bytes_in: Option<Rc<Cell<u64>>>,
+        bytes_out: u64,
+        start: Instant,
+    }
+
+    impl<B> PinnedDrop for AccessLogBody<B> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            let fields = &this.inner.fields;
+
+            if fields.contains(&AccessLogField::Duration) {
+                this.record.duration = Some(this.start.elapsed());
+            }
+
+            if let Some(bytes_in) = this.bytes_in {
+                this.record.bytes_in = Some(bytes_in.get());
+            }
+
+            if fields.contains(&AccessLogField::BytesOut) {
+                this.record.bytes_out = Some(*this.bytes_out);
+            }
+
+            (this.inner.handler)(this.record);
+        }
+    }
+}
+
+impl<B: MessageBody> MessageBody for AccessLogBody<B> {
+    type Error = B::Error;
+
+    #[inline]
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.project();
+
+        let chunk = ready!(this.body.poll_next(cx));
+
+        if let Some(Ok(ref chunk)) = chunk {
+            *this.bytes_out += chunk.len() as u64;
+        }
+
+        Poll::Ready(chunk)
+    }
+}
+
+pin_project! {
+    /// Request payload wrapper that counts the bytes read from it.
+    struct CountedPayload {
+        #[pin]
+        payload: Payload,
+        counter: Rc<Cell<u64>>,
+    }
+}
+
+impl Stream for CountedPayload {
+    type Item = Result<Bytes, PayloadError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        let chunk = ready!(this.payload.poll_next(cx));
+
+        if let Some(Ok(ref chunk)) = chunk {
+            this.counter.set(this.counter.get() + chunk.len() as u64);
+        }
+
+        Poll::Ready(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use actix_service::IntoService;
+    use futures_util::StreamExt as _;
+
+    use super::*;
+    use crate::{
+        body,
+        test::{self, TestRequest},
+        HttpResponse,
+    };
+
+    async fn read_payload(mut req: ServiceRequest) -> Result<ServiceResponse, Error> {
+        let mut payload = req.take_payload();
+        while let Some(chunk) = payload.next().await {
+            chunk?;
+        }
+
+        Ok(req.into_response(HttpResponse::Created().body("hello world")))
+    }
+
+    #[actix_rt::test]
+    async fn record_fields() {
+        let records = Rc::new(RefCell::new(Vec::new()));
+
+        let access_log = AccessLog::new({
+            let records = Rc::clone(&records);
+            move |record| records.borrow_mut().push(record.clone())
+        });
+        let srv = access_log
+            .new_transform(read_payload.into_service())
+            .await
+            .unwrap();
+
+        let req = TestRequest::post()
+            .uri("/upload?name=test")
+            .peer_addr("127.0.0.1:8080".parse().unwrap())
+            .set_payload("12345")
+            .to_srv_request();
+        let res = test::call_service(&srv, req).await;
+
+        // record is emitted once the body has been dropped
+        assert!(records.borrow().is_empty());
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "hello world");
+
+        let record = records.borrow_mut().pop().unwrap();
+        assert_eq!(record.method, Some(Method::POST));
+        assert_eq!(record.path.as_deref(), Some("/upload"));
+        assert_eq!(record.status, Some(StatusCode::CREATED));
+        assert!(record.duration.is_some());
+        assert_eq!(record.bytes_in, Some(5));
+        assert_eq!(record.bytes_out, Some(11));
+        assert_eq!(record.remote_addr, Some("127.0.0.1:8080".parse().unwrap()));
+    }
+
+    #[actix_rt::test]
+    async fn selected_fields() {
+        let records = Rc::new(RefCell::new(Vec::new()));
+
+        let access_log = AccessLog::new({
+            let records = Rc::clone(&records);
+            move |record| {
+                records
+                    .borrow_mut()
+                    .push(serde_json::to_value(record).unwrap())
+            }
+        })
+        .fields([AccessLogField::Status, AccessLogField::BytesOut]);
+        let srv = access_log
+            .new_transform(read_payload.into_service())
+            .await
+            .unwrap();
+
+        let req = TestRequest::default().to_srv_request();
+        let res = test::call_service(&srv, req).await;
+        drop(res);
+
+        assert_eq!(
+            records.borrow().as_slice(),
+            [serde_json::json!({ "status": 201, "bytes_out": 0 })],
+        );
+    }
+}
@@ -7,6 +7,7 @@ use std::{
     task::{Context, Poll},
 };
 
+pub use actix_http::encoding::ContentEncoderConfig;
 use actix_http::encoding::Encoder;
 use actix_service::{Service, Transform};
 use actix_utils::future::{ok, Either, Ready};
@@ -46,6 +47,21 @@ use crate::{
 ///
 /// A (naïve) example serving an pre-compressed Gzip file is included below.
 ///
+/// # Compression Levels
+/// The compression level used for each algorithm can be tuned by registering a
+/// [`ContentEncoderConfig`] as app data. Since app data is resolved from the most specific scope,
+/// different routes can use different levels. A config inserted into a response's extensions
+/// takes precedence over app data, allowing the level to be selected per-response.
+///
+/// The same config can also enable sending `Content-Encoding: identity` on responses that are
+/// left uncompressed; see [`ContentEncoderConfig::identity_header`]. For long-lived streaming
+/// responses, [`ContentEncoderConfig::flush_per_chunk`] makes each chunk reach the client promptly.
+///
+/// # Disabling Compression
+/// Registering [`DisableCompress`] as app data, or inserting it into a response's extensions, makes
+/// `Compress` pass the response through untouched. Request bodies are not affected; extractors still
+/// decode them according to their `Content-Encoding` header.
+///
 /// # Examples
 /// To enable automatic payload compression just include `Compress` as a top-level middleware:
 /// ```
@@ -71,11 +87,50 @@ use crate::{
 ///     .default_service(web::to(index_handler));
 /// ```
 ///
+/// Using a higher Gzip compression level for static assets than for the rest of the app:
+/// ```
+/// use actix_web::{middleware, web, App, HttpResponse};
+/// use actix_web::middleware::ContentEncoderConfig;
+///
+/// let app = App::new()
+///     .wrap(middleware::Compress::default())
+///     .app_data(ContentEncoderConfig::new().gzip_level(4))
+///     .service(
+///         web::resource("/assets/app.js")
+///             .app_data(ContentEncoderConfig::new().gzip_level(9))
+///             .to(|| async { HttpResponse::Ok().body("console.log('hello world')") }),
+///     );
+/// ```
+///
+/// Disabling compression for a resource that serves already-compressed files:
+/// ```
+/// use actix_web::{middleware, web, App, HttpResponse};
+///
+/// let app = App::new()
+///     .wrap(middleware::Compress::default())
+///     .service(
+///         web::resource("/raw")
+///             .app_data(middleware::DisableCompress)
+///             .to(|| async { HttpResponse::Ok().body("already compressed") }),
+///     );
+/// ```
+///
 /// [feature flags]: ../index.html#crate-features
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
 pub struct Compress;
 
+/// Marker that disables response compression by the [`Compress`] middleware.
+///
+/// Can be registered as app data for a scope or resource, or inserted into a response's
+/// extensions. Responses are then sent without a `Content-Encoding` (or with `identity`, if
+/// [`ContentEncoderConfig::identity_header`] is set).
+///
+/// Only the `Compress` middleware checks this marker; it does not change how request bodies are
+/// decompressed by extractors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DisableCompress;
+
 impl<S, B> Transform<S, ServiceRequest> for Compress
 where
     B: MessageBody,
@@ -183,7 +238,26 @@ where
                     }
                 };
 
+                let disabled = resp.response().extensions().contains::<DisableCompress>()
+                    || resp.request().app_data::<DisableCompress>().is_some();
+
+                let config = resp
+                    .response()
+                    .extensions()
+                    .get::<ContentEncoderConfig>()
+                    .or_else(|| resp.request().app_data::<ContentEncoderConfig>())
+                    .cloned()
+                    .unwrap_or_default();
+
                 Poll::Ready(Ok(resp.map_body(move |head, body| {
+                    if disabled {
+                        return EitherBody::left(Encoder::response(
+                            ContentEncoding::Identity,
+                            head,
+                            body,
+                        ));
+                    }
+
                     let content_type = head.headers.get(header::CONTENT_TYPE);
 
                     fn default_compress_predicate(content_type: Option<&HeaderValue>) -> bool {
@@ -205,7 +279,7 @@ where
                         ContentEncoding::Identity
                     };
 
-                    EitherBody::left(Encoder::response(enc, head, body))
+                    EitherBody::left(Encoder::response_with_config(enc, head, body, &config))
                 })))
             }
 
@@ -234,6 +308,11 @@ static SUPPORTED_ENCODINGS_STRING: Lazy<String> = Lazy::new(|| {
         encoding.push("zstd");
     }
 
+    #[cfg(feature = "compress-lz4")]
+    {
+        encoding.push("lz4");
+    }
+
     assert!(
         !encoding.is_empty(),
         "encoding can not be empty unless __compress feature has been explicitly enabled by itself"
@@ -260,6 +339,10 @@ static SUPPORTED_ENCODINGS: &[Encoding] = &[
     {
         Encoding::zstd()
     },
+    #[cfg(feature = "compress-lz4")]
+    {
+        Encoding::lz4()
+    },
 ];
 
 // move cfg(feature) to prevents_double_compressing if more tests are added
@@ -389,6 +472,31 @@ mod tests {
         assert!(vary_headers.contains(&HeaderValue::from_static("accept-encoding")));
     }
 
+    #[actix_rt::test]
+    async fn does_not_duplicate_vary_header() {
+        let app = test::init_service({
+            App::new()
+                .wrap(Compress::default())
+                .default_service(web::to(move || {
+                    HttpResponse::Ok()
+                        .insert_header((header::VARY, "Origin, Accept-Encoding"))
+                        .body(TEXT_DATA)
+                }))
+        })
+        .await;
+
+        let req = test::TestRequest::default()
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+
+        let mut vary_headers = res.headers().get_all(header::VARY);
+        assert_eq!(vary_headers.next().unwrap(), "Origin, Accept-Encoding");
+        assert!(vary_headers.next().is_none());
+    }
+
     fn configure_predicate_test(cfg: &mut web::ServiceConfig) {
         cfg.route(
             "/html",
@@ -447,6 +555,91 @@ mod tests {
         assert!(!res.headers().contains_key(header::CONTENT_ENCODING));
         assert!(test::read_body(res).await.is_empty());
     }
+
+    #[actix_rt::test]
+    async fn disable_compress() {
+        let app = test::init_service({
+            App::new()
+                .wrap(Compress::default())
+                .app_data(ContentEncoderConfig::new().identity_header(true))
+                .service(
+                    web::resource("/raw")
+                        .app_data(DisableCompress)
+                        .to(|| HttpResponse::Ok().body(TEXT_DATA)),
+                )
+                .route(
+                    "/response",
+                    web::get().to(|| {
+                        let mut res = HttpResponse::Ok().body(TEXT_DATA);
+                        res.extensions_mut().insert(DisableCompress);
+                        res
+                    }),
+                )
+                .default_service(web::to(|| HttpResponse::Ok().body(TEXT_DATA)))
+        })
+        .await;
+
+        let req = test::TestRequest::default()
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+
+        for uri in ["/raw", "/response"] {
+            let req = test::TestRequest::with_uri(uri)
+                .insert_header((header::ACCEPT_ENCODING, "gzip"))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert!(!res.headers().contains_key(header::CONTENT_ENCODING));
+            assert!(!res.headers().contains_key(header::VARY));
+            assert_eq!(test::read_body(res).await, TEXT_DATA.as_bytes());
+        }
+    }
+
+    #[actix_rt::test]
+    async fn uses_configured_compression_level() {
+        let app = test::init_service({
+            App::new()
+                .wrap(Compress::default())
+                .app_data(ContentEncoderConfig::new().gzip_level(9))
+                .service(
+                    web::resource("/stored")
+                        .app_data(ContentEncoderConfig::new().gzip_level(0))
+                        .to(|| HttpResponse::Ok().body(TEXT_DATA)),
+                )
+                .route(
+                    "/response",
+                    web::get().to(|| {
+                        let mut res = HttpResponse::Ok().body(TEXT_DATA);
+                        res.extensions_mut()
+                            .insert(ContentEncoderConfig::new().gzip_level(0));
+                        res
+                    }),
+                )
+                .default_service(web::to(|| HttpResponse::Ok().body(TEXT_DATA)))
+        })
+        .await;
+
+        let req = test::TestRequest::default()
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+        let compressed = test::read_body(res).await;
+        assert_eq!(gzip_decode(&compressed), TEXT_DATA.as_bytes());
+        assert!(compressed.len() < TEXT_DATA.len());
+
+        for uri in ["/stored", "/response"] {
+            let req = test::TestRequest::with_uri(uri)
+                .insert_header((header::ACCEPT_ENCODING, "gzip"))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+            let stored = test::read_body(res).await;
+            assert_eq!(gzip_decode(&stored), TEXT_DATA.as_bytes());
+            assert!(stored.len() > TEXT_DATA.len());
+        }
+    }
 }
 
 #[cfg(feature = "compress-brotli")]
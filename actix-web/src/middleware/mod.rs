@@ -242,6 +242,7 @@
 //! [`new_transform`]: crate::dev::Transform::new_transform()
 //! [`from_fn`]: crate
 
+mod access_log;
 mod compat;
 #[cfg(feature = "__compress")]
 mod compress;
@@ -254,8 +255,9 @@ mod logger;
 mod normalize;
 
 #[cfg(feature = "__compress")]
-pub use self::compress::Compress;
+pub use self::compress::{Compress, ContentEncoderConfig, DisableCompress};
 pub use self::{
+    access_log::{AccessLog, AccessLogField, AccessLogRecord},
     compat::Compat,
     condition::Condition,
     default_headers::DefaultHeaders,
@@ -283,6 +285,7 @@ mod tests {
                 Ok(ErrorHandlerResponse::Response(res.map_into_left_body()))
             }))
             .wrap(Logger::default())
+            .wrap(AccessLog::default())
             .wrap(NormalizePath::new(TrailingSlash::Trim));
 
         let _ = App::new()
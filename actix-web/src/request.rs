@@ -16,8 +16,11 @@ use crate::{
     app_service::AppInitServiceState,
     config::AppConfig,
     dev::{Extensions, Payload},
-    error::UrlGenerationError,
-    http::{header::HeaderMap, Method, Uri, Version},
+    error::{ParseError, UrlGenerationError},
+    http::{
+        header::{Header, HeaderMap},
+        Method, Uri, Version,
+    },
     info::ConnectionInfo,
     rmap::ResourceMap,
     Error, FromRequest, HttpMessage,
@@ -138,6 +141,28 @@ impl HttpRequest {
         &self.head().headers
     }
 
+    /// Parses the typed header `H` from the request's headers.
+    ///
+    /// Returns `None` if the header is not present. Otherwise, returns the result of parsing it,
+    /// so that malformed headers can be told apart from missing ones.
+    ///
+    /// ```
+    /// use actix_web::{http::header::ContentType, test::TestRequest};
+    ///
+    /// let req = TestRequest::default()
+    ///     .insert_header(ContentType::json())
+    ///     .to_http_request();
+    ///
+    /// assert_eq!(req.typed_header::<ContentType>().unwrap().unwrap(), ContentType::json());
+    /// ```
+    pub fn typed_header<H: Header>(&self) -> Option<Result<H, ParseError>> {
+        if self.headers().contains_key(H::name()) {
+            Some(H::parse(self))
+        } else {
+            None
+        }
+    }
+
     /// The target path of this request.
     #[inline]
     pub fn path(&self) -> &str {
@@ -558,6 +583,26 @@ mod tests {
         web, App, HttpResponse,
     };
 
+    #[test]
+    fn test_typed_header() {
+        use crate::http::header::{ContentLength, ContentType};
+
+        let req = TestRequest::default()
+            .insert_header(ContentType::plaintext())
+            .insert_header((header::CONTENT_LENGTH, "abc"))
+            .to_http_request();
+
+        assert_eq!(
+            req.typed_header::<ContentType>().unwrap().unwrap(),
+            ContentType::plaintext()
+        );
+        assert!(matches!(
+            req.typed_header::<ContentLength>(),
+            Some(Err(ParseError::Header))
+        ));
+        assert!(req.typed_header::<header::Date>().is_none());
+    }
+
     #[test]
     fn test_debug() {
         let req = TestRequest::default()
@@ -3,6 +3,7 @@ use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use actix_http::{error::HttpError, Response, ResponseHead};
@@ -11,12 +12,12 @@ use futures_core::Stream;
 use serde::Serialize;
 
 use crate::{
-    body::{BodyStream, BoxBody, MessageBody},
+    body::{BodyStream, BoxBody, MessageBody, SizedStream},
     dev::Extensions,
     error::{Error, JsonPayloadError},
     http::{
         header::{self, HeaderName, TryIntoHeaderPair, TryIntoHeaderValue},
-        ConnectionType, StatusCode,
+        ConnectionType, KeepAliveTimeout, StatusCode, Trailers,
     },
     BoxError, HttpRequest, HttpResponse, Responder,
 };
@@ -93,6 +94,33 @@ impl HttpResponseBuilder {
         self
     }
 
+    /// Append a header to a comma-separated list, skipping items that are already listed.
+    ///
+    /// See [`HeaderMap::insert_or_append_unique()`] for how existing values are compared.
+    ///
+    /// ```
+    /// use actix_web::{http::header, HttpResponse};
+    ///
+    /// let res = HttpResponse::Ok()
+    ///     .insert_or_append_unique((header::VARY, "Origin"))
+    ///     .insert_or_append_unique((header::VARY, "origin"))
+    ///     .finish();
+    ///
+    /// assert_eq!(res.headers().get_all(header::VARY).count(), 1);
+    /// ```
+    ///
+    /// [`HeaderMap::insert_or_append_unique()`]: crate::http::header::HeaderMap::insert_or_append_unique
+    pub fn insert_or_append_unique(&mut self, header: impl TryIntoHeaderPair) -> &mut Self {
+        if let Some(parts) = self.inner() {
+            match header.try_into_pair() {
+                Ok((key, value)) => parts.headers.insert_or_append_unique(key, value),
+                Err(err) => self.error = Some(err.into()),
+            };
+        }
+
+        self
+    }
+
     /// Replaced with [`Self::insert_header()`].
     #[doc(hidden)]
     #[deprecated(
@@ -161,6 +189,85 @@ impl HttpResponseBuilder {
         self
     }
 
+    /// Overrides the server's keep-alive timeout for the connection after this response is sent.
+    ///
+    /// The timeout replaces the server's configured keep-alive timeout, so it can also be used to
+    /// keep long-polling connections open for longer. It is capped at
+    /// [`HttpServer::max_keep_alive_timeout()`] and is ignored if keep-alive is disabled. Only
+    /// applies to HTTP/1.x connections. See [`KeepAliveTimeout`].
+    ///
+    /// [`HttpServer::max_keep_alive_timeout()`]: crate::HttpServer::max_keep_alive_timeout
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use actix_web::HttpResponse;
+    ///
+    /// let res = HttpResponse::Ok()
+    ///     .keep_alive_timeout(Duration::from_secs(1))
+    ///     .finish();
+    /// ```
+    #[inline]
+    pub fn keep_alive_timeout(&mut self, timeout: Duration) -> &mut Self {
+        if let Some(res) = self.res.as_mut() {
+            res.extensions_mut().insert(KeepAliveTimeout(timeout));
+        }
+        self
+    }
+
+    /// Sends trailer fields after the response body.
+    ///
+    /// The declared trailer names are advertised in the `Trailer` header and the fields set on
+    /// `trailers` by the time the body stream ends are sent after the last chunk. Only applies to
+    /// chunked HTTP/1.1 responses. See [`Trailers`].
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{
+    ///     http::{
+    ///         header::{HeaderName, HeaderValue},
+    ///         Trailers,
+    ///     },
+    ///     web::Bytes,
+    ///     HttpResponse,
+    /// };
+    /// use futures_util::{stream, StreamExt as _};
+    ///
+    /// let checksum = HeaderName::from_static("x-checksum");
+    /// let trailers = Trailers::new([checksum.clone()]);
+    ///
+    /// let body = stream::iter(["hello", "world"]).map({
+    ///     let trailers = trailers.clone();
+    ///     move |chunk| {
+    ///         // a real checksum would be updated with each chunk
+    ///         trailers.insert(checksum.clone(), HeaderValue::from_static("1234"));
+    ///         Ok::<_, std::io::Error>(Bytes::from(chunk))
+    ///     }
+    /// });
+    ///
+    /// let res = HttpResponse::Ok().trailers(&trailers).streaming(body);
+    /// assert_eq!(res.headers().get("trailer").unwrap(), "x-checksum");
+    /// ```
+    pub fn trailers(&mut self, trailers: &Trailers) -> &mut Self {
+        if !trailers.names().is_empty() {
+            let names = trailers
+                .names()
+                .iter()
+                .map(HeaderName::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            self.insert_header((header::TRAILER, names));
+        }
+
+        if let Some(res) = self.res.as_mut() {
+            res.extensions_mut().insert(trailers.clone());
+        }
+
+        self
+    }
+
     /// Set connection type to Upgrade
     #[inline]
     pub fn upgrade<V>(&mut self, value: V) -> &mut Self
@@ -327,28 +434,59 @@ impl HttpResponseBuilder {
         self.body(BodyStream::new(stream))
     }
 
+    /// Set a streaming body of known size and build the `HttpResponse`.
+    ///
+    /// The `Content-Length` header is set to `size` instead of using chunked transfer encoding. The
+    /// stream must yield exactly `size` bytes.
+    ///
+    /// `HttpResponseBuilder` can not be used after this call.
+    #[inline]
+    pub fn streaming_sized<S, E>(&mut self, size: u64, stream: S) -> HttpResponse
+    where
+        S: Stream<Item = Result<Bytes, E>> + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        self.body(SizedStream::new(size, stream))
+    }
+
     /// Set a JSON body and build the `HttpResponse`.
     ///
     /// `HttpResponseBuilder` can not be used after this call.
     pub fn json(&mut self, value: impl Serialize) -> HttpResponse {
         match serde_json::to_string(&value) {
-            Ok(body) => {
-                let contains = if let Some(parts) = self.inner() {
-                    parts.headers.contains_key(header::CONTENT_TYPE)
-                } else {
-                    true
-                };
-
-                if !contains {
-                    self.insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON));
-                }
+            Ok(body) => self.json_body(body),
+            Err(err) => HttpResponse::from_error(JsonPayloadError::Serialize(err)),
+        }
+    }
 
-                self.body(body)
-            }
+    /// Set a pretty-printed JSON body and build the `HttpResponse`.
+    ///
+    /// Useful for human-facing endpoints. Use [`json`](Self::json) for compact output.
+    ///
+    /// `HttpResponseBuilder` can not be used after this call.
+    pub fn json_pretty(&mut self, value: impl Serialize) -> HttpResponse {
+        let mut body = Vec::new();
+
+        match serde_json::to_writer_pretty(&mut body, &value) {
+            Ok(()) => self.json_body(body),
             Err(err) => HttpResponse::from_error(JsonPayloadError::Serialize(err)),
         }
     }
 
+    fn json_body(&mut self, body: impl MessageBody + 'static) -> HttpResponse {
+        let contains = if let Some(parts) = self.inner() {
+            parts.headers.contains_key(header::CONTENT_TYPE)
+        } else {
+            true
+        };
+
+        if !contains {
+            self.insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON));
+        }
+
+        self.body(body)
+    }
+
     /// Set an empty body and build the `HttpResponse`.
     ///
     /// `HttpResponseBuilder` can not be used after this call.
@@ -420,6 +558,35 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[test]
+    fn test_trailers() {
+        let trailers = Trailers::new([
+            HeaderName::from_static("x-checksum"),
+            HeaderName::from_static("x-count"),
+        ]);
+        let res = HttpResponse::Ok().trailers(&trailers).finish();
+        assert_eq!(
+            res.headers().get(header::TRAILER).unwrap(),
+            "x-checksum, x-count"
+        );
+        assert!(res.extensions().get::<Trailers>().is_some());
+
+        let res = HttpResponse::Ok().trailers(&Trailers::new([])).finish();
+        assert!(!res.headers().contains_key(header::TRAILER));
+        assert!(res.extensions().get::<Trailers>().is_some());
+    }
+
+    #[test]
+    fn test_keep_alive_timeout() {
+        let res = HttpResponse::Ok()
+            .keep_alive_timeout(Duration::from_secs(1))
+            .finish();
+        assert_eq!(
+            res.extensions().get::<KeepAliveTimeout>(),
+            Some(&KeepAliveTimeout(Duration::from_secs(1)))
+        );
+    }
+
     #[test]
     fn test_upgrade() {
         let resp = HttpResponseBuilder::new(StatusCode::OK)
@@ -469,6 +636,34 @@ mod tests {
         assert_body_eq!(res, br#"["v1","v2","v3"]"#);
     }
 
+    #[actix_rt::test]
+    async fn test_streaming_sized() {
+        let stream = futures_util::stream::iter([
+            Ok::<_, Error>(Bytes::from_static(b"hello ")),
+            Ok(Bytes::from_static(b"world")),
+        ]);
+
+        let res = HttpResponse::Ok().streaming_sized(11, stream);
+        assert_eq!(res.body().size(), body::BodySize::Sized(11));
+        assert_body_eq!(res, b"hello world");
+    }
+
+    #[actix_rt::test]
+    async fn test_json_pretty() {
+        let res = HttpResponse::Ok().json_pretty(serde_json::json!({ "key": ["v1", "v2"] }));
+        let ct = res.headers().get(CONTENT_TYPE).unwrap();
+        assert_eq!(ct, HeaderValue::from_static("application/json"));
+        assert_body_eq!(res, b"{\n  \"key\": [\n    \"v1\",\n    \"v2\"\n  ]\n}");
+
+        // content type override
+        let res = HttpResponse::Ok()
+            .insert_header((CONTENT_TYPE, "text/json"))
+            .json_pretty(["v1"]);
+        let ct = res.headers().get(CONTENT_TYPE).unwrap();
+        assert_eq!(ct, HeaderValue::from_static("text/json"));
+        assert_body_eq!(res, b"[\n  \"v1\"\n]");
+    }
+
     #[actix_rt::test]
     async fn test_serde_json_in_body() {
         let resp = HttpResponse::Ok()
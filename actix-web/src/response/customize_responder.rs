@@ -10,6 +10,30 @@ use crate::{HttpRequest, HttpResponse, Responder};
 /// Allows overriding status code and headers (including cookies) for a [`Responder`].
 ///
 /// Created by calling the [`customize`](Responder::customize) method on a [`Responder`] type.
+///
+/// # Fallible Handlers
+/// To customize only the success response of a handler that returns a `Result`, customize the
+/// value inside `Ok`. Errors returned early using `?` are still turned into responses by their
+/// [`ResponseError`](crate::ResponseError) implementation, without the customizations. In contrast,
+/// customizing a whole `Result` also applies the status code and headers to error responses.
+///
+/// ```
+/// use actix_web::{error, http::StatusCode, post, web, Responder};
+///
+/// #[post("/items")]
+/// async fn create_item(name: String) -> actix_web::Result<impl Responder> {
+///     if name.is_empty() {
+///         return Err(error::ErrorBadRequest("name is required"));
+///     }
+///
+///     let id = 42; // e.g., `db.insert(&name).await?`
+///
+///     Ok(web::Json(id)
+///         .customize()
+///         .with_status(StatusCode::CREATED)
+///         .insert_header(("location", format!("/items/{id}"))))
+/// }
+/// ```
 pub struct CustomizeResponder<R> {
     inner: CustomizeResponderInner<R>,
     error: Option<HttpError>,
@@ -251,6 +275,46 @@ mod tests {
         );
     }
 
+    #[actix_rt::test]
+    async fn customize_result_success_only() {
+        #[derive(Debug, derive_more::Display)]
+        #[display("not found")]
+        struct NotFound;
+
+        impl crate::ResponseError for NotFound {
+            fn status_code(&self) -> StatusCode {
+                StatusCode::NOT_FOUND
+            }
+        }
+
+        fn handler(found: bool) -> Result<impl Responder, NotFound> {
+            let value = found.then_some("found").ok_or(NotFound)?;
+
+            Ok(value
+                .customize()
+                .with_status(StatusCode::CREATED)
+                .insert_header(("x-item", "1")))
+        }
+
+        let req = TestRequest::default().to_http_request();
+
+        let res = handler(true).respond_to(&req);
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert_eq!(res.headers().get("x-item").unwrap(), "1");
+        assert_eq!(
+            to_bytes(res.into_body()).await.unwrap(),
+            Bytes::from_static(b"found"),
+        );
+
+        let res = handler(false).respond_to(&req);
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert!(!res.headers().contains_key("x-item"));
+        assert_eq!(
+            to_bytes(res.into_body()).await.unwrap(),
+            Bytes::from_static(b"not found"),
+        );
+    }
+
     #[actix_rt::test]
     async fn tuple_responder_with_status_code() {
         let req = TestRequest::default().to_http_request();
@@ -1,21 +1,18 @@
 use std::{
     cell::{Ref, RefMut},
-    fmt,
+    error::Error as StdError,
+    fmt, mem,
 };
 
 use actix_http::{
-    body::{BoxBody, EitherBody, MessageBody},
-    header::HeaderMap,
+    body::{BodySize, BodyStream, BoxBody, EitherBody, MessageBody, MessageBodyStream},
+    header::{self, HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH},
     Extensions, Response, ResponseHead, StatusCode,
 };
+use bytes::Bytes;
+use futures_core::Stream;
 #[cfg(feature = "cookies")]
-use {
-    actix_http::{
-        error::HttpError,
-        header::{self, HeaderValue},
-    },
-    cookie::Cookie,
-};
+use {crate::error::AddCookiesError, actix_http::error::HttpError, cookie::Cookie};
 
 use crate::{error::Error, HttpRequest, HttpResponseBuilder, Responder};
 
@@ -41,6 +38,73 @@ impl HttpResponse<BoxBody> {
         HttpResponseBuilder::new(status)
     }
 
+    /// Constructs a response builder for a response derived from `res`, such as a `304 Not Modified`
+    /// or `206 Partial Content` response.
+    ///
+    /// The builder starts with the status of `res` and a copy of its `Cache-Control`,
+    /// `Content-Location`, `Date`, `ETag`, `Expires`, and `Vary` headers, which [RFC 9110 §15.4.5]
+    /// requires to be repeated in a `304 Not Modified` response. No other headers are copied.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{http::StatusCode, HttpResponse};
+    ///
+    /// let res = HttpResponse::Ok()
+    ///     .insert_header(("etag", "\"abc\""))
+    ///     .content_type("text/plain")
+    ///     .body("hello");
+    ///
+    /// let not_modified = HttpResponse::builder_from(&res)
+    ///     .status(StatusCode::NOT_MODIFIED)
+    ///     .finish();
+    ///
+    /// assert_eq!(not_modified.headers().get("etag").unwrap(), "\"abc\"");
+    /// assert!(not_modified.headers().get("content-type").is_none());
+    /// ```
+    ///
+    /// [RFC 9110 §15.4.5]: https://www.rfc-editor.org/rfc/rfc9110#section-15.4.5
+    pub fn builder_from<B>(res: &HttpResponse<B>) -> HttpResponseBuilder {
+        const COPIED_HEADERS: [HeaderName; 6] = [
+            header::CACHE_CONTROL,
+            header::CONTENT_LOCATION,
+            header::DATE,
+            header::ETAG,
+            header::EXPIRES,
+            header::VARY,
+        ];
+
+        let mut builder = HttpResponseBuilder::new(res.status());
+
+        for name in COPIED_HEADERS {
+            for value in res.headers().get_all(&name) {
+                builder.append_header((name.clone(), value.clone()));
+            }
+        }
+
+        builder
+    }
+
+    /// Returns a copy of the response body if it is held in memory and at most `max` bytes long.
+    ///
+    /// The body is left in place, so this is useful for middleware that wants to inspect a small
+    /// body, e.g. to log it, without rebuilding the response. Returns `None` for streaming bodies
+    /// and for bodies larger than `max`.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::HttpResponse;
+    ///
+    /// let res = HttpResponse::BadRequest().body("missing field `name`");
+    /// assert_eq!(res.peek_body(64).unwrap(), "missing field `name`");
+    /// assert!(res.peek_body(4).is_none());
+    /// ```
+    pub fn peek_body(&self, max: usize) -> Option<Bytes> {
+        self.body()
+            .as_bytes()
+            .filter(|bytes| bytes.len() <= max)
+            .map(Bytes::copy_from_slice)
+    }
+
     /// Create an error response.
     #[inline]
     pub fn from_error(error: impl Into<Error>) -> Self {
@@ -91,6 +155,23 @@ impl<B> HttpResponse<B> {
         self.res.status_mut()
     }
 
+    /// Returns the reason phrase of the response status line.
+    ///
+    /// This is the custom reason set on the response head, if any, or the canonical reason phrase of
+    /// the status code otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::HttpResponse;
+    ///
+    /// let res = HttpResponse::NotFound().finish();
+    /// assert_eq!(format!("{} {}", res.status().as_u16(), res.reason()), "404 Not Found");
+    /// ```
+    #[inline]
+    pub fn reason(&self) -> &str {
+        self.res.head().reason()
+    }
+
     /// Get the headers from the response
     #[inline]
     pub fn headers(&self) -> &HeaderMap {
@@ -104,6 +185,9 @@ impl<B> HttpResponse<B> {
     }
 
     /// Get an iterator for the cookies set by this response.
+    ///
+    /// `Set-Cookie` headers that fail to parse are skipped. Use
+    /// [`cookies_results`](Self::cookies_results) to also see the parse errors.
     #[cfg(feature = "cookies")]
     pub fn cookies(&self) -> CookieIter<'_> {
         CookieIter {
@@ -111,6 +195,14 @@ impl<B> HttpResponse<B> {
         }
     }
 
+    /// Get an iterator for the parse results of each `Set-Cookie` header of this response.
+    #[cfg(feature = "cookies")]
+    pub fn cookies_results(&self) -> CookieResultIter<'_> {
+        CookieResultIter {
+            iter: self.headers().get_all(header::SET_COOKIE),
+        }
+    }
+
     /// Add a cookie to this response.
     ///
     /// # Errors
@@ -122,6 +214,26 @@ impl<B> HttpResponse<B> {
             .map_err(Into::into)
     }
 
+    /// Add several cookies to this response.
+    ///
+    /// Cookies are added in order, stopping at the first one that results in a malformed
+    /// `Set-Cookie` header. Cookies before it remain added to the response.
+    ///
+    /// # Errors
+    /// Returns an error holding the index of the malformed cookie.
+    #[cfg(feature = "cookies")]
+    pub fn add_cookies<'c>(
+        &mut self,
+        cookies: impl IntoIterator<Item = &'c Cookie<'c>>,
+    ) -> Result<(), AddCookiesError> {
+        for (idx, cookie) in cookies.into_iter().enumerate() {
+            self.add_cookie(cookie)
+                .map_err(|err| AddCookiesError::new(idx, err))?;
+        }
+
+        Ok(())
+    }
+
     /// Add a "removal" cookie to the response that matches attributes of given cookie.
     ///
     /// This will cause browsers/clients to remove stored cookies with this name.
@@ -161,6 +273,37 @@ impl<B> HttpResponse<B> {
     /// [`add_removal_cookie`]: Self::add_removal_cookie
     #[cfg(feature = "cookies")]
     pub fn del_cookie(&mut self, name: &str) -> usize {
+        self.del_cookies_where(|cookie| cookie.name() == name)
+    }
+
+    /// Remove cookies with the given name from this response, if they also match the given path
+    /// and domain.
+    ///
+    /// Returns the number of cookies removed.
+    ///
+    /// A `path` or `domain` of `None` matches cookies with any value for that attribute. Otherwise,
+    /// a cookie only matches if it has that attribute set to the given value.
+    ///
+    /// Like [`del_cookie`], this method only deletes cookies that were added to this response.
+    ///
+    /// [`del_cookie`]: Self::del_cookie
+    #[cfg(feature = "cookies")]
+    pub fn del_cookie_matching(
+        &mut self,
+        name: &str,
+        path: Option<&str>,
+        domain: Option<&str>,
+    ) -> usize {
+        self.del_cookies_where(|cookie| {
+            cookie.name() == name
+                && path.map_or(true, |path| cookie.path() == Some(path))
+                && domain.map_or(true, |domain| cookie.domain() == Some(domain))
+        })
+    }
+
+    /// Removes all `Set-Cookie` headers whose parsed cookie satisfies `predicate`.
+    #[cfg(feature = "cookies")]
+    fn del_cookies_where(&mut self, predicate: impl Fn(&Cookie<'_>) -> bool) -> usize {
         let headers = self.headers_mut();
 
         let vals: Vec<HeaderValue> = headers
@@ -175,7 +318,7 @@ impl<B> HttpResponse<B> {
         for v in vals {
             if let Ok(s) = v.to_str() {
                 if let Ok(c) = Cookie::parse_encoded(s) {
-                    if c.name() == name {
+                    if predicate(&c) {
                         count += 1;
                         continue;
                     }
@@ -218,7 +361,50 @@ impl<B> HttpResponse<B> {
         self.res.body()
     }
 
+    /// Replaces this response's body with one of the same type, returning the previous body.
+    ///
+    /// Unlike [`set_body`](Self::set_body), this works through a mutable reference, which is useful
+    /// in middleware that holds onto the response while wrapping or rewriting its body. Headers,
+    /// including `Content-Length`, are left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{http::StatusCode, HttpResponse};
+    ///
+    /// let mut res = HttpResponse::with_body(StatusCode::OK, "hello");
+    /// assert_eq!(res.replace_body("world"), "hello");
+    /// assert_eq!(*res.body(), "world");
+    /// ```
+    #[inline]
+    pub fn replace_body(&mut self, body: B) -> B {
+        mem::replace(self.res.body_mut(), body)
+    }
+
+    /// Takes this response's body, leaving the default body of the same type in its place.
+    ///
+    /// See [`replace_body`](Self::replace_body).
+    #[inline]
+    pub fn take_body(&mut self) -> B
+    where
+        B: Default,
+    {
+        mem::take(self.res.body_mut())
+    }
+
+    /// Returns the size of this response's body.
+    ///
+    /// For an [`EitherBody`], this is the size of whichever body is active.
+    #[inline]
+    pub fn body_size(&self) -> BodySize
+    where
+        B: MessageBody,
+    {
+        self.res.body().size()
+    }
+
     /// Sets new body.
+    ///
+    /// Any error carried by this response is kept.
     pub fn set_body<B2>(self, body: B2) -> HttpResponse<B2> {
         HttpResponse {
             res: self.res.set_body(body),
@@ -226,11 +412,35 @@ impl<B> HttpResponse<B> {
         }
     }
 
+    /// Sets new body and updates the `Content-Length` header to match it.
+    ///
+    /// If the new body has a known size, the `Content-Length` header is set to that size.
+    /// Otherwise, any existing `Content-Length` header is removed. This is useful when rewriting
+    /// bodies in middleware, where a header set for the previous body would no longer be accurate.
+    ///
+    /// Any error carried by this response is kept.
+    pub fn set_body_sized<B2: MessageBody>(mut self, body: B2) -> HttpResponse<B2> {
+        match body.size() {
+            BodySize::Sized(len) => {
+                self.headers_mut()
+                    .insert(CONTENT_LENGTH, HeaderValue::from(len));
+            }
+            BodySize::None | BodySize::Stream => {
+                self.headers_mut().remove(CONTENT_LENGTH);
+            }
+        }
+
+        self.set_body(body)
+    }
+
     /// Returns split head and body.
     ///
     /// # Implementation Notes
     /// Due to internal performance optimizations, the first element of the returned tuple is an
     /// `HttpResponse` as well but only contains the head of the response this was called on.
+    ///
+    /// Any error carried by this response is kept in the head half, so it is not lost when the
+    /// response is reassembled using [`set_body`](Self::set_body).
     pub fn into_parts(self) -> (HttpResponse<()>, B) {
         let (head, body) = self.res.into_parts();
 
@@ -244,6 +454,8 @@ impl<B> HttpResponse<B> {
     }
 
     /// Drops body and returns new response.
+    ///
+    /// Any error carried by this response is kept.
     pub fn drop_body(self) -> HttpResponse<()> {
         HttpResponse {
             res: self.res.drop_body(),
@@ -289,6 +501,41 @@ impl<B> HttpResponse<B> {
         self.map_body(|_, body| body.boxed())
     }
 
+    /// Map the current body to a new streaming body using a closure, returning a new response.
+    ///
+    /// Closure receives the response head and the current body adapted into a [`Stream`] of
+    /// chunks. The returned stream becomes the new body, allowing middleware to rewrite a body
+    /// using stream combinators without first collecting it into memory.
+    ///
+    /// The new body is pull-based: the current body is only polled when the returned stream
+    /// polls it, which in turn only happens when the connection is ready to write more data.
+    /// Backpressure is therefore preserved unless the closure buffers chunks itself.
+    ///
+    /// Since the size of the new body is unknown, it will be sent using chunked encoding (on
+    /// HTTP/1.1). Any `Content-Length` header set on the response head should be removed by the
+    /// closure if the transform changes the body length.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{web::Bytes, HttpResponse};
+    /// use futures_util::StreamExt as _;
+    ///
+    /// let res = HttpResponse::Ok()
+    ///     .body("hello world")
+    ///     .map_body_stream(|_head, stream| {
+    ///         stream.map(|chunk| chunk.map(|bytes| Bytes::from(bytes.to_ascii_uppercase())))
+    ///     });
+    /// ```
+    pub fn map_body_stream<F, S, E>(self, f: F) -> HttpResponse<BodyStream<S>>
+    where
+        B: MessageBody,
+        F: FnOnce(&mut ResponseHead, MessageBodyStream<B>) -> S,
+        S: Stream<Item = Result<Bytes, E>>,
+        E: Into<Box<dyn StdError>> + 'static,
+    {
+        self.map_body(|head, body| BodyStream::new(f(head, MessageBodyStream::new(body))))
+    }
+
     /// Returns the response body, dropping all other parts.
     pub fn into_body(self) -> B {
         self.res.into_body()
@@ -394,6 +641,31 @@ impl<'a> Iterator for CookieIter<'a> {
     }
 }
 
+/// Iterator over the parse results of a response's `Set-Cookie` headers.
+///
+/// Yields a parsed cookie or a parse error for every `Set-Cookie` header, so that invalid headers
+/// are not silently skipped. Returned by [`HttpResponse::cookies_results()`].
+#[cfg(feature = "cookies")]
+pub struct CookieResultIter<'a> {
+    iter: std::slice::Iter<'a, HeaderValue>,
+}
+
+#[cfg(feature = "cookies")]
+impl<'a> Iterator for CookieResultIter<'a> {
+    type Item = Result<Cookie<'a>, cookie::ParseError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+
+        Some(
+            std::str::from_utf8(v.as_bytes())
+                .map_err(cookie::ParseError::Utf8Error)
+                .and_then(Cookie::parse_encoded),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use static_assertions::assert_impl_all;
@@ -406,6 +678,83 @@ mod tests {
     assert_impl_all!(HttpResponse<&'static str>: Responder);
     assert_impl_all!(HttpResponse<crate::body::None>: Responder);
 
+    #[test]
+    fn reason() {
+        assert_eq!(HttpResponse::Ok().finish().reason(), "OK");
+        assert_eq!(
+            HttpResponse::TooManyRequests().finish().reason(),
+            "Too Many Requests"
+        );
+        assert_eq!(HttpResponse::Ok().reason("Fine").finish().reason(), "Fine");
+
+        let mut res = HttpResponse::Ok().finish();
+        *res.status_mut() = StatusCode::CREATED;
+        assert_eq!(res.reason(), "Created");
+    }
+
+    #[test]
+    fn replace_body() {
+        let mut res = HttpResponse::with_body(StatusCode::OK, Bytes::from_static(b"hello"));
+        res.headers_mut()
+            .insert(CONTENT_LENGTH, HeaderValue::from_static("5"));
+
+        assert_eq!(res.replace_body(Bytes::from_static(b"world")), "hello");
+        assert_eq!(res.body(), "world");
+        assert_eq!(res.take_body(), "world");
+        assert!(res.body().is_empty());
+        assert_eq!(res.headers().get(CONTENT_LENGTH).unwrap(), "5");
+    }
+
+    #[test]
+    fn peek_body() {
+        let res = HttpResponse::Ok().body("hello");
+        assert_eq!(res.peek_body(5).unwrap(), "hello");
+        assert!(res.peek_body(4).is_none());
+        assert_eq!(res.peek_body(5).unwrap(), "hello");
+
+        assert_eq!(HttpResponse::NoContent().finish().peek_body(0).unwrap(), "");
+
+        let stream = futures_util::stream::empty::<Result<Bytes, Error>>();
+        let res = HttpResponse::Ok().streaming(stream);
+        assert!(res.peek_body(1024).is_none());
+    }
+
+    #[test]
+    fn builder_from() {
+        let res = HttpResponse::Ok()
+            .insert_header((header::ETAG, "\"abc\""))
+            .append_header((header::VARY, "accept"))
+            .append_header((header::VARY, "accept-encoding"))
+            .insert_header((header::CONTENT_TYPE, "text/plain"))
+            .body("hello");
+
+        let derived = HttpResponse::builder_from(&res)
+            .status(StatusCode::NOT_MODIFIED)
+            .finish();
+
+        assert_eq!(derived.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(derived.headers().get(header::ETAG).unwrap(), "\"abc\"");
+        assert_eq!(derived.headers().get_all(header::VARY).count(), 2);
+        assert!(!derived.headers().contains_key(header::CONTENT_TYPE));
+        assert!(!derived.headers().contains_key(header::CONTENT_LENGTH));
+    }
+
+    #[test]
+    fn body_size() {
+        let res = HttpResponse::Ok().body("hello");
+        assert_eq!(res.body_size(), BodySize::Sized(5));
+
+        let res = HttpResponse::with_body(StatusCode::OK, "hello").map_into_left_body::<()>();
+        assert_eq!(res.body_size(), BodySize::Sized(5));
+
+        let res = HttpResponse::with_body(
+            StatusCode::OK,
+            BodyStream::new(futures_util::stream::empty::<Result<Bytes, Error>>()),
+        )
+        .map_into_right_body::<String>();
+        assert_eq!(res.body_size(), BodySize::Stream);
+    }
+
     #[test]
     fn test_debug() {
         let resp = HttpResponse::Ok()
@@ -415,6 +764,58 @@ mod tests {
         let dbg = format!("{:?}", resp);
         assert!(dbg.contains("HttpResponse"));
     }
+
+    #[test]
+    fn test_parts_keep_error() {
+        let res = HttpResponse::from_error(crate::error::ErrorBadRequest("bad"));
+
+        let (head, body) = res.into_parts();
+        assert_eq!(head.error().unwrap().to_string(), "bad");
+
+        let res = head.set_body(body);
+        assert_eq!(res.error().unwrap().to_string(), "bad");
+
+        let res = res.drop_body();
+        assert_eq!(res.error().unwrap().to_string(), "bad");
+    }
+
+    #[test]
+    fn test_set_body_sized() {
+        use std::convert::Infallible;
+
+        use futures_util::stream;
+
+        let res = HttpResponse::Ok()
+            .insert_header((CONTENT_LENGTH, 3))
+            .finish()
+            .set_body_sized("hello world");
+        assert_eq!(res.headers().get(CONTENT_LENGTH).unwrap(), "11");
+
+        let body = stream::iter([Ok::<_, Infallible>(Bytes::from_static(b"hello"))]);
+        let res = res.set_body_sized(BodyStream::new(body));
+        assert!(!res.headers().contains_key(CONTENT_LENGTH));
+    }
+
+    #[actix_rt::test]
+    async fn test_map_body_stream() {
+        use futures_util::{stream, StreamExt as _};
+
+        use crate::http::header::{HeaderValue, CONTENT_TYPE};
+
+        let res = HttpResponse::Ok()
+            .body("hello")
+            .map_body_stream(|head, body| {
+                head.headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+                body.chain(stream::iter([Ok(Bytes::from_static(b" world"))]))
+            });
+
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/plain");
+        assert_eq!(res.body().size(), actix_http::body::BodySize::Stream);
+
+        let body = actix_http::body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "hello world");
+    }
 }
 
 #[cfg(test)]
@@ -435,4 +836,84 @@ mod cookie_tests {
             set_cookie_hdr.to_str()
         );
     }
+
+    #[test]
+    fn add_cookies() {
+        let mut res = HttpResponse::Ok().finish();
+        let cookies = [Cookie::new("session", "1"), Cookie::new("csrf", "2")];
+        res.add_cookies(&cookies).unwrap();
+
+        let names = res
+            .cookies()
+            .map(|c| c.name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["session", "csrf"]);
+
+        let mut res = HttpResponse::Ok().finish();
+        let cookies = [
+            Cookie::new("session", "1"),
+            Cookie::new("csrf", "bad\nvalue"),
+            Cookie::new("prefs", "3"),
+        ];
+        let err = res.add_cookies(&cookies).unwrap_err();
+        assert_eq!(err.index(), 1);
+        assert!(err.to_string().starts_with("malformed cookie at index 1: "));
+
+        let names = res
+            .cookies()
+            .map(|c| c.name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["session"]);
+    }
+
+    #[test]
+    fn cookies_results() {
+        let res = HttpResponse::Ok()
+            .append_header((header::SET_COOKIE, "session=1"))
+            .append_header((header::SET_COOKIE, "=empty"))
+            .append_header((header::SET_COOKIE, "csrf=2"))
+            .finish();
+
+        assert_eq!(res.cookies().count(), 2);
+
+        let results = res.cookies_results().collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().name(), "session");
+        assert_eq!(
+            results[1].as_ref().unwrap_err(),
+            &cookie::ParseError::EmptyName
+        );
+        assert_eq!(results[2].as_ref().unwrap().name(), "csrf");
+    }
+
+    #[test]
+    fn del_cookie_matching() {
+        let mut res = HttpResponse::Ok().finish();
+        let cookies = [
+            Cookie::build(("id", "1")).path("/").build(),
+            Cookie::build(("id", "2")).path("/admin").build(),
+            Cookie::build(("id", "3"))
+                .path("/admin")
+                .domain("example.com")
+                .build(),
+            Cookie::new("other", "4"),
+        ];
+        res.add_cookies(&cookies).unwrap();
+
+        assert_eq!(res.del_cookie_matching("id", Some("/api"), None), 0);
+        assert_eq!(
+            res.del_cookie_matching("id", Some("/admin"), Some("example.com")),
+            1,
+        );
+        assert_eq!(res.del_cookie_matching("id", Some("/admin"), None), 1);
+
+        let values = res
+            .cookies()
+            .map(|c| c.value().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["1", "4"]);
+
+        assert_eq!(res.del_cookie_matching("id", None, None), 1);
+        assert_eq!(res.cookies().count(), 1);
+    }
 }
@@ -9,7 +9,10 @@ use std::{
 
 #[cfg(feature = "__tls")]
 use actix_http::TlsAcceptorConfig;
-use actix_http::{body::MessageBody, Extensions, HttpService, KeepAlive, Request, Response};
+use actix_http::{
+    body::MessageBody, h1::HeadLimits, DateHeader, Extensions, HttpService, KeepAlive, Request,
+    Response,
+};
 use actix_server::{Server, ServerBuilder};
 use actix_service::{
     map_config, IntoServiceFactory, Service, ServiceFactory, ServiceFactoryExt as _,
@@ -29,6 +32,10 @@ struct Config {
     keep_alive: KeepAlive,
     client_request_timeout: Duration,
     client_disconnect_timeout: Duration,
+    max_headers: usize,
+    max_header_size: usize,
+    max_keep_alive_timeout: Duration,
+    date_header: DateHeader,
     #[allow(dead_code)] // only dead when no TLS features are enabled
     tls_handshake_timeout: Option<Duration>,
 }
@@ -113,6 +120,10 @@ where
                 keep_alive: KeepAlive::default(),
                 client_request_timeout: Duration::from_secs(5),
                 client_disconnect_timeout: Duration::from_secs(1),
+                max_headers: HeadLimits::default().max_headers,
+                max_header_size: HeadLimits::default().max_size,
+                max_keep_alive_timeout: Duration::from_secs(300),
+                date_header: DateHeader::Auto,
                 tls_handshake_timeout: None,
             })),
             backlog: 1024,
@@ -231,6 +242,58 @@ where
         self
     }
 
+    /// Sets the maximum number of headers accepted in an HTTP/1.x request.
+    ///
+    /// Requests with more headers are rejected with a `431 Request Header Fields Too Large`
+    /// response. Raise this limit if clients, or proxies in front of the server, send many headers;
+    /// lower it to reject such requests early.
+    ///
+    /// By default, up to 96 headers are accepted.
+    pub fn max_headers(self, max_headers: usize) -> Self {
+        self.config.lock().unwrap().max_headers = max_headers;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of an HTTP/1.x request head.
+    ///
+    /// The request head consists of the request line and all headers. Requests with a larger head
+    /// are rejected with a `431 Request Header Fields Too Large` response.
+    ///
+    /// By default, the limit is 128KiB.
+    pub fn max_header_size(self, max_size: usize) -> Self {
+        self.config.lock().unwrap().max_header_size = max_size;
+        self
+    }
+
+    /// Sets the upper limit for keep-alive timeouts set per response.
+    ///
+    /// Handlers can replace the keep-alive timeout that follows their response using
+    /// [`HttpResponseBuilder::keep_alive_timeout()`], e.g., to keep long-polling connections open
+    /// for longer than the server's [`keep_alive`](Self::keep_alive) setting. Such timeouts are
+    /// capped at this duration.
+    ///
+    /// By default, the limit is 5 minutes.
+    ///
+    /// [`HttpResponseBuilder::keep_alive_timeout()`]: crate::HttpResponseBuilder::keep_alive_timeout
+    pub fn max_keep_alive_timeout(self, timeout: Duration) -> Self {
+        self.config.lock().unwrap().max_keep_alive_timeout = timeout;
+        self
+    }
+
+    /// Sets how the `Date` header is added to responses.
+    ///
+    /// By default, the current date is added to every response that does not already have a `Date`
+    /// header; a `Date` header set by a handler or middleware is always sent as-is. Use
+    /// [`DateHeader::Disabled`] to omit the header, or [`DateHeader::Fixed`] to send the same date
+    /// with every response, e.g., for deterministic snapshot tests.
+    ///
+    /// [`DateHeader::Disabled`]: crate::http::DateHeader::Disabled
+    /// [`DateHeader::Fixed`]: crate::http::DateHeader::Fixed
+    pub fn date_header(self, date_header: DateHeader) -> Self {
+        self.config.lock().unwrap().date_header = date_header;
+        self
+    }
+
     /// Sets TLS handshake timeout.
     ///
     /// Defines a timeout for TLS handshake. If the TLS handshake does not complete within this
@@ -271,6 +334,26 @@ where
     ///   Rustls v0.23.
     /// - `actix_web::rt::net::TcpStream` when no encryption is used.
     ///
+    /// # TLS Sessions
+    /// The TLS stream types give access to the connection's TLS session. For mutual TLS, this can
+    /// be used to read the certificate presented by the client during the handshake and make it
+    /// available to handlers through [`HttpRequest::conn_data()`](crate::HttpRequest::conn_data).
+    ///
+    /// ```ignore
+    /// use std::any::Any;
+    ///
+    /// use actix_tls::accept::openssl::TlsStream;
+    /// use actix_web::{dev::Extensions, rt::net::TcpStream};
+    ///
+    /// fn peer_cert(conn: &dyn Any, data: &mut Extensions) {
+    ///     if let Some(tls) = conn.downcast_ref::<TlsStream<TcpStream>>() {
+    ///         if let Some(cert) = tls.ssl().peer_certificate() {
+    ///             data.insert(cert);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
     /// See the `on_connect` example for additional details.
     pub fn on_connect<CB>(self, f: CB) -> HttpServer<F, I, S, B>
     where
@@ -531,6 +614,10 @@ where
                         .keep_alive(cfg.keep_alive)
                         .client_request_timeout(cfg.client_request_timeout)
                         .client_disconnect_timeout(cfg.client_disconnect_timeout)
+                        .max_headers(cfg.max_headers)
+                        .max_header_size(cfg.max_header_size)
+                        .max_keep_alive_timeout(cfg.max_keep_alive_timeout)
+                        .date_header(cfg.date_header)
                         .local_addr(addr);
 
                     if let Some(handler) = on_connect_fn.clone() {
@@ -575,6 +662,10 @@ where
                         .keep_alive(cfg.keep_alive)
                         .client_request_timeout(cfg.client_request_timeout)
                         .client_disconnect_timeout(cfg.client_disconnect_timeout)
+                        .max_headers(cfg.max_headers)
+                        .max_header_size(cfg.max_header_size)
+                        .max_keep_alive_timeout(cfg.max_keep_alive_timeout)
+                        .date_header(cfg.date_header)
                         .local_addr(addr);
 
                     if let Some(handler) = on_connect_fn.clone() {
@@ -650,7 +741,11 @@ where
                     let svc = HttpService::build()
                         .keep_alive(c.keep_alive)
                         .client_request_timeout(c.client_request_timeout)
-                        .client_disconnect_timeout(c.client_disconnect_timeout);
+                        .client_disconnect_timeout(c.client_disconnect_timeout)
+                        .max_headers(c.max_headers)
+                        .max_header_size(c.max_header_size)
+                        .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                        .date_header(c.date_header);
 
                     let svc = if let Some(handler) = on_connect_fn.clone() {
                         svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
@@ -701,7 +796,11 @@ where
                     let svc = HttpService::build()
                         .keep_alive(c.keep_alive)
                         .client_request_timeout(c.client_request_timeout)
-                        .client_disconnect_timeout(c.client_disconnect_timeout);
+                        .client_disconnect_timeout(c.client_disconnect_timeout)
+                        .max_headers(c.max_headers)
+                        .max_header_size(c.max_header_size)
+                        .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                        .date_header(c.date_header);
 
                     let svc = if let Some(handler) = on_connect_fn.clone() {
                         svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
@@ -767,7 +866,11 @@ where
                     let svc = HttpService::build()
                         .keep_alive(c.keep_alive)
                         .client_request_timeout(c.client_request_timeout)
-                        .client_disconnect_timeout(c.client_disconnect_timeout);
+                        .client_disconnect_timeout(c.client_disconnect_timeout)
+                        .max_headers(c.max_headers)
+                        .max_header_size(c.max_header_size)
+                        .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                        .date_header(c.date_header);
 
                     let svc = if let Some(handler) = on_connect_fn.clone() {
                         svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
@@ -833,7 +936,11 @@ where
                     let svc = HttpService::build()
                         .keep_alive(c.keep_alive)
                         .client_request_timeout(c.client_request_timeout)
-                        .client_disconnect_timeout(c.client_disconnect_timeout);
+                        .client_disconnect_timeout(c.client_disconnect_timeout)
+                        .max_headers(c.max_headers)
+                        .max_header_size(c.max_header_size)
+                        .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                        .date_header(c.date_header);
 
                     let svc = if let Some(handler) = on_connect_fn.clone() {
                         svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext))
@@ -899,6 +1006,10 @@ where
                         .keep_alive(c.keep_alive)
                         .client_request_timeout(c.client_request_timeout)
                         .client_disconnect_timeout(c.client_disconnect_timeout)
+                        .max_headers(c.max_headers)
+                        .max_header_size(c.max_header_size)
+                        .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                        .date_header(c.date_header)
                         .local_addr(addr);
 
                     let svc = if let Some(handler) = on_connect_fn.clone() {
@@ -967,6 +1078,10 @@ where
                         .keep_alive(c.keep_alive)
                         .client_request_timeout(c.client_request_timeout)
                         .client_disconnect_timeout(c.client_disconnect_timeout)
+                        .max_headers(c.max_headers)
+                        .max_header_size(c.max_header_size)
+                        .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                        .date_header(c.date_header)
                         .finish(map_config(fac, move |_| config.clone())),
                 )
             },
@@ -1007,7 +1122,11 @@ where
                 let mut svc = HttpService::build()
                     .keep_alive(c.keep_alive)
                     .client_request_timeout(c.client_request_timeout)
-                    .client_disconnect_timeout(c.client_disconnect_timeout);
+                    .client_disconnect_timeout(c.client_disconnect_timeout)
+                    .max_headers(c.max_headers)
+                    .max_header_size(c.max_header_size)
+                    .max_keep_alive_timeout(c.max_keep_alive_timeout)
+                    .date_header(c.date_header);
 
                 if let Some(handler) = on_connect_fn.clone() {
                     svc = svc.on_connect_ext(move |io: &_, ext: _| (handler)(io as &dyn Any, ext));
@@ -2,6 +2,7 @@
 //!
 //! # Initializing A Test Service
 //! - [`init_service`]
+//! - [`try_init_service`]
 //!
 //! # Off-The-Shelf Test Services
 //! - [`ok_service`]
@@ -17,9 +18,13 @@
 //!
 //! # Reading Response Payloads
 //! - [`read_body`]
+//! - [`read_body_limited`]
 //! - [`try_read_body`]
 //! - [`read_body_json`]
 //! - [`try_read_body_json`]
+//!
+//! # Testing WebSocket Handshakes
+//! - [`ws_handshake`]
 
 // TODO: more docs on generally how testing works with these parts
 
@@ -31,16 +36,14 @@ mod test_utils;
 
 #[allow(deprecated)]
 pub use self::test_services::{default_service, ok_service, simple_service, status_service};
-#[cfg(test)]
-pub(crate) use self::test_utils::try_init_service;
 #[allow(deprecated)]
 pub use self::test_utils::{read_response, read_response_json};
 pub use self::{
     test_request::TestRequest,
     test_utils::{
         call_and_read_body, call_and_read_body_json, call_service, init_service, read_body,
-        read_body_json, try_call_and_read_body_json, try_call_service, try_read_body,
-        try_read_body_json,
+        read_body_json, read_body_limited, try_call_and_read_body_json, try_call_service,
+        try_init_service, try_read_body, try_read_body_json, ws_handshake,
     },
 };
 
@@ -1,6 +1,7 @@
 use std::{borrow::Cow, net::SocketAddr, rc::Rc};
 
 use actix_http::{test::TestRequest as HttpTestRequest, Request};
+use futures_core::Stream;
 use serde::Serialize;
 
 #[cfg(feature = "cookies")]
@@ -10,6 +11,7 @@ use crate::{
     config::AppConfig,
     data::Data,
     dev::{Extensions, Path, Payload, ResourceDef, Service, Url},
+    error::PayloadError,
     http::{
         header::{ContentType, TryIntoHeaderPair},
         Method, Uri, Version,
@@ -184,6 +186,36 @@ impl TestRequest {
         self
     }
 
+    /// Sets request payload to a stream, sent using chunked transfer encoding.
+    ///
+    /// The `Transfer-Encoding: chunked` header is set and any `Content-Length` header is removed,
+    /// so the body has no known length. This is useful for testing how extractors and middleware
+    /// handle streamed request bodies.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{error::PayloadError, test::TestRequest, web::Bytes, HttpMessage as _};
+    /// use futures_util::stream;
+    ///
+    /// let chunks = [
+    ///     Ok::<_, PayloadError>(Bytes::from_static(b"hello ")),
+    ///     Ok(Bytes::from_static(b"world")),
+    /// ];
+    ///
+    /// let req = TestRequest::post()
+    ///     .set_chunked_payload(stream::iter(chunks))
+    ///     .to_srv_request();
+    ///
+    /// assert!(req.chunked().unwrap());
+    /// ```
+    pub fn set_chunked_payload<S>(mut self, stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, PayloadError>> + 'static,
+    {
+        self.req.set_chunked_payload(stream);
+        self
+    }
+
     /// Serializes `data` to a URL encoded form and set it as the request payload.
     ///
     /// The `Content-Type` header is set to `application/x-www-form-urlencoded`.
@@ -350,7 +382,10 @@ mod tests {
     use std::time::SystemTime;
 
     use super::*;
-    use crate::{http::header, test::init_service, web, App, Error, Responder};
+    use crate::{
+        http::header, test::init_service, web, App, Error, FromRequest as _, HttpMessage as _,
+        Responder,
+    };
 
     #[actix_rt::test]
     async fn test_basics() {
@@ -399,6 +434,25 @@ mod tests {
         assert_eq!(result, Bytes::from_static(b"welcome!"));
     }
 
+    #[actix_rt::test]
+    async fn test_chunked_payload() {
+        let chunks = [
+            Ok(Bytes::from_static(b"hello ")),
+            Ok(Bytes::from_static(b"world")),
+        ];
+
+        let (req, mut pl) = TestRequest::post()
+            .insert_header((header::CONTENT_LENGTH, 11))
+            .set_chunked_payload(futures_util::stream::iter(chunks))
+            .to_http_parts();
+
+        assert!(req.chunked().unwrap());
+        assert!(!req.headers().contains_key(header::CONTENT_LENGTH));
+
+        let body = Bytes::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(body, Bytes::from_static(b"hello world"));
+    }
+
     #[actix_rt::test]
     async fn test_async_with_block() {
         async fn async_with_block() -> Result<HttpResponse, Error> {
@@ -8,9 +8,11 @@ use crate::{
     body::{self, MessageBody},
     config::AppConfig,
     dev::{Service, ServiceFactory},
+    error::WsHandshakeError,
     service::ServiceResponse,
+    test::TestRequest,
     web::Bytes,
-    Error,
+    Error, HttpResponse,
 };
 
 /// Initialize service from application builder instance.
@@ -52,7 +54,24 @@ where
 }
 
 /// Fallible version of [`init_service`] that allows testing initialization errors.
-pub(crate) async fn try_init_service<R, S, B, E>(
+///
+/// # Examples
+/// ```
+/// use actix_web::{test, web, App};
+///
+/// #[actix_web::test]
+/// async fn test_init_error() {
+///     let res = test::try_init_service(
+///         App::new()
+///             .data_factory(|| async { Err::<u32, _>(()) })
+///             .service(web::resource("/").to(|| async { "OK" })),
+///     )
+///     .await;
+///
+///     assert!(res.is_err());
+/// }
+/// ```
+pub async fn try_init_service<R, S, B, E>(
     app: R,
 ) -> Result<impl Service<Request, Response = ServiceResponse<B>, Error = E>, S::InitError>
 where
@@ -200,6 +219,46 @@ where
         .expect("error reading test response body")
 }
 
+/// Helper function that returns a response body of a ServiceResponse, reading at most `max` bytes.
+///
+/// Useful for guarding against handlers that return unbounded streams, which would otherwise cause
+/// [`read_body`] to never complete.
+///
+/// # Examples
+/// ```
+/// use actix_web::{test, web, App, HttpResponse};
+/// use bytes::Bytes;
+///
+/// #[actix_web::test]
+/// async fn test_index() {
+///     let app = test::init_service(
+///         App::new().route("/", web::get().to(|| async { HttpResponse::Ok().body("welcome!") })),
+///     )
+///     .await;
+///
+///     let req = test::TestRequest::get().uri("/").to_request();
+///     let res = test::call_service(&app, req).await;
+///     let result = test::read_body_limited(res, 1024).await;
+///     assert_eq!(result, Bytes::from_static(b"welcome!"));
+/// }
+/// ```
+///
+/// # Panics
+/// Panics if body is larger than `max` bytes or if it yields an error while it is being read.
+pub async fn read_body_limited<B>(res: ServiceResponse<B>, max: usize) -> Bytes
+where
+    B: MessageBody,
+{
+    let body = res.into_body();
+
+    match body::to_bytes_limited(body, max).await {
+        Ok(res) => res
+            .map_err(Into::<Box<dyn StdError>>::into)
+            .expect("error reading test response body"),
+        Err(_) => panic!("test response body exceeded limit of {max} bytes"),
+    }
+}
+
 /// Fallible version of [`read_body`] that allows testing MessageBody reading errors.
 pub async fn try_read_body<B>(res: ServiceResponse<B>) -> Result<Bytes, <B as MessageBody>::Error>
 where
@@ -267,6 +326,9 @@ where
 }
 
 /// Fallible version of [`read_body_json`] that allows testing response deserialization errors.
+///
+/// Deserialization failures are returned as a boxed [`serde_json::Error`], which can be recovered
+/// using `downcast_ref`.
 pub async fn try_read_body_json<T, B>(res: ServiceResponse<B>) -> Result<T, Box<dyn StdError>>
 where
     B: MessageBody,
@@ -355,13 +417,52 @@ where
     call_and_read_body_json(app, req).await
 }
 
+/// Runs the WebSocket handshake checks on a test request, without starting a server.
+///
+/// Returns the `101 Switching Protocols` response that would be sent to the client, or the
+/// handshake error describing why the request is not a valid WebSocket upgrade.
+///
+/// # Examples
+/// ```
+/// use actix_web::{
+///     error::WsHandshakeError,
+///     http::{header, StatusCode},
+///     test,
+/// };
+///
+/// let req = test::TestRequest::default()
+///     .insert_header((header::UPGRADE, "websocket"))
+///     .insert_header((header::CONNECTION, "upgrade"))
+///     .insert_header((header::SEC_WEBSOCKET_VERSION, "13"));
+/// assert_eq!(
+///     test::ws_handshake(req).unwrap_err(),
+///     WsHandshakeError::BadWebsocketKey,
+/// );
+///
+/// let req = test::TestRequest::default()
+///     .insert_header((header::UPGRADE, "websocket"))
+///     .insert_header((header::CONNECTION, "upgrade"))
+///     .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+///     .insert_header((header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ=="));
+/// let res = test::ws_handshake(req).unwrap();
+/// assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+/// ```
+pub fn ws_handshake(req: TestRequest) -> Result<HttpResponse, WsHandshakeError> {
+    let req = req.to_http_request();
+    let mut res = actix_http::ws::handshake(req.head())?;
+    Ok(HttpResponse::from(res.finish().map_into_boxed_body()))
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
 
     use super::*;
     use crate::{
-        dev::ServiceRequest, http::header, test::TestRequest, web, App, HttpMessage, HttpResponse,
+        dev::ServiceRequest,
+        http::{header, StatusCode},
+        test::TestRequest,
+        web, App, HttpMessage, HttpResponse,
     };
 
     #[actix_rt::test]
@@ -484,7 +585,17 @@ mod tests {
             .await;
 
         let result: Result<Person, Box<dyn StdError>> = try_read_body_json(res).await;
-        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(err.downcast_ref::<serde_json::Error>().is_some());
+    }
+
+    #[actix_rt::test]
+    async fn test_try_body_json_malformed() {
+        let res = TestRequest::default().to_srv_response(HttpResponse::Ok().body(r#"{"id":"#));
+
+        let err = try_read_body_json::<Person, _>(res).await.unwrap_err();
+        let err = err.downcast_ref::<serde_json::Error>().unwrap();
+        assert!(err.is_eof());
     }
 
     #[actix_rt::test]
@@ -531,6 +642,38 @@ mod tests {
         assert_eq!(result, Bytes::from_static(b"welcome!"));
     }
 
+    #[actix_rt::test]
+    async fn test_read_body_limited() {
+        let app = init_service(
+            App::new()
+                .route("/", web::get().to(|| HttpResponse::Ok().body("welcome!")))
+                .route(
+                    "/stream",
+                    web::get().to(|| {
+                        HttpResponse::Ok().streaming(futures_util::stream::repeat_with(|| {
+                            Ok::<_, Error>(Bytes::from_static(b"data"))
+                        }))
+                    }),
+                ),
+        )
+        .await;
+
+        let res = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+        let result = read_body_limited(res, 8).await;
+        assert_eq!(result, Bytes::from_static(b"welcome!"));
+
+        let res = call_service(&app, TestRequest::get().uri("/stream").to_request()).await;
+        let result = futures_util::FutureExt::catch_unwind(std::panic::AssertUnwindSafe(
+            read_body_limited(res, 1024),
+        ))
+        .await;
+        let err = result.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            "test response body exceeded limit of 1024 bytes"
+        );
+    }
+
     #[actix_rt::test]
     async fn test_request_response_json() {
         let app =
@@ -590,4 +733,63 @@ mod tests {
             let _: String = read_body_json(call_service(&svc, req.pop().unwrap()).await).await;
         }
     }
+
+    #[test]
+    fn test_ws_handshake() {
+        let req = TestRequest::post();
+        assert_eq!(
+            ws_handshake(req).unwrap_err(),
+            WsHandshakeError::GetMethodRequired
+        );
+
+        let req = TestRequest::default();
+        assert_eq!(
+            ws_handshake(req).unwrap_err(),
+            WsHandshakeError::NoWebsocketUpgrade
+        );
+
+        let req = TestRequest::default().insert_header((header::UPGRADE, "websocket"));
+        assert_eq!(
+            ws_handshake(req).unwrap_err(),
+            WsHandshakeError::NoConnectionUpgrade
+        );
+
+        let req = TestRequest::default()
+            .insert_header((header::UPGRADE, "websocket"))
+            .insert_header((header::CONNECTION, "upgrade"));
+        assert_eq!(
+            ws_handshake(req).unwrap_err(),
+            WsHandshakeError::NoVersionHeader
+        );
+
+        let req = TestRequest::default()
+            .insert_header((header::UPGRADE, "websocket"))
+            .insert_header((header::CONNECTION, "upgrade"))
+            .insert_header((header::SEC_WEBSOCKET_VERSION, "5"));
+        assert_eq!(
+            ws_handshake(req).unwrap_err(),
+            WsHandshakeError::UnsupportedVersion
+        );
+
+        let req = TestRequest::default()
+            .insert_header((header::UPGRADE, "websocket"))
+            .insert_header((header::CONNECTION, "upgrade"))
+            .insert_header((header::SEC_WEBSOCKET_VERSION, "13"));
+        assert_eq!(
+            ws_handshake(req).unwrap_err(),
+            WsHandshakeError::BadWebsocketKey
+        );
+
+        let req = TestRequest::default()
+            .insert_header((header::UPGRADE, "websocket"))
+            .insert_header((header::CONNECTION, "upgrade"))
+            .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+            .insert_header((header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ=="));
+        let res = ws_handshake(req).unwrap();
+        assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+        assert_eq!(
+            res.headers().get(header::SEC_WEBSOCKET_ACCEPT).unwrap(),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
 }
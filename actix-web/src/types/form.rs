@@ -2,6 +2,7 @@
 
 use std::{
     borrow::Cow,
+    collections::HashMap,
     fmt,
     future::Future,
     ops,
@@ -15,13 +16,21 @@ use bytes::BytesMut;
 use encoding_rs::{Encoding, UTF_8};
 use futures_core::{future::LocalBoxFuture, ready};
 use futures_util::{FutureExt as _, StreamExt as _};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{
+    de::{
+        self,
+        value::{CowStrDeserializer, MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Serialize,
+};
 
 #[cfg(feature = "__compress")]
 use crate::dev::Decompress;
 use crate::{
     body::EitherBody, error::UrlencodedError, extract::FromRequest, http::header::CONTENT_LENGTH,
-    web, Error, HttpMessage, HttpRequest, HttpResponse, Responder,
+    types::payload::set_payload_timeout, web, Error, HttpMessage, HttpRequest, HttpResponse,
+    Responder,
 };
 
 /// URL encoded payload extractor and responder.
@@ -53,6 +62,30 @@ use crate::{
 /// }
 /// ```
 ///
+/// ## Repeated Keys
+/// By default, repeated keys (e.g., `tag=a&tag=b`, as sent by HTML checkbox groups) cannot be
+/// collected into a sequence. Enable [`FormConfig::collect_repeated_keys`] to deserialize them into
+/// a `Vec` field.
+///
+/// ```
+/// use actix_web::{web, App};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Filters {
+///     #[serde(default)]
+///     tag: Vec<String>,
+/// }
+///
+/// async fn index(form: web::Form<Filters>) -> String {
+///     form.tag.join(", ")
+/// }
+///
+/// App::new()
+///     .app_data(web::FormConfig::default().collect_repeated_keys(true))
+///     .route("/", web::post().to(index));
+/// ```
+///
 /// # Responder
 /// The `Form` type also allows you to create URL encoded responses by returning a value of type
 /// `Form<T>` where `T` is the type to be URL encoded, as long as `T` implements [`Serialize`].
@@ -130,10 +163,18 @@ where
 
     #[inline]
     fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
-        let FormConfig { limit, err_handler } = FormConfig::from_req(req).clone();
+        set_payload_timeout(req, payload);
+
+        let FormConfig {
+            limit,
+            collect_repeated_keys,
+            err_handler,
+        } = FormConfig::from_req(req).clone();
 
         FormExtractFut {
-            fut: UrlEncoded::new(req, payload).limit(limit),
+            fut: UrlEncoded::new(req, payload)
+                .limit(limit)
+                .collect_repeated_keys(collect_repeated_keys),
             req: req.clone(),
             err_handler,
         }
@@ -223,6 +264,7 @@ impl<T: Serialize> Responder for Form<T> {
 #[derive(Clone)]
 pub struct FormConfig {
     limit: usize,
+    collect_repeated_keys: bool,
     err_handler: FormErrHandler,
 }
 
@@ -233,6 +275,15 @@ impl FormConfig {
         self
     }
 
+    /// Set whether values of repeated keys are collected into sequences. Disabled by default.
+    ///
+    /// When enabled, `tag=a&tag=b` can be deserialized into a `tag: Vec<String>` field. Fields that
+    /// are not sequences receive the last value of a repeated key.
+    pub fn collect_repeated_keys(mut self, collect_repeated_keys: bool) -> Self {
+        self.collect_repeated_keys = collect_repeated_keys;
+        self
+    }
+
     /// Set custom error handler
     pub fn error_handler<F>(mut self, f: F) -> Self
     where
@@ -255,6 +306,7 @@ impl FormConfig {
 /// Allow shared refs used as default.
 const DEFAULT_CONFIG: FormConfig = FormConfig {
     limit: 16_384, // 2^14 bytes (~16kB)
+    collect_repeated_keys: false,
     err_handler: None,
 };
 
@@ -280,6 +332,7 @@ pub struct UrlEncoded<T> {
     limit: usize,
     length: Option<usize>,
     encoding: &'static Encoding,
+    collect_repeated_keys: bool,
     err: Option<UrlencodedError>,
     fut: Option<LocalBoxFuture<'static, Result<T, UrlencodedError>>>,
 }
@@ -325,6 +378,7 @@ impl<T> UrlEncoded<T> {
             stream: Some(payload),
             limit: 32_768,
             length: len,
+            collect_repeated_keys: false,
             fut: None,
             err: None,
         }
@@ -338,12 +392,27 @@ impl<T> UrlEncoded<T> {
             err: Some(err),
             length: None,
             encoding: UTF_8,
+            collect_repeated_keys: false,
         }
     }
 
-    /// Set maximum accepted payload size. The default limit is 256kB.
+    /// Set maximum accepted payload size. The default limit is 32kB.
     pub fn limit(mut self, limit: usize) -> Self {
         self.limit = limit;
+
+        #[cfg(feature = "__compress")]
+        {
+            self.stream = self.stream.map(|stream| stream.limit(limit));
+        }
+
+        self
+    }
+
+    /// Set whether values of repeated keys are collected into sequences. Disabled by default.
+    ///
+    /// See [`FormConfig::collect_repeated_keys`].
+    pub fn collect_repeated_keys(mut self, collect_repeated_keys: bool) -> Self {
+        self.collect_repeated_keys = collect_repeated_keys;
         self
     }
 }
@@ -373,6 +442,7 @@ where
 
         // future
         let encoding = self.encoding;
+        let collect_repeated_keys = self.collect_repeated_keys;
         let mut stream = self.stream.take().unwrap();
 
         self.fut = Some(
@@ -393,14 +463,22 @@ where
                 }
 
                 if encoding == UTF_8 {
-                    serde_urlencoded::from_bytes::<T>(&body).map_err(UrlencodedError::Parse)
+                    if collect_repeated_keys {
+                        from_bytes_collected::<T>(&body).map_err(UrlencodedError::Parse)
+                    } else {
+                        serde_urlencoded::from_bytes::<T>(&body).map_err(UrlencodedError::Parse)
+                    }
                 } else {
                     let body = encoding
                         .decode_without_bom_handling_and_without_replacement(&body)
                         .map(Cow::into_owned)
                         .ok_or(UrlencodedError::Encoding)?;
 
-                    serde_urlencoded::from_str::<T>(&body).map_err(UrlencodedError::Parse)
+                    if collect_repeated_keys {
+                        from_bytes_collected::<T>(body.as_bytes()).map_err(UrlencodedError::Parse)
+                    } else {
+                        serde_urlencoded::from_str::<T>(&body).map_err(UrlencodedError::Parse)
+                    }
                 }
             }
             .boxed_local(),
@@ -410,6 +488,173 @@ where
     }
 }
 
+/// Deserializes URL encoded `input`, grouping the values of repeated keys.
+fn from_bytes_collected<T: DeserializeOwned>(input: &[u8]) -> Result<T, de::value::Error> {
+    let mut index: HashMap<Cow<'_, str>, usize> = HashMap::new();
+    let mut pairs: Vec<(Cow<'_, str>, Vec<Cow<'_, str>>)> = Vec::new();
+
+    for (key, value) in url::form_urlencoded::parse(input) {
+        match index.get(&key) {
+            Some(&idx) => pairs[idx].1.push(value),
+            None => {
+                index.insert(key.clone(), pairs.len());
+                pairs.push((key, vec![value]));
+            }
+        }
+    }
+
+    T::deserialize(MapDeserializer::new(
+        pairs.into_iter().map(|(key, values)| (key, Values(values))),
+    ))
+}
+
+/// All values of a key, deserialized as a sequence or, otherwise, as the last value.
+struct Values<'de>(Vec<Cow<'de, str>>);
+
+impl<'de> Values<'de> {
+    fn last(mut self) -> Value<'de> {
+        Value(self.0.pop().expect("key should have at least one value"))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for Values<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! forward_to_last_value {
+    ($($method:ident($($arg:ident: $ty:ty),*),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.last().$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Values<'de> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.0.len() == 1 {
+            self.last().deserialize_any(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(SeqDeserializer::new(self.0.into_iter().map(Value)))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_last_value! {
+        deserialize_bool(), deserialize_i8(), deserialize_i16(), deserialize_i32(),
+        deserialize_i64(), deserialize_u8(), deserialize_u16(), deserialize_u32(),
+        deserialize_u64(), deserialize_f32(), deserialize_f64(), deserialize_char(),
+        deserialize_str(), deserialize_string(), deserialize_bytes(), deserialize_byte_buf(),
+        deserialize_unit(), deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_tuple_struct(name: &'static str, len: usize), deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(), deserialize_ignored_any(),
+    }
+}
+
+/// A single value, parsed into primitives as needed.
+struct Value<'de>(Cow<'de, str>);
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for Value<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! forward_parsed_value {
+    ($($ty:ident => $method:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse::<$ty>() {
+                    Ok(val) => val.into_deserializer().$method(visitor),
+                    Err(err) => Err(de::Error::custom(err)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Value<'de> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Cow::Borrowed(val) => visitor.visit_borrowed_str(val),
+            Cow::Owned(val) => visitor.visit_string(val),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        CowStrDeserializer::new(self.0).deserialize_enum(name, variants, visitor)
+    }
+
+    forward_parsed_value! {
+        bool => deserialize_bool,
+        u8 => deserialize_u8,
+        u16 => deserialize_u16,
+        u32 => deserialize_u32,
+        u64 => deserialize_u64,
+        i8 => deserialize_i8,
+        i16 => deserialize_i16,
+        i32 => deserialize_i32,
+        i64 => deserialize_i64,
+        f32 => deserialize_f32,
+        f64 => deserialize_f64,
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct tuple_struct struct identifier tuple
+        ignored_any seq map
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -556,4 +801,99 @@ mod tests {
         let err_str = s.err().unwrap().to_string();
         assert!(err_str.starts_with("URL encoded payload is larger"));
     }
+
+    #[actix_rt::test]
+    async fn test_raised_limit() {
+        let payload = format!("hello={}&counter=1", "a".repeat(300 * 1024));
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .insert_header((CONTENT_LENGTH, payload.len()))
+            .set_payload(payload.clone())
+            .to_http_parts();
+
+        let res = Form::<Info>::from_request(&req, &mut pl).await;
+        let err = res.unwrap_err();
+        assert!(matches!(
+            err.as_error::<UrlencodedError>(),
+            Some(UrlencodedError::Overflow { limit: 16_384, .. })
+        ));
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .insert_header((CONTENT_LENGTH, payload.len()))
+            .set_payload(payload)
+            .app_data(FormConfig::default().limit(512 * 1024))
+            .to_http_parts();
+
+        let Form(info) = Form::<Info>::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(info.hello.len(), 300 * 1024);
+        assert_eq!(info.counter, 1);
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Checkboxes {
+        name: String,
+        tag: Vec<String>,
+        #[serde(default)]
+        id: Vec<u32>,
+        page: Option<u32>,
+    }
+
+    #[actix_rt::test]
+    async fn test_collect_repeated_keys() {
+        let payload = Bytes::from_static(b"name=a&tag=x&name=b&tag=y+z&id=1");
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(payload.clone())
+            .app_data(FormConfig::default().collect_repeated_keys(true))
+            .to_http_parts();
+
+        let Form(form) = Form::<Checkboxes>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(
+            form,
+            Checkboxes {
+                name: "b".to_owned(),
+                tag: vec!["x".to_owned(), "y z".to_owned()],
+                id: vec![1],
+                page: None,
+            }
+        );
+
+        // disabled by default
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(payload)
+            .to_http_parts();
+
+        let res = Form::<Checkboxes>::from_request(&req, &mut pl).await;
+        assert!(res.is_err());
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(Bytes::from_static(b"name=a&page=2&page=x"))
+            .to_http_parts();
+
+        let res = UrlEncoded::<Checkboxes>::new(&req, &mut pl)
+            .collect_repeated_keys(true)
+            .await;
+        assert!(matches!(res, Err(UrlencodedError::Parse(_))));
+
+        // overflow is still enforced
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(Bytes::from_static(b"tag=a&tag=b&tag=c"))
+            .app_data(FormConfig::default().limit(10).collect_repeated_keys(true))
+            .to_http_parts();
+
+        let res = Form::<Checkboxes>::from_request(&req, &mut pl).await;
+        let err = res.unwrap_err();
+        assert!(matches!(
+            err.as_error::<UrlencodedError>(),
+            Some(UrlencodedError::Overflow { limit: 10, .. })
+        ));
+    }
 }
@@ -17,12 +17,15 @@ use serde::{de::DeserializeOwned, Serialize};
 
 #[cfg(feature = "__compress")]
 use crate::dev::Decompress;
+#[cfg(feature = "parse-size")]
+use crate::error::SizeLimitError;
 use crate::{
     body::EitherBody,
     error::{Error, JsonPayloadError},
     extract::FromRequest,
     http::header::{ContentLength, Header as _},
     request::HttpRequest,
+    types::payload::set_payload_timeout,
     web, HttpMessage, HttpResponse, Responder,
 };
 
@@ -142,6 +145,8 @@ impl<T: DeserializeOwned> FromRequest for Json<T> {
 
     #[inline]
     fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        set_payload_timeout(req, payload);
+
         let config = JsonConfig::from_req(req);
 
         let limit = config.limit;
@@ -243,7 +248,30 @@ impl JsonConfig {
         self
     }
 
+    /// Set maximum accepted payload size from a human-readable string, such as `"2MiB"`.
+    ///
+    /// The string is parsed using [parse_size], the same as `MultipartForm` field limits. Returns
+    /// an error if the string can not be parsed.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::web;
+    ///
+    /// let json_cfg = web::JsonConfig::default().limit_str("2MiB").unwrap();
+    /// assert!(web::JsonConfig::default().limit_str("2 lots").is_err());
+    /// ```
+    ///
+    /// [parse_size]: https://docs.rs/parse-size/1/parse_size
+    #[cfg(feature = "parse-size")]
+    pub fn limit_str(mut self, limit: &str) -> Result<Self, SizeLimitError> {
+        self.limit = SizeLimitError::parse(limit)?;
+        Ok(self)
+    }
+
     /// Set custom error handler.
+    ///
+    /// See [`JsonPayloadError::json_error_response`] for building error responses with a structured
+    /// JSON body.
     pub fn error_handler<F>(mut self, f: F) -> Self
     where
         F: Fn(JsonPayloadError, &HttpRequest) -> Error + Send + Sync + 'static,
@@ -381,6 +409,9 @@ impl<T: DeserializeOwned> JsonBody<T> {
                 buf,
                 ..
             } => {
+                #[cfg(feature = "__compress")]
+                let payload = payload.limit(limit);
+
                 if let Some(len) = length {
                     if len > limit {
                         return JsonBody::Error(Some(JsonPayloadError::OverflowKnownLength {
@@ -519,6 +550,22 @@ mod tests {
         assert_eq!(msg.name, "invalid request");
     }
 
+    #[cfg(feature = "parse-size")]
+    #[test]
+    fn test_limit_str() {
+        let cfg = JsonConfig::default().limit_str("2KiB").unwrap();
+        assert_eq!(cfg.limit, 2048);
+
+        let cfg = JsonConfig::default().limit_str("1 MB").unwrap();
+        assert_eq!(cfg.limit, 1_000_000);
+
+        let err = JsonConfig::default().limit_str("lots").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            r#"invalid size limit "lots": invalid digit found in string"#
+        );
+    }
+
     #[actix_rt::test]
     async fn test_extract() {
         let (req, mut pl) = TestRequest::default()
@@ -0,0 +1,263 @@
+//! For combined JSON/form extractor documentation, see [`JsonOrForm`].
+
+use std::{
+    fmt,
+    future::Future,
+    ops,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_http::Payload;
+use futures_core::ready;
+use serde::de::DeserializeOwned;
+
+use super::{form::FormExtractFut, json::JsonExtractFut};
+use crate::{
+    error::Error,
+    extract::FromRequest,
+    web::{Form, Json},
+    HttpMessage as _, HttpRequest,
+};
+
+/// Extract typed data from either a JSON or a URL-encoded form request body.
+///
+/// Requests with the `application/x-www-form-urlencoded` content type are extracted using
+/// [`Form`]; all other requests are extracted using [`Json`]. Each extractor keeps its own
+/// configuration and error handling, so [`FormConfig`] and [`JsonConfig`] apply as usual and
+/// failures surface as either a [`UrlencodedError`] or a [`JsonPayloadError`]. In particular, a
+/// request with any other content type is rejected with [`JsonPayloadError::ContentType`].
+///
+/// # Examples
+/// ```
+/// use actix_web::{post, web};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// // accepts both `{"username":"alice"}` and `username=alice`
+/// #[post("/")]
+/// async fn index(info: web::JsonOrForm<Info>) -> String {
+///     format!("Welcome {}!", info.username)
+/// }
+/// ```
+///
+/// [`FormConfig`]: crate::web::FormConfig
+/// [`JsonConfig`]: crate::web::JsonConfig
+/// [`UrlencodedError`]: crate::error::UrlencodedError
+/// [`JsonPayloadError`]: crate::error::JsonPayloadError
+/// [`JsonPayloadError::ContentType`]: crate::error::JsonPayloadError::ContentType
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct JsonOrForm<T>(pub T);
+
+impl<T> JsonOrForm<T> {
+    /// Unwrap into inner `T` value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> ops::Deref for JsonOrForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for JsonOrForm<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for JsonOrForm<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// See [here](#examples) for example of usage as an extractor.
+impl<T> FromRequest for JsonOrForm<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Error = Error;
+    type Future = JsonOrFormExtractFut<T>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        // same check as `UrlEncoded`, so that form requests are never rejected by `Form`
+        let is_form = req
+            .content_type()
+            .eq_ignore_ascii_case("application/x-www-form-urlencoded");
+
+        let fut = if is_form {
+            JsonOrFormExtractFutInner::Form(Form::from_request(req, payload))
+        } else {
+            JsonOrFormExtractFutInner::Json(Json::from_request(req, payload))
+        };
+
+        JsonOrFormExtractFut { fut }
+    }
+}
+
+enum JsonOrFormExtractFutInner<T> {
+    Json(JsonExtractFut<T>),
+    Form(FormExtractFut<T>),
+}
+
+pub struct JsonOrFormExtractFut<T> {
+    fut: JsonOrFormExtractFutInner<T>,
+}
+
+impl<T> Future for JsonOrFormExtractFut<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Output = Result<JsonOrForm<T>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let res = match &mut this.fut {
+            JsonOrFormExtractFutInner::Json(fut) => {
+                ready!(Pin::new(fut).poll(cx)).map(Json::into_inner)
+            }
+            JsonOrFormExtractFutInner::Form(fut) => {
+                ready!(Pin::new(fut).poll(cx)).map(Form::into_inner)
+            }
+        };
+
+        Poll::Ready(res.map(JsonOrForm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        error::{JsonPayloadError, UrlencodedError},
+        http::header,
+        test::TestRequest,
+        web::{FormConfig, JsonConfig},
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Info {
+        hello: String,
+        counter: i64,
+    }
+
+    #[actix_rt::test]
+    async fn test_json() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .set_payload(Bytes::from_static(b"{\"hello\":\"world\",\"counter\":123}"))
+            .to_http_parts();
+
+        let info = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(
+            info.into_inner(),
+            Info {
+                hello: "world".to_owned(),
+                counter: 123,
+            }
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_form() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "Application/X-WWW-Form-UrlEncoded"))
+            .set_payload(Bytes::from_static(b"hello=world&counter=123"))
+            .to_http_parts();
+
+        let info = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
+        assert_eq!(
+            info.into_inner(),
+            Info {
+                hello: "world".to_owned(),
+                counter: 123,
+            }
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_errors() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .set_payload(Bytes::from_static(b"hello=world"))
+            .to_http_parts();
+        let err = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.as_error::<JsonPayloadError>(),
+            Some(JsonPayloadError::Deserialize(_))
+        ));
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(Bytes::from_static(b"hello=world&counter=abc"))
+            .to_http_parts();
+        let err = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.as_error::<UrlencodedError>(),
+            Some(UrlencodedError::Parse(_))
+        ));
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "text/plain"))
+            .set_payload(Bytes::from_static(b"hello=world&counter=123"))
+            .to_http_parts();
+        let err = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.as_error::<JsonPayloadError>(),
+            Some(JsonPayloadError::ContentType)
+        ));
+    }
+
+    #[actix_rt::test]
+    async fn test_configs() {
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .set_payload(Bytes::from_static(b"{\"hello\":\"world\",\"counter\":123}"))
+            .app_data(JsonConfig::default().limit(10))
+            .to_http_parts();
+        let err = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.as_error::<JsonPayloadError>(),
+            Some(JsonPayloadError::Overflow { .. } | JsonPayloadError::OverflowKnownLength { .. })
+        ));
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_TYPE, "application/x-www-form-urlencoded"))
+            .set_payload(Bytes::from_static(b"hello=world&counter=123"))
+            .app_data(FormConfig::default().limit(10))
+            .to_http_parts();
+        let err = JsonOrForm::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.as_error::<UrlencodedError>(),
+            Some(UrlencodedError::Overflow { .. })
+        ));
+    }
+}
@@ -5,19 +5,29 @@ mod form;
 mod header;
 mod html;
 mod json;
+mod json_or_form;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod ndjson;
 mod path;
 mod payload;
 mod query;
 mod readlines;
+mod sse;
 
+#[cfg(feature = "msgpack")]
+pub use self::msgpack::{MsgPack, MsgPackBody, MsgPackConfig};
 pub use self::{
     either::Either,
     form::{Form, FormConfig, UrlEncoded},
     header::Header,
     html::Html,
     json::{Json, JsonBody, JsonConfig},
+    json_or_form::JsonOrForm,
+    ndjson::NdJson,
     path::{Path, PathConfig},
     payload::{Payload, PayloadConfig},
     query::{Query, QueryConfig},
     readlines::Readlines,
+    sse::{Sse, SseEvent},
 };
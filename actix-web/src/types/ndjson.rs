@@ -0,0 +1,167 @@
+//! Newline-delimited JSON streaming responder. See [`NdJson`].
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{BufMut as _, Bytes, BytesMut};
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+use serde::Serialize;
+
+use crate::{
+    body::{BodySize, MessageBody},
+    http::{
+        header::{self, HeaderValue},
+        StatusCode,
+    },
+    BoxError, HttpRequest, HttpResponse, Responder,
+};
+
+pin_project! {
+    /// Streaming newline-delimited JSON responder and body.
+    ///
+    /// Each item of the wrapped stream is serialized to JSON and followed by a `\n`. Items are sent
+    /// as individual chunks as soon as the stream yields them, so clients see incremental output.
+    ///
+    /// When used as a responder, creates a 200 OK response with the `application/x-ndjson` content
+    /// type. If the stream yields an error, or an item fails to serialize, the response is ended.
+    ///
+    /// # Examples
+    /// ```
+    /// use actix_web::{get, web::NdJson, Responder};
+    /// use futures_util::stream;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct LogLine {
+    ///     level: &'static str,
+    ///     message: &'static str,
+    /// }
+    ///
+    /// #[get("/logs")]
+    /// async fn logs() -> impl Responder {
+    ///     NdJson::new(stream::iter([
+    ///         Ok::<_, std::io::Error>(LogLine { level: "info", message: "started" }),
+    ///         Ok(LogLine { level: "warn", message: "disk almost full" }),
+    ///     ]))
+    /// }
+    /// ```
+    pub struct NdJson<S> {
+        #[pin]
+        stream: S,
+    }
+}
+
+impl<S, T, E> NdJson<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: Serialize,
+    E: Into<BoxError> + 'static,
+{
+    /// Constructs a new `NdJson` from a stream of serializable items.
+    pub fn new(stream: S) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S, T, E> MessageBody for NdJson<S>
+where
+    S: Stream<Item = Result<T, E>>,
+    T: Serialize,
+    E: Into<BoxError> + 'static,
+{
+    type Error = BoxError;
+
+    #[inline]
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let item = match ready!(self.project().stream.poll_next(cx)) {
+            Some(Ok(item)) => item,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => return Poll::Ready(None),
+        };
+
+        let mut buf = BytesMut::new().writer();
+
+        if let Err(err) = serde_json::to_writer(&mut buf, &item) {
+            return Poll::Ready(Some(Err(err.into())));
+        }
+
+        let mut buf = buf.into_inner();
+        buf.put_u8(b'\n');
+
+        Poll::Ready(Some(Ok(buf.freeze())))
+    }
+}
+
+impl<S, T, E> Responder for NdJson<S>
+where
+    S: Stream<Item = Result<T, E>> + 'static,
+    T: Serialize,
+    E: Into<BoxError> + 'static,
+{
+    type Body = Self;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let mut res = HttpResponse::with_body(StatusCode::OK, self);
+        res.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-ndjson"),
+        );
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{body, test::TestRequest};
+
+    #[actix_rt::test]
+    async fn responder() {
+        let req = TestRequest::default().to_http_request();
+
+        let items = stream::iter([Ok::<_, BoxError>(vec![1, 2]), Ok(vec![]), Ok(vec![3])]);
+        let res = NdJson::new(items).respond_to(&req);
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/x-ndjson"
+        );
+
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "[1,2]\n[]\n[3]\n");
+    }
+
+    #[actix_rt::test]
+    async fn chunk_per_item() {
+        let items = stream::iter([Ok::<_, BoxError>("a"), Ok("b")]);
+        let body = NdJson::new(items);
+        actix_rt::pin!(body);
+
+        let chunk = std::future::poll_fn(|cx| body.as_mut().poll_next(cx)).await;
+        assert_eq!(chunk.unwrap().unwrap(), "\"a\"\n");
+        let chunk = std::future::poll_fn(|cx| body.as_mut().poll_next(cx)).await;
+        assert_eq!(chunk.unwrap().unwrap(), "\"b\"\n");
+        let chunk = std::future::poll_fn(|cx| body.as_mut().poll_next(cx)).await;
+        assert!(chunk.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn stream_error() {
+        let items = stream::iter([Ok("a"), Err(std::io::Error::other("disconnected"))]);
+        let err = body::to_bytes(NdJson::new(items)).await.unwrap_err();
+        assert_eq!(err.to_string(), "disconnected");
+    }
+}
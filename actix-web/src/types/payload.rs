@@ -6,9 +6,11 @@ use std::{
     pin::Pin,
     str,
     task::{Context, Poll},
+    time::Duration,
 };
 
-use actix_http::error::PayloadError;
+use actix_http::{error::PayloadError, BoxedPayloadStream};
+use actix_rt::time::{sleep, Sleep};
 use actix_utils::future::{ready, Either, Ready};
 use bytes::{Bytes, BytesMut};
 use encoding_rs::{Encoding, UTF_8};
@@ -134,7 +136,8 @@ impl FromRequest for Payload {
     type Future = Ready<Result<Self, Self::Error>>;
 
     #[inline]
-    fn from_request(_: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+    fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+        set_payload_timeout(req, payload);
         ready(Ok(Payload(payload.take())))
     }
 }
@@ -169,7 +172,9 @@ impl FromRequest for Bytes {
         }
 
         Either::left(BytesExtractFut {
-            body_fut: HttpMessageBody::new(req, payload).limit(cfg.limit),
+            body_fut: HttpMessageBody::new(req, payload)
+                .limit(cfg.limit)
+                .timeout(cfg.timeout.unwrap_or(Duration::ZERO)),
         })
     }
 }
@@ -220,8 +225,9 @@ impl FromRequest for String {
             Ok(enc) => enc,
             Err(err) => return Either::right(ready(Err(err.into()))),
         };
-        let limit = cfg.limit;
-        let body_fut = HttpMessageBody::new(req, payload).limit(limit);
+        let body_fut = HttpMessageBody::new(req, payload)
+            .limit(cfg.limit)
+            .timeout(cfg.timeout.unwrap_or(Duration::ZERO));
 
         Either::left(StringExtractFut { body_fut, encoding })
     }
@@ -264,9 +270,11 @@ fn bytes_to_string(body: Bytes, encoding: &'static Encoding) -> Result<String, E
 /// Applies to the built-in [`Bytes`] and [`String`] extractors.
 /// Note that the [`Payload`] extractor does not automatically check
 /// conformance with this configuration to allow more flexibility when
-/// building extractors on top of [`Payload`].
+/// building extractors on top of [`Payload`]. The [timeout](Self::timeout) is the exception; it is
+/// also enforced by [`Payload`] and the other built-in body extractors.
 ///
-/// By default, the payload size limit is 256kB and there is no mime type condition.
+/// By default, the payload size limit is 256kB, there is no mime type condition, and there is no
+/// timeout for reading the payload.
 ///
 /// To use this, add an instance of it to your [`app`](crate::App), [`scope`](crate::Scope)
 /// or [`resource`](crate::Resource) through the associated `.app_data()` method.
@@ -274,6 +282,7 @@ fn bytes_to_string(body: Bytes, encoding: &'static Encoding) -> Result<String, E
 pub struct PayloadConfig {
     limit: usize,
     mimetype: Option<Mime>,
+    timeout: Option<Duration>,
 }
 
 impl PayloadConfig {
@@ -286,6 +295,9 @@ impl PayloadConfig {
     }
 
     /// Set maximum accepted payload size in bytes. The default limit is 256KiB.
+    ///
+    /// For compressed payloads, the limit applies to the decoded size, and decoding stops as soon
+    /// as it is exceeded.
     pub fn limit(mut self, limit: usize) -> Self {
         self.limit = limit;
         self
@@ -297,6 +309,21 @@ impl PayloadConfig {
         self
     }
 
+    /// Set maximum time allowed for reading the payload.
+    ///
+    /// The timeout covers reading the whole payload, starting when it is first polled. If the
+    /// payload is not fully received in time, extraction fails with a `408 Request Timeout` error.
+    /// By default, or when set to a zero duration, there is no timeout.
+    ///
+    /// Unlike the other settings, the timeout is enforced by all of the built-in body extractors:
+    /// [`Bytes`], [`String`], [`Payload`], [`Json`](crate::web::Json),
+    /// [`Form`](crate::web::Form), [`JsonOrForm`](crate::web::JsonOrForm), and `MsgPack`. The
+    /// [`Payload`] stream yields [`PayloadError::Timeout`] once it elapses.
+    pub fn timeout(mut self, dur: Duration) -> Self {
+        self.timeout = (!dur.is_zero()).then_some(dur);
+        self
+    }
+
     fn check_mimetype(&self, req: &HttpRequest) -> Result<(), Error> {
         // check content-type
         if let Some(ref mt) = self.mimetype {
@@ -333,6 +360,7 @@ const DEFAULT_CONFIG_LIMIT: usize = 262_144; // 2^18 bytes (~256kB)
 const DEFAULT_CONFIG: PayloadConfig = PayloadConfig {
     limit: DEFAULT_CONFIG_LIMIT,
     mimetype: None,
+    timeout: None,
 };
 
 impl Default for PayloadConfig {
@@ -341,12 +369,61 @@ impl Default for PayloadConfig {
     }
 }
 
+/// Applies the read timeout of the request's [`PayloadConfig`], if any, to `payload`.
+pub(crate) fn set_payload_timeout(req: &HttpRequest, payload: &mut dev::Payload) {
+    if let Some(timeout) = PayloadConfig::from_req(req).timeout {
+        let stream: BoxedPayloadStream = Box::pin(TimeoutPayload {
+            payload: payload.take(),
+            timeout: Some(timeout),
+            deadline: None,
+        });
+
+        *payload = dev::Payload::from(stream);
+    }
+}
+
+/// Payload stream that fails with `PayloadError::Timeout` when not finished in time.
+///
+/// The timer starts on first poll.
+struct TimeoutPayload {
+    payload: dev::Payload,
+    timeout: Option<Duration>,
+    deadline: Option<Pin<Box<Sleep>>>,
+}
+
+impl Stream for TimeoutPayload {
+    type Item = Result<Bytes, PayloadError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if let Some(dur) = this.timeout.take() {
+            this.deadline = Some(Box::pin(sleep(dur)));
+        }
+
+        if let Some(deadline) = &mut this.deadline {
+            if deadline.as_mut().poll(cx).is_ready() {
+                // end the stream after reporting the timeout
+                this.deadline = None;
+                this.payload = dev::Payload::None;
+                return Poll::Ready(Some(Err(PayloadError::Timeout)));
+            }
+        }
+
+        Pin::new(&mut this.payload).poll_next(cx)
+    }
+}
+
 /// Future that resolves to a complete HTTP body payload.
 ///
-/// By default only 256kB payload is accepted before `PayloadError::Overflow` is returned.
+/// By default only 256kB payload is accepted before `PayloadError::OverflowWithSize` is returned.
 /// Use `MessageBody::limit()` method to change upper limit.
+///
+/// There is no read timeout by default. Use `MessageBody::timeout()` method to set one.
 pub struct HttpMessageBody {
     limit: usize,
+    timeout: Option<Duration>,
+    deadline: Option<Pin<Box<Sleep>>>,
     length: Option<usize>,
     #[cfg(feature = "__compress")]
     stream: dev::Decompress<dev::Payload>,
@@ -368,7 +445,10 @@ impl HttpMessageBody {
                 Ok(s) => match s.parse::<usize>() {
                     Ok(l) => {
                         if l > DEFAULT_CONFIG_LIMIT {
-                            err = Some(PayloadError::Overflow);
+                            err = Some(PayloadError::OverflowWithSize {
+                                limit: DEFAULT_CONFIG_LIMIT,
+                                read: l,
+                            });
                         }
                         length = Some(l)
                     }
@@ -391,6 +471,8 @@ impl HttpMessageBody {
         HttpMessageBody {
             stream,
             limit: DEFAULT_CONFIG_LIMIT,
+            timeout: None,
+            deadline: None,
             length,
             buf: BytesMut::with_capacity(8192),
             err,
@@ -399,9 +481,14 @@ impl HttpMessageBody {
 
     /// Change max size of payload. By default max size is 256kB
     pub fn limit(mut self, limit: usize) -> Self {
+        #[cfg(feature = "__compress")]
+        {
+            self.stream = self.stream.limit(limit);
+        }
+
         if let Some(l) = self.length {
             self.err = if l > limit {
-                Some(PayloadError::Overflow)
+                Some(PayloadError::OverflowWithSize { limit, read: l })
             } else {
                 None
             };
@@ -409,6 +496,14 @@ impl HttpMessageBody {
         self.limit = limit;
         self
     }
+
+    /// Set maximum time allowed for reading the payload, starting from the first poll.
+    ///
+    /// When it elapses, `PayloadError::Timeout` is returned. A zero duration disables the timeout.
+    pub fn timeout(mut self, dur: Duration) -> Self {
+        self.timeout = (!dur.is_zero()).then_some(dur);
+        self
+    }
 }
 
 impl Future for HttpMessageBody {
@@ -421,13 +516,26 @@ impl Future for HttpMessageBody {
             return Poll::Ready(Err(err));
         }
 
+        if let Some(dur) = this.timeout.take() {
+            this.deadline = Some(Box::pin(sleep(dur)));
+        }
+
+        if let Some(deadline) = &mut this.deadline {
+            if deadline.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(PayloadError::Timeout));
+            }
+        }
+
         loop {
             let res = ready!(Pin::new(&mut this.stream).poll_next(cx));
             match res {
                 Some(chunk) => {
                     let chunk = chunk?;
                     if this.buf.len() + chunk.len() > this.limit {
-                        return Poll::Ready(Err(PayloadError::Overflow));
+                        return Poll::Ready(Err(PayloadError::OverflowWithSize {
+                            limit: this.limit,
+                            read: this.buf.len() + chunk.len(),
+                        }));
                     } else {
                         this.buf.extend_from_slice(&chunk);
                     }
@@ -625,7 +733,10 @@ mod tests {
             .into_parts();
         let res = HttpMessageBody::new(&req, &mut pl).await;
         match res.err().unwrap() {
-            PayloadError::Overflow => {}
+            PayloadError::OverflowWithSize { limit, read } => {
+                assert_eq!(limit, DEFAULT_CONFIG_LIMIT);
+                assert_eq!(read, 1_000_000);
+            }
             _ => unreachable!("error"),
         }
 
@@ -640,8 +751,121 @@ mod tests {
             .to_http_parts();
         let res = HttpMessageBody::new(&req, &mut pl).limit(5).await;
         match res.err().unwrap() {
-            PayloadError::Overflow => {}
+            PayloadError::OverflowWithSize { limit, read } => {
+                assert_eq!(limit, 5);
+                assert_eq!(read, 14);
+            }
+            _ => unreachable!("error"),
+        }
+    }
+
+    #[cfg(feature = "compress-gzip")]
+    #[actix_rt::test]
+    async fn test_message_body_decoded_limit() {
+        use std::io::Write as _;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&[b'a'; 64 * 1024]).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_ENCODING, "gzip"))
+            .set_payload(compressed.clone())
+            .to_http_parts();
+        let res = HttpMessageBody::new(&req, &mut pl).limit(1024).await;
+        match res.err().unwrap() {
+            PayloadError::Overflow | PayloadError::OverflowWithSize { .. } => {}
             _ => unreachable!("error"),
         }
+
+        let (req, mut pl) = TestRequest::default()
+            .insert_header((header::CONTENT_ENCODING, "gzip"))
+            .set_payload(compressed)
+            .to_http_parts();
+        let res = HttpMessageBody::new(&req, &mut pl).limit(64 * 1024).await;
+        assert_eq!(res.unwrap().len(), 64 * 1024);
+    }
+
+    #[actix_rt::test]
+    async fn test_payload_timeout() {
+        // no timeout unless one is set
+        assert!(PayloadConfig::default().timeout.is_none());
+
+        let (req, _) = TestRequest::default()
+            .app_data(PayloadConfig::default().timeout(Duration::from_millis(10)))
+            .to_http_parts();
+        let (_sender, payload) = actix_http::h1::Payload::create(false);
+        let mut pl = dev::Payload::from(payload);
+
+        let err = Bytes::from_request(&req, &mut pl).await.unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::REQUEST_TIMEOUT
+        );
+
+        let (req, mut pl) = TestRequest::default()
+            .app_data(PayloadConfig::default().timeout(Duration::ZERO))
+            .set_payload(Bytes::from_static(b"test"))
+            .to_http_parts();
+        let s = String::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(s, "test");
+    }
+
+    #[actix_rt::test]
+    async fn test_payload_timeout_other_extractors() {
+        use futures_util::StreamExt as _;
+        use serde::Deserialize;
+
+        #[derive(Debug, Deserialize)]
+        struct Info {
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        // requests whose payload never arrives
+        let timeout = |content_type: &'static str| {
+            let (sender, payload) = actix_http::h1::Payload::create(false);
+            let req = TestRequest::default()
+                .app_data(PayloadConfig::default().timeout(Duration::from_millis(10)))
+                .insert_header((header::CONTENT_TYPE, content_type))
+                .to_http_request();
+            (req, dev::Payload::from(payload), sender)
+        };
+
+        let (req, mut pl, _sender) = timeout("application/octet-stream");
+        let mut body = Payload::from_request(&req, &mut pl).await.unwrap();
+        assert!(matches!(
+            body.next().await,
+            Some(Err(PayloadError::Timeout))
+        ));
+        assert!(body.next().await.is_none());
+
+        let (req, mut pl, _sender) = timeout("application/json");
+        let err = web::Json::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::REQUEST_TIMEOUT
+        );
+
+        let (req, mut pl, _sender) = timeout("application/x-www-form-urlencoded");
+        let err = web::Form::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::REQUEST_TIMEOUT
+        );
+
+        // payloads that finish in time are unaffected
+        let (req, mut pl) = TestRequest::default()
+            .app_data(PayloadConfig::default().timeout(Duration::from_secs(10)))
+            .insert_header(header::ContentType::json())
+            .set_payload(r#"{"name":"test"}"#)
+            .to_http_parts();
+        web::Json::<Info>::from_request(&req, &mut pl)
+            .await
+            .unwrap();
     }
 }
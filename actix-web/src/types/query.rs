@@ -112,29 +112,35 @@ impl<T: DeserializeOwned> FromRequest for Query<T> {
 
     #[inline]
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        let error_handler = req
-            .app_data::<QueryConfig>()
-            .and_then(|c| c.err_handler.clone());
-
-        serde_urlencoded::from_str::<T>(req.query_string())
-            .map(|val| ok(Query(val)))
-            .unwrap_or_else(move |err| {
-                let err = QueryPayloadError::Deserialize(err);
-
-                log::debug!(
-                    "Failed during Query extractor deserialization. \
-                     Request path: {:?}",
-                    req.path()
-                );
-
-                let err = if let Some(error_handler) = error_handler {
-                    (error_handler)(err, req)
-                } else {
-                    err.into()
-                };
-
-                ready(Err(err))
-            })
+        let config = req.app_data::<QueryConfig>();
+        let error_handler = config.and_then(|c| c.err_handler.clone());
+        let limit = config.and_then(|c| c.limit);
+
+        let query = req.query_string();
+
+        let res = match limit {
+            Some(limit) if query.len() > limit => Err(QueryPayloadError::Overflow {
+                length: query.len(),
+                limit,
+            }),
+            _ => serde_urlencoded::from_str::<T>(query).map_err(QueryPayloadError::Deserialize),
+        };
+
+        res.map(|val| ok(Query(val))).unwrap_or_else(move |err| {
+            log::debug!(
+                "Failed during Query extractor deserialization. \
+                 Request path: {:?}",
+                req.path()
+            );
+
+            let err = if let Some(error_handler) = error_handler {
+                (error_handler)(err, req)
+            } else {
+                err.into()
+            };
+
+            ready(Err(err))
+        })
     }
 }
 
@@ -158,6 +164,8 @@ impl<T: DeserializeOwned> FromRequest for Query<T> {
 ///
 /// // custom `Query` extractor configuration
 /// let query_cfg = web::QueryConfig::default()
+///     // limit query string length
+///     .limit(1024)
 ///     // use custom error handler
 ///     .error_handler(|err, req| {
 ///         error::InternalError::from_response(err, HttpResponse::Conflict().finish()).into()
@@ -171,9 +179,19 @@ impl<T: DeserializeOwned> FromRequest for Query<T> {
 pub struct QueryConfig {
     #[allow(clippy::type_complexity)]
     err_handler: Option<Arc<dyn Fn(QueryPayloadError, &HttpRequest) -> Error + Send + Sync>>,
+    limit: Option<usize>,
 }
 
 impl QueryConfig {
+    /// Sets maximum accepted query string length, in bytes.
+    ///
+    /// Longer query strings are rejected with [`QueryPayloadError::Overflow`] before being
+    /// deserialized. There is no limit by default.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
     /// Set custom error handler
     pub fn error_handler<F>(mut self, f: F) -> Self
     where
@@ -249,6 +267,35 @@ mod tests {
             .unwrap();
     }
 
+    #[actix_rt::test]
+    async fn test_limit() {
+        let req = TestRequest::with_uri("/name/user1/?id=test")
+            .app_data(QueryConfig::default().limit(7))
+            .to_srv_request();
+        let (req, mut pl) = req.into_parts();
+        let s = Query::<Id>::from_request(&req, &mut pl).await.unwrap();
+        assert_eq!(s.id, "test");
+
+        let req = TestRequest::with_uri("/name/user1/?id=test1")
+            .app_data(QueryConfig::default().limit(7).error_handler(|err, _| {
+                assert!(matches!(
+                    err,
+                    QueryPayloadError::Overflow {
+                        length: 8,
+                        limit: 7
+                    }
+                ));
+                err.into()
+            }))
+            .to_srv_request();
+        let (req, mut pl) = req.into_parts();
+        let err = Query::<Id>::from_request(&req, &mut pl).await.unwrap_err();
+        assert_eq!(
+            err.as_response_error().error_response().status(),
+            StatusCode::URI_TOO_LONG
+        );
+    }
+
     #[actix_rt::test]
     async fn test_custom_error_responder() {
         let req = TestRequest::with_uri("/name/user1/")
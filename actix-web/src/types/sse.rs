@@ -0,0 +1,306 @@
+//! Server-Sent Events streaming responder. See [`Sse`].
+
+use std::{
+    fmt::Write as _,
+    future::Future as _,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use actix_rt::time::{sleep, Instant, Sleep};
+use bytes::Bytes;
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::{
+    body::{BodySize, MessageBody},
+    http::{
+        header::{self, CacheControl, HeaderValue, TryIntoHeaderValue as _},
+        StatusCode,
+    },
+    BoxError, HttpRequest, HttpResponse, Responder,
+};
+
+/// A single Server-Sent Event.
+///
+/// Line breaks in the event type and ID are removed. Data containing line breaks is sent as
+/// multiple `data` fields, which clients join back together.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    event: Option<String>,
+    data: String,
+    id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl SseEvent {
+    /// Constructs a new event with the given data.
+    pub fn data(data: impl Into<String>) -> Self {
+        Self {
+            data: data.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the event type.
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// Sets the event ID.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the time clients should wait before reconnecting.
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    fn into_bytes(self) -> Bytes {
+        fn single_line(val: &str) -> String {
+            val.replace(['\r', '\n'], "")
+        }
+
+        let mut buf = String::new();
+
+        if let Some(event) = &self.event {
+            let _ = writeln!(buf, "event: {}", single_line(event));
+        }
+
+        if let Some(id) = &self.id {
+            let _ = writeln!(buf, "id: {}", single_line(id));
+        }
+
+        if let Some(retry) = self.retry {
+            let _ = writeln!(buf, "retry: {}", retry.as_millis());
+        }
+
+        // CRLF, CR, and LF all end a line in an event stream
+        for line in self.data.replace("\r\n", "\n").split(['\r', '\n']) {
+            let _ = writeln!(buf, "data: {line}");
+        }
+
+        buf.push('\n');
+
+        Bytes::from(buf)
+    }
+}
+
+pin_project! {
+    /// Server-Sent Events (`text/event-stream`) responder and body.
+    ///
+    /// Each [`SseEvent`] of the wrapped stream is sent as an individual chunk as soon as the stream
+    /// yields it. Use [`keep_alive`](Self::keep_alive) to send comment lines while the stream is
+    /// idle, which stops proxies from closing the connection.
+    ///
+    /// When used as a responder, creates a 200 OK response with the `text/event-stream` content type
+    /// and `Cache-Control: no-cache`. The response is also excluded from the [`Compress`]
+    /// middleware so that events are not held in a compression buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use actix_web::{
+    ///     get,
+    ///     web::{Sse, SseEvent},
+    ///     Responder,
+    /// };
+    /// use futures_util::stream;
+    ///
+    /// #[get("/events")]
+    /// async fn events() -> impl Responder {
+    ///     let events = stream::iter([
+    ///         Ok::<_, std::io::Error>(SseEvent::data("hello").event("greeting")),
+    ///         Ok(SseEvent::data("world").id("2")),
+    ///     ]);
+    ///
+    ///     Sse::new(events).keep_alive(Duration::from_secs(15))
+    /// }
+    /// ```
+    ///
+    /// [`Compress`]: crate::middleware::Compress
+    pub struct Sse<S> {
+        #[pin]
+        stream: S,
+        keep_alive: Option<Duration>,
+        keep_alive_timer: Option<Pin<Box<Sleep>>>,
+    }
+}
+
+impl<S, E> Sse<S>
+where
+    S: Stream<Item = Result<SseEvent, E>>,
+    E: Into<BoxError> + 'static,
+{
+    /// Constructs a new `Sse` from a stream of events.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            keep_alive: None,
+            keep_alive_timer: None,
+        }
+    }
+
+    /// Sends a keep-alive comment whenever no event has been sent for `interval`.
+    pub fn keep_alive(mut self, interval: Duration) -> Self {
+        self.keep_alive = Some(interval);
+        self
+    }
+}
+
+impl<S, E> MessageBody for Sse<S>
+where
+    S: Stream<Item = Result<SseEvent, E>>,
+    E: Into<BoxError> + 'static,
+{
+    type Error = BoxError;
+
+    #[inline]
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        let this = self.project();
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => {
+                if let (Some(interval), Some(timer)) = (this.keep_alive, this.keep_alive_timer) {
+                    timer.as_mut().reset(Instant::now() + *interval);
+                }
+
+                Poll::Ready(Some(Ok(event.into_bytes())))
+            }
+
+            Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err.into()))),
+
+            Poll::Ready(None) => Poll::Ready(None),
+
+            Poll::Pending => {
+                let interval = match this.keep_alive {
+                    Some(interval) => *interval,
+                    None => return Poll::Pending,
+                };
+
+                let timer = this
+                    .keep_alive_timer
+                    .get_or_insert_with(|| Box::pin(sleep(interval)));
+
+                if timer.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                timer.as_mut().reset(Instant::now() + interval);
+
+                Poll::Ready(Some(Ok(Bytes::from_static(b": keep-alive\n\n"))))
+            }
+        }
+    }
+}
+
+impl<S, E> Responder for Sse<S>
+where
+    S: Stream<Item = Result<SseEvent, E>> + 'static,
+    E: Into<BoxError> + 'static,
+{
+    type Body = Self;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let mut res = HttpResponse::with_body(StatusCode::OK, self);
+
+        res.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        res.headers_mut().insert(
+            header::CACHE_CONTROL,
+            CacheControl::new().no_cache().try_into_value().unwrap(),
+        );
+
+        #[cfg(feature = "__compress")]
+        res.extensions_mut()
+            .insert(crate::middleware::DisableCompress);
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::poll_fn;
+
+    use futures_util::stream;
+
+    use super::*;
+    use crate::{body, test::TestRequest};
+
+    #[test]
+    fn event_format() {
+        assert_eq!(SseEvent::data("hello").into_bytes(), "data: hello\n\n");
+        assert_eq!(SseEvent::data("").into_bytes(), "data: \n\n");
+
+        let event = SseEvent::data("line 1\r\nline 2\nline 3")
+            .event("update\n")
+            .id("42")
+            .retry(Duration::from_secs(3));
+        assert_eq!(
+            event.into_bytes(),
+            "event: update\nid: 42\nretry: 3000\ndata: line 1\ndata: line 2\ndata: line 3\n\n"
+        );
+
+        // a lone CR must not let data inject other fields
+        assert_eq!(
+            SseEvent::data("x\revent: admin").into_bytes(),
+            "data: x\ndata: event: admin\n\n"
+        );
+        assert_eq!(
+            SseEvent::data("a\r\rb\n").into_bytes(),
+            "data: a\ndata: \ndata: b\ndata: \n\n"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn responder() {
+        let req = TestRequest::default().to_http_request();
+
+        let events = stream::iter([
+            Ok::<_, BoxError>(SseEvent::data("a")),
+            Ok(SseEvent::data("b").event("note")),
+        ]);
+        let res = Sse::new(events).respond_to(&req);
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        assert_eq!(
+            res.headers().get(header::CACHE_CONTROL).unwrap(),
+            "no-cache"
+        );
+
+        let body = body::to_bytes(res.into_body()).await.unwrap();
+        assert_eq!(body, "data: a\n\nevent: note\ndata: b\n\n");
+    }
+
+    #[actix_rt::test]
+    async fn keep_alive() {
+        let events = stream::pending::<Result<SseEvent, BoxError>>();
+        let body = Sse::new(events).keep_alive(Duration::from_millis(10));
+        actix_rt::pin!(body);
+
+        for _ in 0..2 {
+            let chunk = poll_fn(|cx| body.as_mut().poll_next(cx)).await;
+            assert_eq!(chunk.unwrap().unwrap(), ": keep-alive\n\n");
+        }
+    }
+}
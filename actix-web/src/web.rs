@@ -8,11 +8,13 @@
 //! - [`Query`]: URL query parameters
 //! - [`Header`]: Typed header
 //! - [`Json`]: JSON payload
+//! - `MsgPack`: MessagePack payload (requires `msgpack` feature)
 //! - [`Form`]: URL-encoded payload
 //! - [`Bytes`]: Raw payload
 //!
 //! # Responders
 //! - [`Json`]: JSON response
+//! - `MsgPack`: MessagePack response (requires `msgpack` feature)
 //! - [`Form`]: URL-encoded response
 //! - [`Bytes`]: Raw bytes response
 //! - [`Redirect`](Redirect::to): Convenient redirect responses
@@ -153,3 +153,96 @@ async fn test_start_ssl() {
 
     srv.stop(false).await;
 }
+
+#[actix_rt::test]
+#[cfg(feature = "openssl")]
+async fn test_on_connect_ssl_peer_cert() {
+    use std::any::Any;
+
+    use actix_tls::accept::openssl::TlsStream;
+    use actix_web::{dev::Extensions, rt::net::TcpStream, HttpRequest};
+    use openssl::{
+        nid::Nid,
+        pkey::PKey,
+        ssl::{SslConnector, SslMethod, SslVerifyMode},
+        x509::X509,
+    };
+
+    #[derive(Clone)]
+    struct PeerCommonName(String);
+
+    fn peer_cert(conn: &dyn Any, data: &mut Extensions) {
+        let tls = conn.downcast_ref::<TlsStream<TcpStream>>().unwrap();
+
+        if let Some(cert) = tls.ssl().peer_certificate() {
+            let name = cert.subject_name().entries_by_nid(Nid::COMMONNAME).next();
+            let name = String::from_utf8(name.unwrap().data().as_slice().to_vec()).unwrap();
+            data.insert(PeerCommonName(name));
+        }
+    }
+
+    let addr = actix_test::unused_addr();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        actix_rt::System::new()
+            .block_on(async {
+                let mut builder = ssl_acceptor();
+
+                // request a client certificate, accepting any that is presented
+                builder.set_verify_callback(SslVerifyMode::PEER, |_, _| true);
+
+                let srv = HttpServer::new(|| {
+                    App::new().default_service(web::to(|req: HttpRequest| async move {
+                        match req.conn_data::<PeerCommonName>() {
+                            Some(PeerCommonName(name)) => HttpResponse::Ok().body(name.clone()),
+                            None => HttpResponse::Unauthorized().finish(),
+                        }
+                    }))
+                })
+                .workers(1)
+                .shutdown_timeout(1)
+                .system_exit()
+                .disable_signals()
+                .on_connect(peer_cert)
+                .bind_openssl(format!("{}", addr), builder)
+                .unwrap();
+
+                let srv = srv.run();
+                tx.send(srv.handle()).unwrap();
+
+                srv.await
+            })
+            .unwrap()
+    });
+    let srv = rx.recv().unwrap();
+
+    let rcgen::CertifiedKey { cert, key_pair } =
+        rcgen::generate_simple_self_signed(["client.example".to_owned()]).unwrap();
+    let cert = X509::from_pem(cert.pem().as_bytes()).unwrap();
+    let key = PKey::private_key_from_pem(key_pair.serialize_pem().as_bytes()).unwrap();
+
+    let mut builder = SslConnector::builder(SslMethod::tls()).unwrap();
+    builder.set_verify(SslVerifyMode::NONE);
+    builder.set_certificate(&cert).unwrap();
+    builder.set_private_key(&key).unwrap();
+
+    let client = awc::Client::builder()
+        .connector(
+            awc::Connector::new()
+                .openssl(builder.build())
+                .timeout(Duration::from_millis(100)),
+        )
+        .finish();
+
+    let mut response = client
+        .get(format!("https://{}", addr))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    // common name that rcgen gives self-signed certificates
+    assert_eq!(response.body().await.unwrap(), "rcgen self signed cert");
+
+    srv.stop(false).await;
+}
@@ -69,13 +69,14 @@
 //! outgoing requests, allowing servers to select their `Content-Encoding` accordingly.
 //!
 //! Feature flags enable these codecs according to the table below. By default, all `compress-*`
-//! features are enabled.
+//! features are enabled, except for `compress-lz4`.
 //!
 //! | Feature           | Codecs        |
 //! | ----------------- | ------------- |
 //! | `compress-brotli` | brotli        |
 //! | `compress-gzip`   | gzip, deflate |
 //! | `compress-zstd`   | zstd          |
+//! | `compress-lz4`    | lz4           |
 //!
 //! [iana-encodings]: https://www.iana.org/assignments/http-parameters/http-parameters.xhtml#content-coding
 //!
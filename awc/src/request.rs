@@ -477,6 +477,9 @@ impl ClientRequest {
                 #[cfg(feature = "compress-zstd")]
                 encoding.push("zstd");
 
+                #[cfg(feature = "compress-lz4")]
+                encoding.push("lz4");
+
                 assert!(
                     !encoding.is_empty(),
                     "encoding can not be empty unless __compress feature has been explicitly enabled"
@@ -98,7 +98,12 @@ where
         if let Some(len) = this.length.take() {
             let body = Option::as_ref(&this.body).unwrap();
             if len > body.limit {
-                return Poll::Ready(Err(JsonPayloadError::Payload(PayloadError::Overflow)));
+                return Poll::Ready(Err(JsonPayloadError::Payload(
+                    PayloadError::OverflowWithSize {
+                        limit: body.limit,
+                        read: len,
+                    },
+                )));
             }
         }
 
@@ -129,8 +134,11 @@ mod tests {
 
     fn json_eq(err: JsonPayloadError, other: JsonPayloadError) -> bool {
         match err {
-            JsonPayloadError::Payload(PayloadError::Overflow) => {
-                matches!(other, JsonPayloadError::Payload(PayloadError::Overflow))
+            JsonPayloadError::Payload(PayloadError::OverflowWithSize { .. }) => {
+                matches!(
+                    other,
+                    JsonPayloadError::Payload(PayloadError::OverflowWithSize { .. })
+                )
             }
             JsonPayloadError::ContentType => matches!(other, JsonPayloadError::ContentType),
             _ => false,
@@ -166,7 +174,10 @@ mod tests {
         let json = JsonBody::<_, MyObject>::new(&mut req).limit(100).await;
         assert!(json_eq(
             json.err().unwrap(),
-            JsonPayloadError::Payload(PayloadError::Overflow)
+            JsonPayloadError::Payload(PayloadError::OverflowWithSize {
+                limit: 100,
+                read: 10000
+            })
         ));
 
         let mut req = TestResponse::default()
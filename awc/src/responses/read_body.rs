@@ -39,7 +39,10 @@ where
 
         while let Some(chunk) = ready!(this.stream.as_mut().poll_next(cx)?) {
             if (this.buf.len() + chunk.len()) > *this.limit {
-                return Poll::Ready(Err(PayloadError::Overflow));
+                return Poll::Ready(Err(PayloadError::OverflowWithSize {
+                    limit: *this.limit,
+                    read: this.buf.len() + chunk.len(),
+                }));
             }
 
             this.buf.extend_from_slice(&chunk);
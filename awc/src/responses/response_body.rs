@@ -95,7 +95,10 @@ where
         if let Some(len) = this.length.take() {
             let body = Option::as_ref(&this.body).unwrap();
             if len > body.limit {
-                return Poll::Ready(Err(PayloadError::Overflow));
+                return Poll::Ready(Err(PayloadError::OverflowWithSize {
+                    limit: body.limit,
+                    read: len,
+                }));
             }
         }
 
@@ -124,7 +127,7 @@ mod tests {
 
         let mut req = TestResponse::with_header((header::CONTENT_LENGTH, "10000000")).finish();
         match req.body().await.err().unwrap() {
-            PayloadError::Overflow => {}
+            PayloadError::Overflow | PayloadError::OverflowWithSize { .. } => {}
             _ => unreachable!("error"),
         }
 
@@ -137,7 +140,7 @@ mod tests {
             .set_payload(Bytes::from_static(b"11111111111111"))
             .finish();
         match req.body().limit(5).await.err().unwrap() {
-            PayloadError::Overflow => {}
+            PayloadError::Overflow | PayloadError::OverflowWithSize { .. } => {}
             _ => unreachable!("error"),
         }
     }